@@ -41,7 +41,7 @@ impl Playground {
         infinite
     }
 
-    fn view(&self) -> Element<Message> {
+    fn view(&self) -> Element<'_, Message> {
         let content = self.graph().width(900).height(750);
         //let content = text("Work In Progress");
 
@@ -110,7 +110,7 @@ impl Scale {
         let step = 3.0;
 
         let exponent = (zoom_level / step).trunc();
-        let sub_index = (zoom_level.abs() as usize % Self::SCALE_FACTORS.len()) as usize;
+        let sub_index = zoom_level.abs() as usize % Self::SCALE_FACTORS.len();
 
         let factor = Self::SCALE_FACTORS[sub_index];
 
@@ -366,11 +366,9 @@ impl Program<Message, Theme, Renderer> for Graph {
         &self,
         state: &Self::State,
         theme: &Theme,
-        bounds: iced::Rectangle,
-        _cursor: iced::mouse::Cursor,
-        _infinite_cursor: iced::mouse::Cursor,
-        center: iced::Point,
+        context: DrawContext,
     ) -> Vec<Buffer<'a>> {
+        let DrawContext { bounds, center, .. } = context;
         use iced::widget::canvas::Stroke;
 
         let color2 = color!(128, 0, 128);
@@ -393,20 +391,15 @@ impl Program<Message, Theme, Renderer> for Graph {
                 ..Default::default()
             });
 
-            let path = Path::circle((0., 0.).into(), 5.0.into());
-            buffer.fill(path, color2);
+            buffer.fill_circle((0., 0.), 5.0, color2);
 
-            let path = Path::circle((150., 150.).into(), 15.0.into());
-            buffer.fill(path, color1);
+            buffer.fill_circle((150., 150.), 15.0, color1);
 
-            let path = Path::circle((150., -150.).into(), 15.0.into());
-            buffer.fill(path, color1);
+            buffer.fill_circle((150., -150.), 15.0, color1);
 
-            let path = Path::circle((-150., 150.).into(), 15.0.into());
-            buffer.fill(path, color1);
+            buffer.fill_circle((-150., 150.), 15.0, color1);
 
-            let path = Path::circle((-150., -150.).into(), 15.0.into());
-            buffer.fill(path, color1);
+            buffer.fill_circle((-150., -150.), 15.0, color1);
 
             buffer.fill_rounded_rectangle((120.0, 120.), (150., 100.), 10., color);
 
@@ -514,12 +507,12 @@ impl Program<Message, Theme, Renderer> for Graph {
     fn on_scroll(
         &self,
         state: &mut Self::State,
-        bounds: iced::Rectangle,
-        _cursor: iced::mouse::Cursor,
-        _infinite_cursor: iced::mouse::Cursor,
+        context: InputContext,
         scroll: Vector,
         diff: Vector,
     ) -> Option<Message> {
+        let InputContext { bounds, .. } = context;
+
         state.canvas_offset = scroll;
         if state.flag {
             state.flag = false;
@@ -533,7 +526,7 @@ impl Program<Message, Theme, Renderer> for Graph {
             let steps = (scroll.x / x_width).trunc();
             state.x.scroll(steps);
 
-            scroll.x = scroll.x % x_width;
+            scroll.x %= x_width;
         }
 
         state.scroll = scroll;
@@ -549,9 +542,7 @@ impl Program<Message, Theme, Renderer> for Graph {
     fn on_scroll_reset(
         &self,
         state: &mut Self::State,
-        _bounds: iced::Rectangle,
-        _cursor: iced::mouse::Cursor,
-        _infinite_cursor: iced::mouse::Cursor,
+        _context: InputContext,
         scroll: Vector,
     ) -> Option<Message> {
         state.scroll = scroll;
@@ -564,13 +555,16 @@ impl Program<Message, Theme, Renderer> for Graph {
     fn on_zoom(
         &self,
         state: &mut Self::State,
-        bounds: iced::Rectangle,
-        _cursor: iced::mouse::Cursor,
-        infinite_cursor: iced::mouse::Cursor,
+        context: InputContext,
         focal_point: Point,
         zoom: f32,
         diff: f32,
     ) -> Option<Message> {
+        let InputContext {
+            bounds,
+            infinite_cursor,
+            ..
+        } = context;
         let is_origin_zoom = focal_point == Point::ORIGIN;
 
         state.flag = !is_origin_zoom;
@@ -600,9 +594,7 @@ impl Program<Message, Theme, Renderer> for Graph {
     fn on_zoom_reset(
         &self,
         state: &mut Self::State,
-        _bounds: iced::Rectangle,
-        _cursor: iced::mouse::Cursor,
-        _infinite_cursor: iced::mouse::Cursor,
+        _context: InputContext,
         zoom: f32,
     ) -> Option<Message> {
         state.scale = zoom;
@@ -614,17 +606,17 @@ impl Program<Message, Theme, Renderer> for Graph {
     fn overlay<'a>(
         &self,
         state: &'a mut Self::State,
-        bounds: iced::Rectangle,
-        _infinite_cursor: Point,
-        translation: Vector,
+        context: OverlayContext,
     ) -> Option<iced::advanced::overlay::Element<'a, Message, Theme, Renderer>> {
+        let OverlayContext {
+            bounds,
+            viewport,
+            translation,
+            ..
+        } = context;
         let width = 150.0;
-        let translation = {
-            let other = Vector::new(bounds.width - width, 0.0);
-            other + translation
-        };
-
-        let position = bounds.position() + translation;
+        let position = OverlayAnchor::Pane(Point::new(bounds.width - width, 0.0))
+            .position(&viewport, translation);
 
         let overlay = Overlay::new(state, position, width);
         let overlay = overlay::Element::new(Box::new(overlay));
@@ -27,7 +27,7 @@ impl Playground {
         match message {}
     }
 
-    fn view(&self) -> Element<Message> {
+    fn view(&self) -> Element<'_, Message> {
         let content = Infinite::new(Fractal).width(900).height(750);
 
         let content = center(content).width(Length::Fill).height(Length::Fill);
@@ -87,11 +87,9 @@ impl Program<Message, Theme, Renderer> for Fractal {
         &self,
         state: &Self::State,
         theme: &Theme,
-        bounds: Rectangle,
-        _cursor: iced::mouse::Cursor,
-        _infinite_cursor: iced::mouse::Cursor,
-        _center: Point,
+        context: DrawContext,
     ) -> Vec<Buffer<'a>> {
+        let bounds = context.bounds;
         let mut buffer = Buffer::new();
 
         let width = bounds.width / 4.0;
@@ -112,9 +110,7 @@ impl Program<Message, Theme, Renderer> for Fractal {
     fn on_zoom(
         &self,
         state: &mut Self::State,
-        _bounds: Rectangle,
-        _cursor: iced::mouse::Cursor,
-        _infinite_cursor: iced::mouse::Cursor,
+        _context: InputContext,
         _focal_point: Point,
         _zoom: f32,
         diff: f32,
@@ -129,9 +125,7 @@ impl Program<Message, Theme, Renderer> for Fractal {
     fn on_zoom_reset(
         &self,
         state: &mut Self::State,
-        _bounds: Rectangle,
-        _cursor: iced::mouse::Cursor,
-        _infinite_cursor: iced::mouse::Cursor,
+        _context: InputContext,
         _zoom: f32,
     ) -> Option<Message> {
         state.reset();
@@ -34,7 +34,7 @@ impl Playground {
         infinite
     }
 
-    fn view(&self) -> Element<Message> {
+    fn view(&self) -> Element<'_, Message> {
         let content = self.graph().width(900).height(750);
         //let content = text("Work In Progress");
 
@@ -112,7 +112,7 @@ impl Scale {
 
         let exp = exp.floor();
 
-        (base as f32) * 10_f32.powf(exp)
+        base * 10_f32.powf(exp)
     }
 
     fn grow_step(fract: f32, exp: f32) -> (f32, f32) {
@@ -121,7 +121,7 @@ impl Scale {
 
         let base = if exp >= 0. {
             match fract {
-                x if x >= 0. && x < 0.3 => 2,
+                x if (0. ..0.3).contains(&x) => 2,
                 x if x < 0.697 => 5,
                 _ => {
                     exp += 1.;
@@ -305,7 +305,7 @@ impl ZoomState {
                     self.threshold = threshold;
                     self.tracker %= self.threshold;
                     x_scale.zoom(!is_zoom_in);
-                    self.kx = self.kx / kx_delta;
+                    self.kx /= kx_delta;
 
                     self.kind = ZoomKind::ZoomedIn(amt + 1);
                 } else if !is_zoom_in && self.tracker < 0 {
@@ -315,10 +315,10 @@ impl ZoomState {
 
                     self.kx = (self.kx * kx_delta).max(self.og_kx);
                     if amt == 0 {
-                        self.tracker = threshold + self.tracker;
+                        self.tracker += threshold;
                         self.kind = ZoomKind::None;
                     } else {
-                        self.tracker = threshold + self.tracker;
+                        self.tracker += threshold;
                         self.threshold = threshold;
                         self.kind = ZoomKind::ZoomedIn(amt);
                     }
@@ -331,7 +331,7 @@ impl ZoomState {
                     self.threshold = threshold;
                     self.tracker %= self.threshold;
                     x_scale.zoom(!is_zoom_in);
-                    self.kx = self.kx * kx_delta;
+                    self.kx *= kx_delta;
 
                     self.kind = ZoomKind::ZoomedOut(amt + 1)
                 } else if is_zoom_in && self.tracker > 0 {
@@ -341,10 +341,10 @@ impl ZoomState {
 
                     self.kx = (self.kx / kx_delta).max(self.og_kx);
                     if amt == 0 {
-                        self.tracker = -threshold + self.tracker;
+                        self.tracker += -threshold;
                         self.kind = ZoomKind::None;
                     } else {
-                        self.tracker = -threshold + self.tracker;
+                        self.tracker += -threshold;
                         self.threshold = threshold;
                         self.kind = ZoomKind::ZoomedOut(amt);
                     }
@@ -359,7 +359,7 @@ impl<Message> Program<Message, Theme, Renderer> for Graph {
 
     fn init_state(&self) -> Self::State {
         GraphState {
-            x_scale: Scale::new(10.0.into()),
+            x_scale: Scale::new(10.0),
             scroll: iced::Vector::new(0., 0.),
             zoom_state: ZoomState::default(),
         }
@@ -369,11 +369,9 @@ impl<Message> Program<Message, Theme, Renderer> for Graph {
         &self,
         state: &Self::State,
         theme: &Theme,
-        bounds: iced::Rectangle,
-        _cursor: iced::mouse::Cursor,
-        _infinite_cursor: iced::mouse::Cursor,
-        center: iced::Point,
+        context: DrawContext,
     ) -> Vec<Buffer<'a>> {
+        let DrawContext { bounds, center, .. } = context;
         use iced::widget::canvas::Stroke;
         let color2 = color!(128, 0, 128);
         let color = color!(0, 128, 128);
@@ -395,8 +393,7 @@ impl<Message> Program<Message, Theme, Renderer> for Graph {
                 ..Default::default()
             });
 
-            let path = Path::circle((0., 0.).into(), 5.0.into());
-            buffer.fill(path, color2);
+            buffer.fill_circle((0., 0.), 5.0, color2);
 
             buffer.fill_rounded_rectangle((120.0, 120.), (150., 100.), 10., color);
 
@@ -442,7 +439,7 @@ impl<Message> Program<Message, Theme, Renderer> for Graph {
             let pad = 18.0;
 
             for point in state.range() {
-                let x = width * point as f32;
+                let x = width * point;
                 let _spacing = if x == 0. {
                     6.5
                 } else if x < 0. {
@@ -514,12 +511,11 @@ impl<Message> Program<Message, Theme, Renderer> for Graph {
     fn on_scroll(
         &self,
         state: &mut Self::State,
-        bounds: iced::Rectangle,
-        _cursor: iced::mouse::Cursor,
-        _infinite_cursor: iced::mouse::Cursor,
+        context: InputContext,
         _scroll: iced::Vector,
         diff: iced::Vector,
     ) -> Option<Message> {
+        let InputContext { bounds, .. } = context;
         let scroll = state.scroll;
         let mut scroll = scroll + diff;
 
@@ -529,7 +525,7 @@ impl<Message> Program<Message, Theme, Renderer> for Graph {
             let steps = (scroll.x / x_width).trunc();
             state.x_scale.scroll(steps);
 
-            scroll.x = scroll.x % x_width;
+            scroll.x %= x_width;
         }
 
         state.scroll = scroll;
@@ -540,9 +536,7 @@ impl<Message> Program<Message, Theme, Renderer> for Graph {
     fn on_scroll_reset(
         &self,
         state: &mut Self::State,
-        _bounds: iced::Rectangle,
-        _cursor: iced::mouse::Cursor,
-        _infinite_cursor: iced::mouse::Cursor,
+        _context: InputContext,
         scroll: iced::Vector,
     ) -> Option<Message> {
         state.scroll = scroll;
@@ -553,9 +547,7 @@ impl<Message> Program<Message, Theme, Renderer> for Graph {
     fn on_zoom(
         &self,
         state: &mut Self::State,
-        _bounds: iced::Rectangle,
-        _cursor: iced::mouse::Cursor,
-        _infinite_cursor: iced::mouse::Cursor,
+        _context: InputContext,
         _focal_point: Point,
         zoom: f32,
         diff: f32,
@@ -568,9 +560,7 @@ impl<Message> Program<Message, Theme, Renderer> for Graph {
     fn on_zoom_reset(
         &self,
         state: &mut Self::State,
-        _bounds: iced::Rectangle,
-        _cursor: iced::mouse::Cursor,
-        _infinite_cursor: iced::mouse::Cursor,
+        _context: InputContext,
         _zoom: f32,
     ) -> Option<Message> {
         state.x_scale.reset();
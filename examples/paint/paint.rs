@@ -648,7 +648,7 @@ impl Paint {
         }
     }
 
-    fn view(&self) -> Element<Message> {
+    fn view(&self) -> Element<'_, Message> {
         let canvas = self.canvas.view(&self.drawings).map(Message::CanvasMessage);
 
         let stage = row!(self.side_panel(), canvas)
@@ -664,20 +664,22 @@ impl Paint {
 
 mod canvas {
 
-    use infinite;
-
     use iced::{
+        advanced::Clipboard,
         color, mouse,
         widget::canvas::{self, stroke, LineDash, Path, Stroke, Text},
         Color, Element, Fill, Point, Rectangle, Size, Theme, Vector,
     };
 
+    use infinite::{geometry, text_edit::TextEditor};
+
     use super::{Action, Shapes, Tool};
 
     const TEXT_LEFT_PADDING: f32 = 0.005;
     const TEXT_TOP_PADDING: f32 = 0.005;
     const SHAPE_DEFAULT_THICKNESS: f32 = 3.0;
     const DEFAULT_ERASER_WIDTH: f32 = 20.0;
+    const FREEFORM_TOLERANCE: f32 = 0.5;
 
     #[derive(Default, Debug)]
     pub struct State {
@@ -716,7 +718,7 @@ mod canvas {
 
         pub fn view<'a>(&'a self, paintings: &'a [Painting]) -> Element<'a, CanvasMessage> {
             infinite::Infinite::new(PaintingCanvas {
-                state: &self,
+                state: self,
                 paintings,
             })
             .width(Fill)
@@ -740,10 +742,10 @@ mod canvas {
         fn mouse_interaction(
             &self,
             state: &Self::State,
-            bounds: Rectangle,
-            cursor: mouse::Cursor,
-            _infite_cursor: mouse::Cursor,
+            context: infinite::InputContext,
         ) -> mouse::Interaction {
+            let infinite::InputContext { bounds, cursor, .. } = context;
+
             match state {
                 Some(Pending::Text(TextPending::One { .. })) if cursor.is_over(bounds) => {
                     mouse::Interaction::Text
@@ -761,12 +763,13 @@ mod canvas {
             &self,
             state: &mut Self::State,
             event: infinite::event::Event,
-            _bounds: Rectangle,
-            _cursor: mouse::Cursor,
-            infinite_cursor: mouse::Cursor,
+            context: infinite::InputContext,
+            clipboard: &mut dyn Clipboard,
         ) -> (infinite::event::Status, Option<CanvasMessage>) {
             use infinite::event::{self, Event};
 
+            let infinite_cursor = context.infinite_cursor;
+
             if let Some(Pending::Erase(_)) = &state {
                 if !self.state.is_erasing_tool {
                     state.take();
@@ -776,29 +779,12 @@ mod canvas {
             match (infinite_cursor.position(), state.clone()) {
                 (
                     Some(cursor_position),
-                    Some(Pending::Text(TextPending::Typing {
-                        from,
-                        to,
-                        text: mut state_text,
-                    })),
+                    Some(Pending::Text(TextPending::Typing { from, to, editor })),
                 ) if self.state.current_action == Action::Tool(Tool::Text) => match event {
-                    Event::Keyboard(iced::keyboard::Event::KeyPressed {
-                        text: Some(new_text),
-                        ..
-                    }) => {
-                        if &new_text == "\u{8}" {
-                            state_text.pop();
-                        } else {
-                            state_text.push_str(&new_text);
+                    Event::Keyboard(_) => {
+                        if let Some(Pending::Text(TextPending::Typing { editor, .. })) = state {
+                            return (editor.update(&event, clipboard).status(), None);
                         }
-
-                        state.replace(Pending::Text(TextPending::Typing {
-                            from,
-                            to,
-                            text: state_text,
-                        }));
-
-                        return (event::Status::Captured, None);
                     }
                     Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
                         let bounds = Rectangle::new(from, Size::new(to.x - from.x, from.y - to.y));
@@ -807,7 +793,7 @@ mod canvas {
                             let painting = Painting::Text {
                                 top_left: position,
                                 bottom_right: Point::new(to.x, from.y),
-                                text: state_text.clone(),
+                                text: editor.text().to_string(),
                                 color: self.state.color,
                                 scale: self.state.scale,
                             };
@@ -825,30 +811,11 @@ mod canvas {
                     _ => {}
                 },
 
-                (
-                    _,
-                    Some(Pending::Text(TextPending::Typing {
-                        text: mut state_text,
-                        from,
-                        to,
-                    })),
-                ) => match event {
-                    Event::Keyboard(iced::keyboard::Event::KeyPressed {
-                        text: Some(new_text),
-                        ..
-                    }) => {
-                        state_text.push_str(&new_text);
-
-                        state.replace(Pending::Text(TextPending::Typing {
-                            from,
-                            to,
-                            text: state_text,
-                        }));
-
-                        return (event::Status::Captured, None);
+                (_, Some(Pending::Text(TextPending::Typing { .. }))) => {
+                    if let Some(Pending::Text(TextPending::Typing { editor, .. })) = state {
+                        return (editor.update(&event, clipboard).status(), None);
                     }
-                    _ => {}
-                },
+                }
 
                 (Some(cursor_position), Some(Pending::FreeForm(prev_points))) => match event {
                     Event::Mouse(mouse::Event::CursorMoved { .. }) => {
@@ -860,9 +827,9 @@ mod canvas {
                             } else {
                                 match points.pop() {
                                     Some(prev) => {
-                                        if prev.x == cursor_position.x {
-                                            points.push(cursor_position);
-                                        } else if prev.y == cursor_position.y {
+                                        if prev.x == cursor_position.x
+                                            || prev.y == cursor_position.y
+                                        {
                                             points.push(cursor_position);
                                         } else {
                                             points.push(prev);
@@ -884,7 +851,7 @@ mod canvas {
                     Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
                         let painting = Painting::new_freeform(
                             self.state.current_action,
-                            prev_points.clone(),
+                            geometry::simplify(&prev_points, FREEFORM_TOLERANCE),
                             self.state.color,
                             self.state.scale,
                         );
@@ -999,7 +966,7 @@ mod canvas {
                                 let typing = Pending::Text(TextPending::Typing {
                                     from,
                                     to,
-                                    text: String::default(),
+                                    editor: TextEditor::default(),
                                 });
 
                                 state.replace(typing);
@@ -1139,7 +1106,7 @@ mod canvas {
 
                             return (event::Status::Captured, Some(painting.into()));
                         }
-                        Some(Pending::Text(TextPending::Typing { from, to, text }))
+                        Some(Pending::Text(TextPending::Typing { from, to, editor }))
                             if self.state.current_action == Action::Tool(Tool::Text) =>
                         {
                             let bounds =
@@ -1149,7 +1116,7 @@ mod canvas {
                                 let painting = Painting::Text {
                                     top_left: position,
                                     bottom_right: Point::new(to.x, from.y),
-                                    text: text.clone(),
+                                    text: editor.text().to_string(),
                                     color: self.state.color,
                                     scale: self.state.scale,
                                 };
@@ -1192,21 +1159,23 @@ mod canvas {
                 _ => {}
             };
 
-            return (event::Status::Ignored, None);
+            (event::Status::Ignored, None)
         }
 
         fn draw<'a>(
             &self,
             state: &Self::State,
             theme: &Theme,
-            bounds: Rectangle,
-            _cursor: mouse::Cursor,
-            infinite_cursor: mouse::Cursor,
-            _center: Point,
+            context: infinite::DrawContext,
         ) -> Vec<infinite::Buffer<'a>> {
+            let infinite::DrawContext {
+                bounds,
+                infinite_cursor,
+                ..
+            } = context;
             let mut buffer = infinite::Buffer::new();
 
-            Painting::draw_all(&self.paintings, &mut buffer, bounds, theme);
+            Painting::draw_all(self.paintings, &mut buffer, bounds, theme);
 
             if let Some(pending) = state {
                 vec![
@@ -1384,7 +1353,7 @@ mod canvas {
             }
         }
 
-        fn draw_all<'a>(
+        fn draw_all(
             paintings: &[Self],
             buffer: &mut infinite::Buffer<'_>,
             bounds: Rectangle,
@@ -1461,9 +1430,15 @@ mod canvas {
                 return;
             }
 
+            buffer.draw_text(Text {
+                content: text,
+                ..Painting::text_style(bounds, top_left, color, scale)
+            });
+        }
+
+        fn text_style(bounds: Rectangle, top_left: Point, color: Color, scale: f32) -> Text {
             let size = (16.0 * scale.max(0.1)).into();
 
-            //dbg!(top_left);
             let position = {
                 let left = bounds.width * TEXT_LEFT_PADDING;
                 let top = bounds.height * TEXT_TOP_PADDING;
@@ -1471,16 +1446,13 @@ mod canvas {
                 Point::new(top_left.x + left, top_left.y - top)
             };
 
-            let text = Text {
-                content: text.clone(),
+            Text {
                 position,
                 color,
                 size,
                 shaping: iced::widget::text::Shaping::Advanced,
                 ..Default::default()
-            };
-
-            buffer.draw_text(text);
+            }
         }
 
         fn draw_bezier(
@@ -1553,10 +1525,9 @@ mod canvas {
 
             let radius = center.distance(to);
 
-            let cirlce = Path::circle(center, radius);
-
-            buffer.stroke(
-                cirlce,
+            buffer.stroke_circle(
+                center,
+                radius,
                 Stroke::default()
                     .with_width(SHAPE_DEFAULT_THICKNESS * scale)
                     .with_color(color),
@@ -1571,18 +1542,10 @@ mod canvas {
             scale: f32,
         ) {
             let scale = SHAPE_DEFAULT_THICKNESS * scale;
-            let triangle = Path::new(|builder| {
-                let left_x = (right.x - top.x) * 2.0;
-                let left = Point::new(right.x - left_x, right.y);
-
-                builder.move_to(top);
-                builder.line_to(right);
-                builder.line_to(left);
-                builder.line_to(top);
-            });
+            let left = Point::new(right.x - (right.x - top.x) * 2.0, right.y);
 
-            buffer.stroke(
-                triangle,
+            buffer.stroke_polygon(
+                &[top, right, left],
                 Stroke::default().with_color(color).with_width(scale),
             );
         }
@@ -1596,22 +1559,20 @@ mod canvas {
         ) {
             let scale = SHAPE_DEFAULT_THICKNESS * scale;
 
-            let bestagon = Path::new(|builder| {
-                let x_diff = right.x - top.x;
-                let y_diff = right.y - top.y;
+            let x_diff = right.x - top.x;
+            let y_diff = right.y - top.y;
 
-                builder.move_to(top);
-                builder.line_to(right);
-                builder.line_to(Point::new(right.x, right.y + y_diff));
-                builder.line_to(Point::new(right.x - x_diff, right.y + (y_diff * 2.0)));
-                builder.line_to(Point::new(right.x - (x_diff * 2.0), right.y + y_diff));
-                builder.line_to(Point::new(right.x - (x_diff * 2.0), right.y));
+            let bestagon = [
+                top,
+                right,
+                Point::new(right.x, right.y + y_diff),
+                Point::new(right.x - x_diff, right.y + (y_diff * 2.0)),
+                Point::new(right.x - (x_diff * 2.0), right.y + y_diff),
+                Point::new(right.x - (x_diff * 2.0), right.y),
+            ];
 
-                builder.line_to(top);
-            });
-
-            buffer.stroke(
-                bestagon,
+            buffer.stroke_polygon(
+                &bestagon,
                 Stroke::default().with_color(color).with_width(scale),
             );
         }
@@ -1644,27 +1605,13 @@ mod canvas {
                 }
             };
 
-            let freeform = Path::new(|builder| {
-                for (idx, point) in points.iter().enumerate() {
-                    let point = *point;
-                    if idx == 0 {
-                        builder.move_to(point);
-                    }
-
-                    builder.line_to(point);
-                }
-            });
-
-            frame.stroke(freeform, stroke);
+            frame.stroke(geometry::smooth(points), stroke);
         }
 
         pub fn is_selected(&self, bounds: Rectangle) -> bool {
             match self {
                 Self::Line { from, to, .. } => {
-                    let size = Size::new(to.x - from.x, to.y - from.y);
-                    let own = Rectangle::new(*from, size);
-
-                    bounds.intersects(&own) || bounds.is_within(&own)
+                    geometry::stroke_intersects(&Path::line(*from, *to), bounds)
                 }
                 Self::Text {
                     top_left,
@@ -1679,20 +1626,12 @@ mod canvas {
                 Self::Bezier {
                     from, to, control, ..
                 } => {
-                    let (x1, y1) = (
-                        f32::min(from.x, to.x.min(control.x)),
-                        f32::min(from.y, to.y.min(control.y)),
-                    );
-                    let (x2, y2) = (
-                        f32::max(from.x, to.x.max(control.x)),
-                        f32::max(from.y, to.y.max(control.y)),
-                    );
-
-                    let size = Size::new(x2 - x1, y2 - y1);
-
-                    let own = Rectangle::new(Point::new(x1, y2), size);
+                    let curve = Path::new(|builder| {
+                        builder.move_to(*from);
+                        builder.quadratic_curve_to(*control, *to)
+                    });
 
-                    bounds.intersects(&own) || bounds.is_within(&own)
+                    geometry::stroke_intersects(&curve, bounds)
                 }
                 Self::Rectangle {
                     top_left,
@@ -1746,7 +1685,16 @@ mod canvas {
 
                     bounds.intersects(&own) || bounds.is_within(&own)
                 }
-                Self::FreeForm { points, .. } => points.iter().any(|point| bounds.contains(*point)),
+                Self::FreeForm { points, .. } => {
+                    let freeform = Path::new(|builder| {
+                        for point in points {
+                            builder.line_to(*point);
+                        }
+                    });
+
+                    points.iter().any(|point| bounds.contains(*point))
+                        || geometry::stroke_intersects(&freeform, bounds)
+                }
             }
         }
 
@@ -1819,17 +1767,16 @@ mod canvas {
             let mut buffer = infinite::Buffer::new();
 
             match action {
-                Action::Tool(Tool::Text) => match self {
-                    Self::Text(text) => text.draw(&mut buffer, bounds, cursor, color, scale),
-                    _ => {}
-                },
-                Action::Tool(Tool::Brush) => match self {
-                    Self::FreeForm(points) => {
+                Action::Tool(Tool::Text) => {
+                    if let Self::Text(text) = self {
+                        text.draw(&mut buffer, bounds, cursor, color, scale)
+                    }
+                }
+                Action::Tool(Tool::Brush) => {
+                    if let Self::FreeForm(points) = self {
                         Painting::draw_freeform(&mut buffer, points, color, scale, false)
                     }
-
-                    _ => {}
-                },
+                }
                 Action::Shape(Shapes::Bezier) => match self {
                     Self::One { from } => {
                         if let Some(to) = cursor.position() {
@@ -1910,26 +1857,24 @@ mod canvas {
                     }
                     _ => {}
                 },
-                Action::Tool(Tool::Pencil) => match self {
-                    Self::FreeForm(points) => {
+                Action::Tool(Tool::Pencil) => {
+                    if let Self::FreeForm(points) = self {
                         Painting::draw_freeform(&mut buffer, points, color, scale, true)
                     }
-
-                    _ => {}
-                },
-                Action::Select => match self {
-                    Self::Selection(selection) => selection.draw(&mut buffer, cursor, color),
-                    _ => {}
-                },
+                }
+                Action::Select => {
+                    if let Self::Selection(selection) = self {
+                        selection.draw(&mut buffer, cursor, color)
+                    }
+                }
                 Action::Tool(Tool::Eraser) => {
                     if cursor.position().is_some() {
-                        match self {
-                            Self::Erase(bounds) => buffer.fill_rectangle(
+                        if let Self::Erase(bounds) = self {
+                            buffer.fill_rectangle(
                                 bounds.position(),
                                 bounds.size(),
                                 color!(225, 29, 72),
-                            ),
-                            _ => {}
+                            )
                         }
                     }
                 }
@@ -1947,7 +1892,7 @@ mod canvas {
         Typing {
             from: Point,
             to: Point,
-            text: String,
+            editor: TextEditor,
         },
     }
 
@@ -1981,17 +1926,15 @@ mod canvas {
                         buffer.stroke_rectangle(bottom_left, size, stroke);
                     }
                 }
-                Self::Typing { from, to, text } => {
+                Self::Typing { from, to, editor } => {
                     let size = Size::new(to.x - from.x, from.y - to.y);
                     let bottom_left = Point::new(from.x, from.y - size.height);
                     buffer.stroke_rectangle(bottom_left, size, stroke);
 
-                    let mut text = text.clone();
-                    text.push_str("▸");
-
                     let position = Point::new(from.x, to.y);
+                    let text = Painting::text_style(bounds, position, color, scale);
 
-                    Painting::draw_text(buffer, bounds, text, position, color, scale);
+                    editor.draw(buffer, text, color.scale_alpha(0.3));
                 }
             }
         }
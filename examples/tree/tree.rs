@@ -1,5 +1,5 @@
 use iced::{
-    application, color, keyboard,
+    application, color,
     widget::{canvas::path, center},
     Element, Length, Padding, Point, Rectangle, Renderer, Theme,
 };
@@ -34,11 +34,10 @@ impl Playground {
     }
 
     fn graph(&self) -> Infinite<'_, Tree, Message, Theme, Renderer> {
-        let infinite = Infinite::new(Tree);
-        infinite
+        Infinite::new(Tree).auto_pan(EdgePanConfig::default())
     }
 
-    fn view(&self) -> Element<Message> {
+    fn view(&self) -> Element<'_, Message> {
         let content = self.graph().width(900).height(750);
 
         let content = center(content).width(Length::Fill).height(Length::Fill);
@@ -193,13 +192,9 @@ impl Node {
     }
 
     fn get_idx_child(&mut self, rec: &[usize], position: usize) -> Option<&mut Self> {
-        let Some(idx) = rec.get(position) else {
-            return None;
-        };
+        let idx = rec.get(position)?;
 
-        let Some(child) = self.children.get_mut(*idx) else {
-            return None;
-        };
+        let child = self.children.get_mut(*idx)?;
 
         if position + 1 >= rec.len() {
             return Some(child);
@@ -284,11 +279,15 @@ impl Node {
             };
 
             //let curve = cubic_bezier(from, join);
-            let (bezier, arrow) = bezier_arrow(from, join);
-            beziers.stroke(bezier, stroke);
-
-            //buffer.stroke(arrow, stroke.with_color(color!(0, 0, 0)));
-            buffer.fill(arrow, color!(72, 121, 183));
+            let (control_a, control_b) = bezier_controls(from, join);
+            beziers.stroke_bezier_arrow(
+                from,
+                control_a,
+                control_b,
+                join,
+                stroke,
+                ArrowStyle::default(),
+            );
         }
     }
 }
@@ -299,7 +298,6 @@ struct Tree;
 #[derive(Debug)]
 struct TreeState {
     nodes: Vec<Node>,
-    modifier: keyboard::Modifiers,
     dragging: bool,
     drag_index: Option<Vec<usize>>,
 }
@@ -337,7 +335,6 @@ impl TreeState {
 
         Self {
             nodes,
-            modifier: keyboard::Modifiers::default(),
             dragging: false,
             drag_index: None,
         }
@@ -376,25 +373,19 @@ impl TreeState {
     }
 
     fn get_dragged(&mut self) -> Option<&mut Node> {
-        let Some(indices) = &self.drag_index else {
-            return None;
-        };
+        let indices = self.drag_index.as_ref()?;
 
         let p = 0;
 
-        let Some(idx) = indices.get(p) else {
-            return None;
-        };
+        let idx = indices.get(p)?;
 
-        let Some(node) = self.nodes.get_mut(*idx) else {
-            return None;
-        };
+        let node = self.nodes.get_mut(*idx)?;
 
         if p + 1 >= indices.len() {
             return Some(node);
         }
 
-        node.get_idx_child(&indices, p + 1)
+        node.get_idx_child(indices, p + 1)
     }
 }
 
@@ -409,10 +400,7 @@ impl Program<Message, Theme, Renderer> for Tree {
         &self,
         state: &Self::State,
         _theme: &Theme,
-        _bounds: iced::Rectangle,
-        _cursor: iced::mouse::Cursor,
-        _infinite_cursor: iced::mouse::Cursor,
-        _center: iced::Point,
+        _context: DrawContext,
     ) -> Vec<Buffer<'a>> {
         let mut buffer = Buffer::new();
         let mut oth = Buffer::new();
@@ -429,14 +417,20 @@ impl Program<Message, Theme, Renderer> for Tree {
         &self,
         state: &mut Self::State,
         event: event::Event,
-        bounds: Rectangle,
-        cursor: iced::mouse::Cursor,
-        infinite_cursor: iced::mouse::Cursor,
+        context: InputContext,
+        _clipboard: &mut dyn iced::advanced::Clipboard,
     ) -> (event::Status, Option<Message>) {
+        let InputContext {
+            bounds,
+            cursor,
+            infinite_cursor,
+            modifiers,
+        } = context;
         use event::{Event, Status};
         use iced::mouse;
 
-        if !cursor.is_over(bounds) {
+        // Drags carry on outside of the canvas while the pointer is captured
+        if !state.dragging && !cursor.is_over(bounds) {
             return (Status::Ignored, None);
         }
 
@@ -445,12 +439,8 @@ impl Program<Message, Theme, Renderer> for Tree {
         };
 
         match event {
-            Event::Keyboard(keyboard::Event::ModifiersChanged(modifier)) => {
-                state.modifier = modifier;
-                (Status::Captured, None)
-            }
             Event::Mouse(mouse::Event::ButtonPressed(button)) => match button {
-                mouse::Button::Left if state.modifier.command() => {
+                mouse::Button::Left if modifiers.command() => {
                     match state.get_mut(cursor_position) {
                         Some(node) => {
                             node.new_child("");
@@ -464,7 +454,7 @@ impl Program<Message, Theme, Renderer> for Tree {
                     state.set_drag(cursor_position);
                     (Status::Captured, None)
                 }
-                mouse::Button::Right if state.modifier.command() => {
+                mouse::Button::Right if modifiers.command() => {
                     match state.get_mut(cursor_position) {
                         Some(node) => {
                             node.collapse();
@@ -500,12 +490,35 @@ impl Program<Message, Theme, Renderer> for Tree {
         }
     }
 
+    fn on_scroll(
+        &self,
+        state: &mut Self::State,
+        context: InputContext,
+        _scroll: iced::Vector,
+        _diff: iced::Vector,
+    ) -> Option<Message> {
+        let InputContext {
+            infinite_cursor, ..
+        } = context;
+
+        // Keeps a dragged node under the cursor while auto panning
+        if let (true, Some(position)) = (state.dragging, infinite_cursor.position()) {
+            if let Some(node) = state.get_dragged() {
+                node.drag(position);
+            }
+        }
+
+        None
+    }
+
+    fn captures_pointer(&self, state: &Self::State) -> bool {
+        state.dragging
+    }
+
     fn mouse_interaction(
         &self,
         state: &Self::State,
-        _bounds: Rectangle,
-        _cursor: iced::mouse::Cursor,
-        _infinite_cursor: iced::mouse::Cursor,
+        _context: InputContext,
     ) -> iced::mouse::Interaction {
         if state.dragging {
             iced::mouse::Interaction::Grabbing
@@ -586,14 +599,11 @@ fn _cubic_bezier(start: Point, end: Point) -> Path {
     builder.build()
 }
 
-fn bezier_arrow(start: Point, end: Point) -> (Path, Path) {
-    let mut builder = path::Builder::new();
+fn bezier_controls(start: Point, end: Point) -> (Point, Point) {
     let x1 = 0.19;
     let y1 = 0.54;
     let x2 = 1.0;
     let y2 = 0.39;
-    let t: f32 = 0.8;
-    let arrow_size = 8.0;
 
     let control_a = {
         let x = start.x + ((end.x - start.x) * x1);
@@ -608,48 +618,5 @@ fn bezier_arrow(start: Point, end: Point) -> (Path, Path) {
         Point::new(x, y)
     };
 
-    builder.move_to(start);
-    builder.bezier_curve_to(control_a, control_b, end);
-
-    let bezier = builder.build();
-    let mut builder = path::Builder::new();
-
-    let bx = (1.0 - t).powi(3) * start.x
-        + 3.0 * (1.0 - t).powi(2) * t * control_a.x
-        + 3.0 * (1.0 - t) * t.powi(2) * control_b.x
-        + t.powi(3) * end.x;
-
-    let by = (1.0 - t).powi(3) * start.y
-        + 3.0 * (1.0 - t).powi(2) * t * control_a.y
-        + 3.0 * (1.0 - t) * t.powi(2) * control_b.y
-        + t.powi(3) * end.y;
-
-    let dx = 3.0 * (1.0 - t).powi(2) * (control_a.x - start.x)
-        + 6.0 * (1.0 - t) * t * (control_b.x - start.x)
-        + 3.0 * t.powi(2) * (end.x - control_b.x);
-    let dy = 3.0 * (1.0 - t).powi(2) * (control_a.y - start.y)
-        + 6.0 * (1.0 - t) * t * (control_b.y - start.y)
-        + 3.0 * t.powi(2) * (end.y - control_b.y);
-
-    //let dx = 3.0 * (end.x - control_b.x);
-    //let dy = 3.0 * (end.y - control_b.y);
-    let len = f32::sqrt(dx * dx + dy * dy);
-
-    let dx = dx / len;
-    let dy = dy / len;
-
-    let left_x = bx - arrow_size * (dx + dy);
-    let left_y = by - arrow_size * (dy - dx);
-    let right_x = bx - arrow_size * (dx - dy);
-    let right_y = by - arrow_size * (dy + dx);
-
-    //
-    builder.move_to(Point::new(bx, by));
-    builder.line_to(Point::new(left_x, left_y));
-    builder.line_to(Point::new(right_x, right_y));
-    builder.line_to(Point::new(bx, by));
-
-    let arrow = builder.build();
-
-    (bezier, arrow)
+    (control_a, control_b)
 }
@@ -6,52 +6,253 @@
 //! Functionality:
 //!
 //! All functionality requires the [`Infinite`] to be hovered on by the
-//! cursor. These are currently implemented:
+//! cursor. Keyboard shortcuts can instead be processed whenever the
+//! [`Infinite`] is focused or visible, see [`KeyboardScope`], and can be
+//! changed, see [`KeyBindings`]. These are currently implemented:
 //!
 //! - Cursor-focused scrolling: Mouse scroll or Cmd(Ctrl) + arrow direction.
 //! - Origin-focused scrolling: Mouse scroll + Shift or Cmd(Ctrl) + Shift + arrow direction.
-//! - Zoom: Shift + Mouse scroll or Shift + arrow direction.
+//! - Drag scrolling: Dragging an empty part of the canvas, see [`Infinite::drag_pan`].
+//! - Edge scrolling: Dragging an item towards the border, see [`Infinite::auto_pan`].
+//! - Zoom: Shift + Mouse scroll or Shift + arrow direction. The mouse wheel
+//!   can instead zoom without Shift, see [`WheelBehavior`].
+//! - Axis zoom: Zooming only the X or Y axis, see [`Infinite::zoom_axes`].
+//! - Pinch Zoom: Moving two fingers apart or together on touch devices.
+//! - Rotation: Twisting two fingers on touch devices, see [`Infinite::rotate`].
+//! - Precise scrolling: Keeping items far from the origin sharp, see
+//!   [`Infinite::rebase_origin`].
+//! - Zoom to rectangle: Cmd(Ctrl) + dragging, see [`Infinite::zoom_box`].
+//! - Double click zoom: Double clicking or double tapping, see
+//!   [`Infinite::double_click_zoom`].
 //! - Reset Zoom: Shift + Home key.
 //! - Reset Scroll: Home key.
 //! - Reset Scroll and Zoom: Cmd(Ctrl) + Home key.
+//! - Page scrolling: PageUp/PageDown or Shift + PageUp/PageDown for the X
+//!   direction. A page is half of the visible canvas.
+//! - Next Zoom preset: Cmd(Ctrl) + 0.
+//! - Previous Zoom preset: Cmd(Ctrl) + Shift + 0.
+//! - Smooth scrolling and zooming: see [`CameraMotion`].
+//! - Bounded scrolling: Keeping the view within a region, see
+//!   [`Infinite::scroll_bounds`].
+//! - Split view: Two panes of the same [`Program`], see [`Infinite::split`].
+//! - Spotlight: Dimming everything outside a region, see [`Spotlight`].
+//! - Grid: Lines which subdivide and merge as the canvas is zoomed, see
+//!   [`Infinite::grid`]. Isometric and hexagonal grids are also available,
+//!   see [`GridPattern`].
+//! - Pixel grid: Lines between every unit when zoomed in closely, see
+//!   [`Infinite::pixel_grid`].
+//! - Patterns: Motifs repeated across the whole view, as for graph paper,
+//!   dot grids and checkerboards, see [`Buffer::fill_infinite_pattern`].
+//! - Gradients: Fills and strokes with a [`Gradient`] whose endpoints are on
+//!   the canvas and move with it.
+//! - Item transforms: Rotating, scaling or skewing items before they are
+//!   placed on the canvas, see [`Buffer::push_transform`]. Groups of items
+//!   can be drawn in their own co-ordinates, see [`Buffer::with_save`].
+//! - Marching ants: Dashed outlines whose dashes move over time, see
+//!   [`Buffer::stroke_marching_ants`].
+//! - Retained scenes: Shapes kept between frames and updated through their
+//!   ids, see [`Scene`].
+//! - Picking: Finding the items under the cursor by their ids, see
+//!   [`Buffer::item_id`] and [`Program::on_item_click`].
+//! - Parallax: Layers which scroll slower or faster than the canvas, see
+//!   [`Anchor::Parallax`].
+//! - Screen overlays: Legends, toolbars and badges pinned to the pane
+//!   regardless of the view, see [`Anchor::Screen`].
+//! - World overlays: Overlays kept at a point of the canvas as it is
+//!   scrolled and zoomed, see [`OverlayAnchor`].
+//! - Context menu: Entries offered on a right click or a long press, see
+//!   [`Program::context_menu`].
+//! - Tooltips: Text shown once the cursor rests over the canvas, at the
+//!   cursor or a point of the canvas, see [`Program::tooltip`].
+//! - Text editing: A caret and selection driven by keyboard events and drawn
+//!   on a [`Buffer`], see the `text_edit` module.
+//! - Chunked loading: Reporting the tiles of the canvas which come into view,
+//!   see [`Infinite::chunks`].
+//! - Map tiles: Raster tiles of a map server beneath the items, see the
+//!   `tiles` module behind the feature of the same name.
+//! - Level of detail: Drawing items in more detail as the canvas is zoomed
+//!   in, see [`Detail`] and [`Buffer::zoom_range`].
+//! - Cached buffers: Geometry kept between frames while the canvas is
+//!   scrolled, see [`Buffer::cache`]. Large buffers can be tessellated on a
+//!   worker thread, see [`Infinite::background_tessellation`].
+//! - Animation: Redrawing continuously or at a later time as the [`Program`]
+//!   asks, see [`Program::tick`].
+//! - Dirty tracking: Drawing the last frame again while neither the view nor
+//!   the content changes, see [`Program::content_version`].
+//! - Text boxes and rich text: Text wrapped within a region, see
+//!   [`Buffer::draw_text_box`], and text made of differently styled spans, see
+//!   [`Buffer::draw_rich_text`].
+//! - Axes: The X and Y axes through the origin with labelled ticks, see
+//!   [`Infinite::show_axes`].
+//! - Rulers: Measuring the canvas along its edges and dragging out guides,
+//!   see [`Infinite::rulers`].
+//! - Scale bar: A bar of a round length on the canvas, see
+//!   [`Infinite::scale_bar`].
+//! - Debug overlay: How long each frame took to prepare and how much was
+//!   drawn, see [`Infinite::debug_overlay`].
 //!
 //! Note:
 //!
 //! - Text cannot be zoomed (scaled up or down).
+//! - The width and the [`LineDash`](stroke::LineDash) of a [`Stroke`] are in
+//!   screen pixels, so outlines and dashes look the same at every zoom while
+//!   their [`Path`]s are scaled.
+//! - Items are blended with what is beneath them by their opacity alone, as
+//!   the renderers of iced do not support other blend modes such as multiply
+//!   or screen. See [`Buffer::opacity`].
 //! - Items on the canvas can be anchored on a single, both and no axis. An
 //!   anchored Item does not move when scrolled on the anchoring axis.
 //! - The Scrolling direction for the [`Infinite`] can be set using
-//!       [`ScrollDirection`].
+//!   [`ScrollDirection`].
 //! - Like the regualar Iced canvas, Items on an [`Infinite`] benefit
-//!   from antialiasing being enabled.
+//!   from antialiasing being enabled. Without it, an [`Infinite`] can
+//!   approximate antialiasing by itself, see [`Antialiasing`].
 //! - Unlike the regular Iced canvas, unless otherwise stated, shapes
-//!   are drawn with respect to their bottom-left point.
-
-use std::f32::consts::E;
+//!   are drawn with respect to their bottom-left point. The Y axis can
+//!   instead point down, see [`YDirection`].
+//! - Items can be drawn with polar co-ordinates, see [`Coordinates`].
+//! - Tasks which move the view, like [`scroll_to`], take effect as soon as
+//!   they run. The message of [`Program::on_viewport_change`] is published
+//!   with the next event, but those of the other [`Program`] hooks they
+//!   trigger are not.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::f32::consts::{E, PI};
 use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
+use std::sync::{
+    atomic::{self, AtomicU64},
+    Arc,
+};
+use std::thread::{self, JoinHandle};
 
 use iced::{
-    advanced::{self, layout, mouse::Cursor, widget::tree, Widget},
+    advanced::{
+        self, layout,
+        mouse::Cursor,
+        text,
+        widget::{operation, tree, Id, Operation},
+        Widget,
+    },
+    alignment,
     border::Radius,
-    color, event as iced_event, keyboard, mouse, touch,
-    widget::canvas::{path::lyon_path::geom::euclid::Transform2D, Frame},
-    Background, Border, Color, Element, Length, Pixels, Point, Rectangle, Shadow, Size, Theme,
-    Vector,
+    color, event as iced_event, keyboard, mouse,
+    time::{Duration, Instant},
+    touch,
+    widget::canvas::{
+        fill,
+        path::{self, lyon_path::geom::euclid::Transform2D},
+        stroke, Frame,
+    },
+    window, Background, Border, Color, Element, Font, Length, Padding, Pixels, Point, Radians,
+    Rectangle, Shadow, Size, Task, Theme, Transformation, Vector,
 };
 
-pub use iced::widget::canvas::{Fill, Path, Stroke, Text};
+pub use iced::widget::canvas::{gradient, Fill, Gradient, Path, Stroke, Text};
 
+/// A 2D affine transform of the items of a [`Buffer`], see
+/// [`Buffer::push_transform`].
+pub type Transform = path::lyon_path::math::Transform;
+
+use iced_graphics::cache::{self, Cached};
 use iced_graphics::geometry;
 
-use event::Event;
+use crate::easing::Easing;
+use crate::geometry::path_bounds;
+use crate::tween::Animated;
+use event::{Event, WindowEvent};
 use style::*;
 
 const DEFAULT_BACKGROUND: Background = Background::Color(color!(203, 213, 240));
+const DEFAULT_SPOTLIGHT_DIM: Color = Color::from_rgba(0.0, 0.0, 0.0, 0.6);
+const DEFAULT_SPOTLIGHT_DURATION: Duration = Duration::from_millis(250);
 const SCALE_STEP: f32 = 0.1;
 const OFFSET_STEP: f32 = 25.0;
+/// The distance, in pixels, scrolled by a single line of a mouse wheel.
+const LINE_SCROLL: f32 = 100.0;
+const PAGE_FRACTION: f32 = 0.5;
+const ZOOM_PRESETS: [f32; 5] = [0.25, 0.5, 1.0, 2.0, 4.0];
+/// The difference in scale level under which a zoom matches a preset.
+const PRESET_EPSILON: f32 = 1e-3;
+const DIVIDER_WIDTH: f32 = 4.0;
+/// The smallest width or height of a zoom box which zooms the canvas.
+const MIN_ZOOM_BOX: f32 = 4.0;
+const FEATHER_WIDTH: f32 = 1.0;
+const FOCUS_RING_WIDTH: f32 = 2.0;
+/// The time without scrolls or zooms after which a gesture ends.
+const GESTURE_IDLE: Duration = Duration::from_millis(150);
+/// The longest time between the presses of a double click.
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(300);
+/// The furthest distance, in pixels, between the presses of a double click.
+const DOUBLE_CLICK_DISTANCE: f32 = 4.0;
+const MIN_SPLIT_RATIO: f32 = 0.1;
+const MAX_SPLIT_RATIO: f32 = 0.9;
+/// The fraction of a scroll past the scroll bounds which is applied while
+/// overscrolling.
+const OVERSCROLL_RESISTANCE: f32 = 0.3;
+/// The furthest, in pixels, the view can be overscrolled.
+const MAX_OVERSCROLL: f32 = 120.0;
+/// The time it takes an overscroll to spring back once released.
+const OVERSCROLL_RELEASE: Duration = Duration::from_millis(300);
+/// The furthest, in pixels, a polar line drawn as straight segments strays
+/// from the curve it approximates.
+const POLAR_TOLERANCE: f32 = 0.25;
+/// The dash pattern, in pixels, of marching ants given no other pattern.
+const MARCHING_ANTS_DASH: [f32; 2] = [6.0, 4.0];
+/// The furthest, in pixels, a flattened curve of marching ants strays from
+/// the curve it approximates.
+const DASH_TOLERANCE: f32 = 0.1;
+/// The most straight segments a single polar line is drawn with.
+const MAX_POLAR_SEGMENTS: f32 = 1024.0;
+/// The thickness of the rulers, in pixels.
+const RULER_SIZE: f32 = 20.0;
+const RULER_TEXT_SIZE: f32 = 10.0;
+/// The smallest distance, in pixels, between the labelled ticks of a ruler.
+const MIN_TICK_SPACING: f32 = 60.0;
+/// The number of ticks between two labelled ticks of a ruler.
+const MINOR_TICKS: i64 = 5;
+/// The distance, in pixels, within which a guide can be grabbed.
+const GUIDE_GRAB: f32 = 4.0;
+/// The longest the scale bar is drawn, in pixels.
+const MAX_SCALE_BAR: f32 = 120.0;
+const SCALE_BAR_TEXT_SIZE: f32 = 14.0;
+/// The number of grid lines from one major grid line to the next.
+const GRID_MAJOR: i64 = 5;
+/// The most grid lines drawn along an axis.
+const MAX_GRID_LINES: i64 = 10_000;
+/// The most cells of a hexagonal grid drawn.
+const MAX_HEX_CELLS: i64 = 20_000;
+/// The most copies of the motif of a pattern drawn, see
+/// [`Buffer::fill_infinite_pattern`].
+const MAX_PATTERN_COPIES: i64 = 20_000;
+/// The most chunks reported as visible, see [`Infinite::chunks`].
+const MAX_CHUNKS: i64 = 4096;
+/// The length, in pixels, of the ticks of the axes.
+const AXIS_TICK: f32 = 6.0;
+const AXIS_TEXT_SIZE: f32 = 12.0;
+/// The time it takes the details to fade out, see [`HudConfig::fade_out`].
+const HUD_FADE: Duration = Duration::from_millis(300);
+/// The distance, in pixels, the view can be scrolled from its origin before
+/// the origin is moved, see [`Infinite::rebase_origin`].
+const REBASE_DISTANCE: f32 = 10_000.0;
+/// How long a finger is held still to open the context menu.
+const LONG_PRESS: Duration = Duration::from_millis(500);
+/// How far a finger moves before it no longer opens the context menu.
+const LONG_PRESS_DISTANCE: f32 = 8.0;
+const MENU_TEXT_SIZE: f32 = 14.0;
+const MENU_PADDING: f32 = 8.0;
+/// How long the cursor rests over the [`Infinite`] before a tooltip shows.
+const TOOLTIP_DELAY: Duration = Duration::from_millis(500);
+const TOOLTIP_TEXT_SIZE: f32 = 12.0;
+/// How far a tooltip following the cursor is placed from it.
+const TOOLTIP_OFFSET: Vector = Vector::new(12.0, 16.0);
 
 /// Handle [`Infinite`] canvas event.
 pub mod event {
+    use iced::{mouse, time::Instant, window, Point, Size, Vector};
+
     /// The status of an [`Event`] after being processed.
     #[derive(Debug, Default, Clone, Copy, PartialEq)]
     pub enum Status {
@@ -93,6 +294,65 @@ pub mod event {
         Keyboard(iced::keyboard::Event),
         /// A touch event.
         Touch(iced::touch::Event),
+        /// The [`Infinite`](super::Infinite) was double clicked or double
+        /// tapped at a point.
+        ///
+        /// The point is translated to fit the [`Infinite`](super::Infinite)
+        /// coordinate system. It follows the second press of the double click.
+        DoubleClick(Point),
+        /// A stylus event.
+        ///
+        /// Iced does not report stylus input yet, so stylus events are only
+        /// produced once its backends provide them. Until then, styluses are
+        /// reported as mouse or touch events.
+        Pen(PenEvent),
+        /// An event of the window the [`Infinite`](super::Infinite) is in.
+        Window(WindowEvent),
+    }
+
+    /// An event of the window an [`Infinite`](super::Infinite) is in.
+    ///
+    /// Iced does not report changes of the scale factor to widgets, so they
+    /// are not among these events.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum WindowEvent {
+        /// The window was resized to the given logical size.
+        Resized(Size),
+        /// The window gained focus.
+        Focused,
+        /// The window lost focus.
+        ///
+        /// The release of a button or finger held down may never arrive, so
+        /// drags in progress are best cancelled.
+        Unfocused,
+        /// The window is about to be redrawn, at the given time.
+        RedrawRequested(Instant),
+    }
+
+    /// A stylus event.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum PenEvent {
+        /// The stylus touched the surface.
+        Pressed(Pen),
+        /// The stylus moved while touching or hovering over the surface.
+        Moved(Pen),
+        /// The stylus was lifted from the surface.
+        Released(Pen),
+    }
+
+    /// The state of a stylus.
+    ///
+    /// Values the device does not report are [`None`].
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Pen {
+        /// The position of the stylus on the [`Infinite`](super::Infinite).
+        pub position: Point,
+        /// The pressure of the stylus, from 0.0 to 1.0.
+        pub pressure: Option<f32>,
+        /// The tilt of the stylus along the x and y axes, in radians.
+        pub tilt: Option<Vector>,
+        /// Whether the eraser tip of the stylus is in use.
+        pub eraser: bool,
     }
 
     impl From<Event> for iced::Event {
@@ -101,6 +361,29 @@ pub mod event {
                 Event::Mouse(event) => iced::Event::Mouse(event),
                 Event::Touch(event) => iced::Event::Touch(event),
                 Event::Keyboard(event) => iced::Event::Keyboard(event),
+                Event::DoubleClick(_) => {
+                    iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+                }
+                // Iced has no stylus events, so they are treated as a mouse
+                Event::Pen(PenEvent::Pressed(_)) => {
+                    iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+                }
+                Event::Pen(PenEvent::Moved(pen)) => iced::Event::Mouse(mouse::Event::CursorMoved {
+                    position: pen.position,
+                }),
+                Event::Pen(PenEvent::Released(_)) => {
+                    iced::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+                }
+                Event::Window(WindowEvent::Resized(size)) => {
+                    iced::Event::Window(window::Event::Resized(size))
+                }
+                Event::Window(WindowEvent::Focused) => iced::Event::Window(window::Event::Focused),
+                Event::Window(WindowEvent::Unfocused) => {
+                    iced::Event::Window(window::Event::Unfocused)
+                }
+                Event::Window(WindowEvent::RedrawRequested(now)) => {
+                    iced::Event::Window(window::Event::RedrawRequested(now))
+                }
             }
         }
     }
@@ -138,25 +421,60 @@ where
 
     /// Draws the state of the [`Program`], returning a bunch of [`Buffer`]s.
     ///
-    /// A cursor whose position is translated to fit the [`Infinite`] coordinate
-    /// system is provided as `infinite_cursor`.
-    fn draw<'a>(
-        &self,
-        state: &Self::State,
-        theme: &Theme,
-        bounds: Rectangle,
-        cursor: mouse::Cursor,
-        infinite_cursor: mouse::Cursor,
-        center: Point,
-    ) -> Vec<Buffer<'a>>;
+    /// The cursors, the visible region and the level of detail are provided
+    /// through `context`.
+    ///
+    /// The [`Buffer`]s are drawn from the lowest [`Buffer::z_index`] to the
+    /// highest. Buffers with the same z-index are drawn in the order they are
+    /// returned. Once drawn, they are handed back through
+    /// [`Program::recycle`].
+    fn draw<'a>(&self, state: &Self::State, theme: &Theme, context: DrawContext)
+        -> Vec<Buffer<'a>>;
+
+    /// Takes back the [`Buffer`]s returned by [`Program::draw`] once they
+    /// have been drawn.
+    ///
+    /// The buffers can be kept in the state, behind a
+    /// [`RefCell`], then [cleared](Buffer::clear) and
+    /// returned again on the next draw, reusing their allocations rather than
+    /// recording thousands of items into new ones every frame.
+    ///
+    /// By default, this method drops the buffers.
+    fn recycle(&self, _state: &Self::State, _buffers: Vec<Buffer<'static>>) {}
+
+    /// Returns the retained [`Scene`] of the [`Program`], if there is any.
+    ///
+    /// Its shapes are drawn along with the [`Buffer`]s of
+    /// [`Program::draw`], but are only transformed again when they change or
+    /// the view moves.
+    ///
+    /// By default, this method returns `None`.
+    fn scene<'s>(&self, _state: &'s Self::State) -> Option<&'s Scene> {
+        None
+    }
+
+    /// Returns the version of what the [`Program`] draws, if it keeps track
+    /// of one.
+    ///
+    /// While the version and the view of a pane stay the same, the geometry
+    /// of its last frame is drawn again without calling [`Program::draw`].
+    /// The version must change whenever the [`Buffer`]s or the [`Scene`] of
+    /// the [`Program`] would be drawn differently, including when they
+    /// depend on the cursor. Frames with animated buffers are never reused.
+    ///
+    /// By default, this method returns `None` and every frame is drawn anew.
+    fn content_version(&self, _state: &Self::State) -> Option<u64> {
+        None
+    }
 
     /// Updates the state of the [`Program`].
     ///
     /// Captured [`Event`]s do not trigger a scroll or zoom on the
     /// [`Infinite`].
     ///
-    /// A cursor whose position is translated to fit the [`Infinite`] coordinate
-    /// system is provided as `infinite_cursor`.
+    /// The cursors and the keyboard modifiers are provided through
+    /// `context`. The clipboard is provided as `clipboard`, as for copying
+    /// and pasting text, see [`TextEditor::update`](crate::text_edit::TextEditor::update).
     ///
     /// This method can optionally return a Message to notify an application of any meaningful interactions.
     ///
@@ -165,48 +483,147 @@ where
         &self,
         _state: &mut Self::State,
         _event: Event,
-        _bounds: Rectangle,
-        _cursor: mouse::Cursor,
-        _infinite_cursor: mouse::Cursor,
+        _context: InputContext,
+        _clipboard: &mut dyn advanced::Clipboard,
     ) -> (event::Status, Option<Message>) {
         (event::Status::Ignored, None)
     }
 
     /// Returns the current mouse interaction of the [`Program`].
     ///
-    /// A cursor whose position is translated to fit the [`Infinite`] coordinate
-    /// system is provided as `infinite_cursor`.
+    /// The cursors and the keyboard modifiers are provided through
+    /// `context`.
     fn mouse_interaction(
         &self,
         _state: &Self::State,
-        _bounds: Rectangle,
-        _cursor: mouse::Cursor,
-        _infinite_cursor: mouse::Cursor,
+        _context: InputContext,
     ) -> mouse::Interaction {
         mouse::Interaction::default()
     }
 
     /// Returns the overlay of the [`Infinite`], if there is any.
     ///
-    /// A cursor whose position is translated to fit the [`Infinite`] coordinate
-    /// system is provided as `infinite_cursor`.
+    /// The cursor and the current [`Viewport`], with which an
+    /// [`OverlayAnchor`] gives the position of an overlay, are provided
+    /// through `context`.
+    ///
+    /// Several overlays are returned through [`Program::overlays`] instead.
     fn overlay<'a>(
         &self,
         _state: &'a mut Self::State,
-        _bounds: Rectangle,
-        _infinite_cursor: Point,
-        _translation: Vector,
+        _context: OverlayContext,
     ) -> Option<iced::advanced::overlay::Element<'a, Message, Theme, Renderer>> {
         None
     }
 
+    /// Returns the overlays of the [`Infinite`], which are shown together,
+    /// like a context menu beside a floating toolbar.
+    ///
+    /// The arguments are those of [`Program::overlay`]. Overlays later in the
+    /// list are drawn above those before them.
+    ///
+    /// By default, this method returns the overlay of [`Program::overlay`],
+    /// if there is any.
+    fn overlays<'a>(
+        &self,
+        state: &'a mut Self::State,
+        context: OverlayContext,
+    ) -> Vec<iced::advanced::overlay::Element<'a, Message, Theme, Renderer>> {
+        self.overlay(state, context).into_iter().collect()
+    }
+
+    /// Returns the entries of the context menu opened at `position` on the
+    /// [`Infinite`], by right clicking or a long press.
+    ///
+    /// The menu is shown as an overlay and publishes the message of the
+    /// chosen entry. Right clicks and long presses the [`Program`] captures
+    /// do not open it.
+    ///
+    /// By default, this method returns no entries and no menu is opened.
+    fn context_menu(&self, _state: &Self::State, _position: Point) -> Vec<MenuEntry<Message>> {
+        Vec::new()
+    }
+
+    /// Returns the tooltip shown while the cursor rests at `position` on the
+    /// [`Infinite`], see [`Infinite::tooltip_delay`].
+    ///
+    /// The tooltip stays shown while the cursor moves over points with a
+    /// tooltip, and is hidden once it reaches a point without one, or a
+    /// button is pressed.
+    ///
+    /// By default, this method returns [`None`].
+    fn tooltip(&self, _state: &Self::State, _position: Point) -> Option<Tooltip> {
+        None
+    }
+
+    /// Returns the region of the [`Infinite`] covered by the contents of the
+    /// [`Program`], given by its bottom-left corner.
+    ///
+    /// This is the region shown by [`fit_content`].
+    ///
+    /// By default, this method returns [`None`] and [`fit_content`] does
+    /// nothing.
+    fn content_bounds(&self, _state: &Self::State) -> Option<Rectangle> {
+        None
+    }
+
+    /// Returns true if the [`Program`] holds the pointer captured, like
+    /// during a drag.
+    ///
+    /// While the pointer is captured, the [`Program`] keeps receiving mouse
+    /// events with the last known cursor position even after the cursor
+    /// leaves the bounds of the [`Infinite`] or the window. The [`Infinite`]
+    /// does not scroll or zoom from these events and captures them, so
+    /// widgets underneath the cursor do not react to them.
+    ///
+    /// By default, this method returns false.
+    fn captures_pointer(&self, _state: &Self::State) -> bool {
+        false
+    }
+
+    /// Returns true if the [`Program`] has animations running at `now`.
+    ///
+    /// The [`Infinite`] keeps redrawing for as long as this returns true.
+    /// See the [`tween`](crate::tween) module for animating items.
+    ///
+    /// By default, this method returns false.
+    fn is_animating(&self, _state: &Self::State, _now: Instant) -> bool {
+        false
+    }
+
+    /// Advances the [`Program`] to `now`, once before every frame, returning
+    /// when the next frame is needed.
+    ///
+    /// Returning [`RedrawRequest::NextFrame`](window::RedrawRequest::NextFrame)
+    /// redraws continuously, as for a playing simulation, while
+    /// [`RedrawRequest::At`](window::RedrawRequest::At) redraws once at a
+    /// later time, as for a blinking caret. With
+    /// [`Program::content_version`], the version is to change along with
+    /// what is drawn.
+    ///
+    /// By default, this method does and returns nothing.
+    fn tick(&self, _state: &mut Self::State, _now: Instant) -> Option<window::RedrawRequest> {
+        None
+    }
+
+    /// Returns the [`Spotlight`] of the [`Infinite`], if there is any.
+    ///
+    /// Everything outside the region of the [`Spotlight`] is dimmed. The
+    /// dimming fades in when a [`Spotlight`] is returned and fades out once
+    /// `None` is returned again.
+    ///
+    /// By default, this method returns `None`.
+    fn spotlight(&self, _state: &Self::State) -> Option<Spotlight> {
+        None
+    }
+
     /// Updates the state of the [`Program`] whenever a scroll occurs.
     ///
     /// The current scroll of the canvas is provided as `scroll` and the change
     /// is also provided as `diff`.
     ///
-    /// A cursor whose position is translated to fit the [`Infinite`] coordinate
-    /// system is provided as `infinite_cursor`.
+    /// The cursors and the keyboard modifiers are provided through
+    /// `context`.
     ///
     /// An optional Message can be returned to notify an application of any
     /// meaningful interactions.
@@ -215,9 +632,7 @@ where
     fn on_scroll(
         &self,
         _state: &mut Self::State,
-        _bounds: Rectangle,
-        _cursor: mouse::Cursor,
-        _infinite_cursor: mouse::Cursor,
+        _context: InputContext,
         _scroll: Vector,
         _diff: Vector,
     ) -> Option<Message> {
@@ -229,8 +644,8 @@ where
     /// The current zoom of the canvas is provided as `zoom` and the change
     /// is also provided as `diff`.
     ///
-    /// A cursor whose position is translated to fit the [`Infinite`] coordinate
-    /// system is provided as `infinite_cursor`.
+    /// The cursors and the keyboard modifiers are provided through
+    /// `context`.
     ///
     /// An optional Message can be returned to notify an application of any
     /// meaningful interactions.
@@ -239,9 +654,7 @@ where
     fn on_zoom(
         &self,
         _state: &mut Self::State,
-        _bounds: Rectangle,
-        _cursor: mouse::Cursor,
-        _infinite_cursor: mouse::Cursor,
+        _context: InputContext,
         _focal_point: Point,
         _zoom: f32,
         _diff: f32,
@@ -249,11 +662,151 @@ where
         None
     }
 
+    /// Notifies the [`Program`] that the scroll or zoom of the [`Infinite`]
+    /// changed, whatever the cause.
+    ///
+    /// The new [`Viewport`] is provided as `viewport`, see
+    /// [`Viewport::visible`] for the region now visible. This is called once
+    /// per event, after [`Program::on_scroll`], [`Program::on_zoom`] and the
+    /// reset hooks. When a task like [`scroll_to`] moves the view, this is
+    /// called with the next event instead.
+    ///
+    /// By default, this method does and returns nothing.
+    fn on_viewport_change(&self, _state: &mut Self::State, _viewport: Viewport) -> Option<Message> {
+        None
+    }
+
+    /// Notifies the [`Program`] that the visible [`Chunk`]s of the
+    /// [`Infinite`] changed, see [`Infinite::chunks`].
+    ///
+    /// All chunks now visible are provided as `visible`, and those which were
+    /// not visible before as `needed`, so their content can be loaded, as
+    /// from a database or over the network. Content of chunks no longer
+    /// visible can be dropped.
+    ///
+    /// By default, this method does and returns nothing.
+    fn on_region_needed(
+        &self,
+        _state: &mut Self::State,
+        _visible: &[Chunk],
+        _needed: &[Chunk],
+    ) -> Option<Message> {
+        None
+    }
+
+    /// Notifies the [`Program`] that a [`Guide`] was added, moved or removed
+    /// by dragging it, see [`Infinite::rulers`].
+    ///
+    /// All guides of the [`Infinite`] are provided as `guides`.
+    ///
+    /// By default, this method does and returns nothing.
+    fn on_guides_change(&self, _state: &mut Self::State, _guides: &[Guide]) -> Option<Message> {
+        None
+    }
+
+    /// Notifies the [`Program`] that the item under the cursor changed, see
+    /// [`Buffer::item_id`].
+    ///
+    /// The id of the top-most item under the cursor is provided as `id`, or
+    /// `None` once the cursor leaves all items, along with the position of
+    /// the cursor on the [`Infinite`] as `position`.
+    ///
+    /// By default, this method does and returns nothing.
+    fn on_item_hover(
+        &self,
+        _state: &mut Self::State,
+        _id: Option<u64>,
+        _position: Point,
+    ) -> Option<Message> {
+        None
+    }
+
+    /// Notifies the [`Program`] that an item was clicked, see
+    /// [`Buffer::item_id`].
+    ///
+    /// An item is clicked when the left mouse button is pressed and released
+    /// over it without the [`Infinite`] scrolling or zooming in between. The
+    /// id of the top-most item under the cursor is provided as `id`, along
+    /// with the position of the cursor on the [`Infinite`] as `position`.
+    ///
+    /// By default, this method does and returns nothing.
+    fn on_item_click(
+        &self,
+        _state: &mut Self::State,
+        _id: u64,
+        _position: Point,
+    ) -> Option<Message> {
+        None
+    }
+
+    /// Notifies the [`Program`] that a scroll gesture started, like a drag or
+    /// a run of mouse wheel scrolls.
+    ///
+    /// The scroll of the canvas before the gesture is provided as `scroll`.
+    /// Every scroll through [`Program::on_scroll`] happens within a gesture.
+    ///
+    /// By default, this method does and returns nothing.
+    fn on_scroll_start(
+        &self,
+        _state: &mut Self::State,
+        _bounds: Rectangle,
+        _scroll: Vector,
+    ) -> Option<Message> {
+        None
+    }
+
+    /// Notifies the [`Program`] that a scroll gesture ended.
+    ///
+    /// A gesture ends once it is released and no scroll occurred for a short
+    /// while. The scroll of the canvas after the gesture is provided as
+    /// `scroll`.
+    ///
+    /// By default, this method does and returns nothing.
+    fn on_scroll_end(
+        &self,
+        _state: &mut Self::State,
+        _bounds: Rectangle,
+        _scroll: Vector,
+    ) -> Option<Message> {
+        None
+    }
+
+    /// Notifies the [`Program`] that a zoom gesture started, like a pinch or
+    /// a run of mouse wheel zooms.
+    ///
+    /// The zoom of the canvas before the gesture is provided as `zoom`.
+    /// Every zoom through [`Program::on_zoom`] happens within a gesture.
+    ///
+    /// By default, this method does and returns nothing.
+    fn on_zoom_start(
+        &self,
+        _state: &mut Self::State,
+        _bounds: Rectangle,
+        _zoom: f32,
+    ) -> Option<Message> {
+        None
+    }
+
+    /// Notifies the [`Program`] that a zoom gesture ended.
+    ///
+    /// A gesture ends once it is released and no zoom occurred for a short
+    /// while. The zoom of the canvas after the gesture is provided as `zoom`.
+    ///
+    /// By default, this method does and returns nothing.
+    fn on_zoom_end(
+        &self,
+        _state: &mut Self::State,
+        _bounds: Rectangle,
+        _zoom: f32,
+    ) -> Option<Message> {
+        None
+    }
+
     /// Updates the state of the [`Program`] when the scroll is reset to the
     /// starting value.
     ///
-    /// A cursor whose position is translated to fit the [`Infinite`] coordinate
-    /// system is provided as `infinite_cursor`.
+    /// The cursors and the keyboard modifiers are provided through
+    /// `context`.
     ///
     /// An optional Message can be returned to notify an application of any
     /// meaningful interactions.
@@ -262,9 +815,7 @@ where
     fn on_scroll_reset(
         &self,
         _state: &mut Self::State,
-        _bounds: Rectangle,
-        _cursor: mouse::Cursor,
-        _infinite_cursor: mouse::Cursor,
+        _context: InputContext,
         _scroll: Vector,
     ) -> Option<Message> {
         None
@@ -273,8 +824,8 @@ where
     /// Updates the state of the [`Program`] when the zoom is reset to the
     /// starting value.
     ///
-    /// A cursor whose position is translated to fit the [`Infinite`] coordinate
-    /// system is provided as `infinite_cursor`.
+    /// The cursors and the keyboard modifiers are provided through
+    /// `context`.
     ///
     /// An optional Message can be returned to notify an application of any
     /// meaningful interactions.
@@ -283,9 +834,7 @@ where
     fn on_zoom_reset(
         &self,
         _state: &mut Self::State,
-        _bounds: Rectangle,
-        _cursor: mouse::Cursor,
-        _infinite_cursor: mouse::Cursor,
+        _context: InputContext,
         _zoom: f32,
     ) -> Option<Message> {
         None
@@ -307,1411 +856,10262 @@ pub enum Anchor {
     /// any direction.
     #[default]
     None,
+    /// Items are pinned to the pane rather than the canvas, ignoring its
+    /// scroll, zoom and rotation, as for legends and toolbars.
+    ///
+    /// Points are in pixels from the point of the pane given by the
+    /// alignments, such as its top-left corner, with the Y axis pointing as
+    /// in the [`YDirection`] of the [`Infinite`].
+    Screen(alignment::Horizontal, alignment::Vertical),
+    /// Items move by the given fraction of the scroll of the canvas along
+    /// each axis, as for parallax backgrounds, which seem further away the
+    /// smaller the fraction.
+    ///
+    /// A fraction of 1.0 moves as with [`Anchor::None`] and 0.0 stays as with
+    /// [`Anchor::Both`].
+    Parallax(Vector),
 }
 
-#[derive(Debug, Clone)]
-/// A buffer which records the items on an [`Infinite`] canvas.
-pub struct Buffer<'a> {
-    fills: Vec<(Path, Fill, Anchor)>,
-    strokes: Vec<(Path, Stroke<'a>, Anchor)>,
-    text: Vec<(Text, Anchor)>,
-    /// If `Some`, all items in this buffer inherit this anchor.
-    anchor: Option<Anchor>,
-    /// If true a scale transform is applied to all recorded Path.
-    scale: bool,
+/// The co-ordinate system the points of the items in a [`Buffer`] are given
+/// in.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Coordinates {
+    /// Points are given as (x, y).
+    #[default]
+    Cartesian,
+    /// Points are given as (radius, angle) around the origin, with the angle
+    /// in radians from the positive X axis towards the positive Y axis.
+    ///
+    /// A line between two points sweeps both its radius and its angle, so a
+    /// line of constant radius is drawn as an arc. Arcs and other curves of
+    /// a [`Path`] are drawn as straight lines between their points.
+    Polar,
 }
 
-impl<'a> Default for Buffer<'a> {
-    fn default() -> Self {
-        Self::new()
-    }
+/// A line across an [`Infinite`] dragged out of one of its rulers, see
+/// [`Infinite::rulers`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Guide {
+    /// A horizontal line at the given y co-ordinate.
+    Horizontal(f32),
+    /// A vertical line at the given x co-ordinate.
+    Vertical(f32),
 }
 
-impl<'a> Buffer<'a> {
-    /// Creates a new [`Buffer`].
-    pub fn new() -> Self {
+/// What a [`Program`] is drawn with, see [`Program::draw`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrawContext {
+    /// The bounds of the [`Infinite`], or of the pane being drawn.
+    pub bounds: Rectangle,
+    /// The cursor, relative to the window.
+    pub cursor: mouse::Cursor,
+    /// The cursor, translated to fit the [`Infinite`] coordinate system.
+    pub infinite_cursor: mouse::Cursor,
+    /// The center of the [`Infinite`], translated by the current scroll.
+    pub center: Point,
+    /// The keyboard modifiers currently held.
+    pub modifiers: keyboard::Modifiers,
+    /// The region of the [`Infinite`] currently visible, given by its
+    /// bottom-left corner. Items outside of it need not be drawn.
+    pub visible: Rectangle,
+    /// The current zoom and a suggested tier of detail, so items can be drawn
+    /// simplified when zoomed out, see also [`Buffer::zoom_range`].
+    pub detail: Detail,
+}
+
+/// What a [`Program`] handles input with, see [`Program::update`],
+/// [`Program::mouse_interaction`] and the scroll and zoom hooks, like
+/// [`Program::on_scroll`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InputContext {
+    /// The bounds of the [`Infinite`], or of the pane under the cursor.
+    pub bounds: Rectangle,
+    /// The cursor, relative to the window.
+    pub cursor: mouse::Cursor,
+    /// The cursor, translated to fit the [`Infinite`] coordinate system.
+    pub infinite_cursor: mouse::Cursor,
+    /// The keyboard modifiers currently held.
+    pub modifiers: keyboard::Modifiers,
+}
+
+impl InputContext {
+    fn new(
+        bounds: Rectangle,
+        (cursor, infinite_cursor): (mouse::Cursor, mouse::Cursor),
+        modifiers: keyboard::Modifiers,
+    ) -> Self {
         Self {
-            fills: Vec::new(),
-            strokes: Vec::new(),
-            text: Vec::new(),
-            anchor: None,
-            scale: true,
+            bounds,
+            cursor,
+            infinite_cursor,
+            modifiers,
         }
     }
+}
 
-    /// Creates a [`Buffer`] with all items having the same anchored.
-    ///
-    ///
-    /// After calling this function, the all stored items, both past and
-    /// future will have their anchors removed.
-    pub fn anchor_all(mut self, anchor: Anchor) -> Self {
-        self.anchor = Some(anchor);
-        self
-    }
+/// What a [`Program`] places its overlays with, see [`Program::overlay`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OverlayContext {
+    /// The bounds of the [`Infinite`], or of the active pane.
+    pub bounds: Rectangle,
+    /// The position of the cursor, translated to fit the [`Infinite`]
+    /// coordinate system.
+    pub infinite_cursor: Point,
+    /// The current [`Viewport`], with which an [`OverlayAnchor`] gives the
+    /// position of an overlay.
+    pub viewport: Viewport,
+    /// The translation of the overlays, as given to the [`Infinite`] by the
+    /// widgets containing it.
+    pub translation: Vector,
+}
 
-    /// Sets whether all items in the [`Buffer`] should be scale transformed
-    pub fn scale_all(mut self, scale: bool) -> Self {
-        self.scale = scale;
-        self
-    }
+/// The level of detail the items of a [`Program`] are drawn with, see
+/// [`DrawContext::detail`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Detail {
+    /// The current zoom of the canvas, as in [`Program::on_zoom`].
+    pub zoom: f32,
+    /// The suggested tier of detail, which goes up by one each time the zoom
+    /// doubles. It is 0 from a zoom of 1.0 up to 2.0 and negative when zoomed
+    /// out further.
+    pub tier: i32,
+}
 
-    /// Draws the characters of the given [`Text`] on the [`Infinite`] canvas with the anchor.
-    pub fn draw_text_anchored(&mut self, text: impl Into<Text>, anchor: Anchor) {
-        self.text.push((text.into(), anchor))
+impl Detail {
+    fn new(zoom: f32) -> Self {
+        Self {
+            zoom,
+            tier: zoom.log2().floor() as i32,
+        }
     }
+}
 
-    /// Draws the characters of the given [`Text`] on the [`Infinite`] canvas using the anchor of the [`Buffer`].
-    pub fn draw_text(&mut self, text: impl Into<Text>) {
-        self.text
-            .push((text.into(), self.anchor.unwrap_or_default()))
-    }
+/// A square tile of the canvas, for loading content on demand, see
+/// [`Infinite::chunks`].
+///
+/// The chunk at (0, 0) has its bottom-left corner at the origin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Chunk {
+    /// The column of the chunk, counted along the X axis.
+    pub x: i32,
+    /// The row of the chunk, counted along the Y axis.
+    pub y: i32,
+}
 
-    /// Draws the fill of the given [`Path`] on the [`Infinite`] canvas with an anchor by filling it with the provided style.
-    pub fn fill_anchored(&mut self, path: Path, fill: impl Into<Fill>, anchor: Anchor) {
-        self.fills.push((path, fill.into(), anchor))
+impl Chunk {
+    /// Returns the region of the canvas covered by the chunk, given by its
+    /// bottom-left corner, for chunks of the given `size`.
+    pub fn bounds(self, size: f32) -> Rectangle {
+        Rectangle::new(
+            Point::new(self.x as f32 * size, self.y as f32 * size),
+            Size::new(size, size),
+        )
     }
+}
 
-    /// Draws the fill of the given [`Path`] on the [`Infinite`] canvas with the [`Buffer`]'s anchor by filling it with the provided style.
-    pub fn fill(&mut self, path: Path, fill: impl Into<Fill>) {
-        self.fills
-            .push((path, fill.into(), self.anchor.unwrap_or_default()))
-    }
+/// Determines how the text of a text box is drawn, see
+/// [`Buffer::draw_text_box`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextStyle {
+    /// The color of the text.
+    pub color: Color,
+    /// The size of the text, in pixels.
+    pub size: Pixels,
+    /// The height of each line of the text.
+    pub line_height: text::LineHeight,
+    /// The font of the text.
+    pub font: Font,
+    /// The alignment of each line within the width of the box.
+    pub horizontal_alignment: alignment::Horizontal,
+    /// The shaping strategy of the text.
+    pub shaping: text::Shaping,
+}
 
-    /// Draws the stroke of the given [`Path`] on the [`Infinite`] canvas with the provided style and anchor.
-    pub fn stroke_anchored(&mut self, path: Path, stroke: impl Into<Stroke<'a>>, anchor: Anchor) {
-        self.strokes.push((path, stroke.into(), anchor))
+impl Default for TextStyle {
+    fn default() -> Self {
+        let text = Text::default();
+
+        Self {
+            color: text.color,
+            size: text.size,
+            line_height: text.line_height,
+            font: text.font,
+            horizontal_alignment: alignment::Horizontal::Left,
+            shaping: text.shaping,
+        }
     }
+}
 
-    /// Draws the stroke of the given [`Path`] on the [`Infinite`] canvas with the provided style and the [`Buffer`]'s anchor.
-    pub fn stroke(&mut self, path: Path, stroke: impl Into<Stroke<'a>>) {
-        self.strokes
-            .push((path, stroke.into(), self.anchor.unwrap_or_default()))
+/// Determines how the heads of an arrow are drawn, see
+/// [`Buffer::stroke_arrow`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArrowStyle {
+    /// The length, in units, of the sides of a head.
+    pub head_size: f32,
+    /// The angle between each side of a head and the line of the arrow.
+    pub head_angle: Radians,
+    /// Whether heads are filled triangles rather than two open lines.
+    pub closed: bool,
+    /// Whether a head is also drawn at the start of the arrow.
+    pub start_head: bool,
+}
+
+impl Default for ArrowStyle {
+    fn default() -> Self {
+        Self {
+            head_size: 8.0,
+            head_angle: Radians(PI / 6.0),
+            closed: true,
+            start_head: false,
+        }
     }
+}
 
-    /// Draws a rectangle given its bottom-left corner coordinate, [`Size`] and [`Anchor`] by filling it with the provided style.
-    pub fn fill_rectangle_anchored(
-        &mut self,
-        bottom_left: impl Into<Point>,
-        size: impl Into<Size>,
-        fill: impl Into<Fill>,
-        anchor: Anchor,
-    ) {
-        let size: Size = size.into();
-        let bottom_left = bottom_left.into();
-
-        let path = Path::rectangle(bottom_left, size);
-
-        self.fill_anchored(path, fill, anchor)
-    }
-
-    /// Draws a rectangle given its bottom-left corner coordinate and its [`Size`] by filling it with the provided style and the [`Buffer`]'s anchor.
-    pub fn fill_rectangle(
-        &mut self,
-        bottom_left: impl Into<Point>,
-        size: impl Into<Size>,
-        fill: impl Into<Fill>,
-    ) {
-        let size: Size = size.into();
-        let bottom_left = bottom_left.into();
+impl ArrowStyle {
+    /// Returns the [`Path`] of a head pointing at `tip` in the `direction`.
+    fn head(&self, tip: Point, direction: Vector) -> Path {
+        let length = (direction.x * direction.x + direction.y * direction.y).sqrt();
 
-        let path = Path::rectangle(bottom_left, size);
-
-        self.fill_anchored(path, fill, self.anchor.unwrap_or_default())
-    }
+        if length == 0.0 {
+            return Path::new(|_| {});
+        }
 
-    /// Draws a rounded rectangle given its bottom-left corner coordinate, [`Size`] and [`Anchor`] by filling it with the provided style.
-    pub fn fill_rounded_rectangle_anchored(
-        &mut self,
-        bottom_left: impl Into<Point>,
-        size: impl Into<Size>,
-        radius: impl Into<Radius>,
-        fill: impl Into<Fill>,
-        anchor: Anchor,
-    ) {
-        let size: Size = size.into();
-        let bottom_left = bottom_left.into();
+        let back = Vector::new(-direction.x, -direction.y) * (self.head_size / length);
+        let left = tip + rotate(back, self.head_angle.0);
+        let right = tip + rotate(back, -self.head_angle.0);
 
-        let path = Path::rounded_rectangle(bottom_left, size, radius.into());
+        Path::new(|builder| {
+            builder.move_to(left);
+            builder.line_to(tip);
+            builder.line_to(right);
 
-        self.fill_anchored(path, fill, anchor);
+            if self.closed {
+                builder.close();
+            }
+        })
     }
+}
 
-    /// Draws a rounded rectangle given its bottom-left corner coordinate and its [`Size`] by filling it with the provided style and the [`Buffer`]'s anchor.
-    pub fn fill_rounded_rectangle(
-        &mut self,
-        bottom_left: impl Into<Point>,
-        size: impl Into<Size>,
-        radius: impl Into<Radius>,
-        fill: impl Into<Fill>,
-    ) {
-        let size: Size = size.into();
-        let bottom_left = bottom_left.into();
-
-        let path = Path::rounded_rectangle(bottom_left, size, radius.into());
+/// A region of an [`Infinite`] which stays visible while everything else is
+/// dimmed.
+///
+/// The region is in the [`Infinite`]'s coordinate system, so it follows the
+/// canvas as it is scrolled and zoomed.
+#[derive(Debug, Clone)]
+pub struct Spotlight {
+    region: Path,
+    dim: Color,
+    duration: Duration,
+    easing: Easing,
+}
 
-        self.fill(path, fill);
+impl Spotlight {
+    /// Creates a [`Spotlight`] on the given [`Path`].
+    pub fn new(region: Path) -> Self {
+        Self {
+            region,
+            dim: DEFAULT_SPOTLIGHT_DIM,
+            duration: DEFAULT_SPOTLIGHT_DURATION,
+            easing: Easing::QuadInOut,
+        }
     }
 
-    /// Draws the stroke of a rectangle with the provided style given its bottom-left corner coordinate and its [`Size`].
-    pub fn stroke_rect_anchored(
-        &mut self,
-        bottom_left: impl Into<Point>,
-        size: impl Into<Size>,
-        stroke: impl Into<Stroke<'a>>,
-        anchor: Anchor,
-    ) {
-        let size: Size = size.into();
-        let bottom_left = bottom_left.into();
-
-        let path = Path::rectangle(bottom_left, size);
-
-        self.stroke_anchored(path, stroke, anchor)
+    /// Creates a [`Spotlight`] on a rectangle given its bottom-left corner
+    /// coordinate and its [`Size`].
+    pub fn rectangle(bottom_left: impl Into<Point>, size: impl Into<Size>) -> Self {
+        Self::new(Path::rectangle(bottom_left.into(), size.into()))
     }
 
-    /// Draws the stroke of a rectangle with the provided style given its bottom-left corner coordinate and its [`Size`] and the [`Buffer`]'s anchor.
-    pub fn stroke_rectangle(
-        &mut self,
-        bottom_left: impl Into<Point>,
-        size: impl Into<Size>,
-        stroke: impl Into<Stroke<'a>>,
-    ) {
-        let size: Size = size.into();
-        let bottom_left = bottom_left.into();
-
-        let path = Path::rectangle(bottom_left, size);
-
-        self.stroke(path, stroke)
+    /// Sets the [`Color`] everything outside the [`Spotlight`] is dimmed with.
+    pub fn dim(mut self, color: impl Into<Color>) -> Self {
+        self.dim = color.into();
+        self
     }
 
-    /// Draws the stroke of a rounded rectangle with the provided style given its bottom-left corner coordinate and its [`Size`].
-    pub fn stroke_rounded_rectangle_anchored(
-        &mut self,
-        bottom_left: impl Into<Point>,
-        size: impl Into<Size>,
-        radius: impl Into<Radius>,
-        stroke: impl Into<Stroke<'a>>,
-        anchor: Anchor,
-    ) {
-        let size: Size = size.into();
-        let bottom_left = bottom_left.into();
-
-        let path = Path::rounded_rectangle(bottom_left, size, radius.into());
-
-        self.stroke_anchored(path, stroke, anchor);
+    /// Sets how long the dimming takes to fade in or out.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
     }
 
-    /// Draws the stroke of a rounded rectangle with the provided style given its bottom-left corner coordinate and its [`Size`] and the [`Buffer`]'s anchor.
-    pub fn stroke_rounded_rectangle(
-        &mut self,
-        bottom_left: impl Into<Point>,
-        size: impl Into<Size>,
-        radius: impl Into<Radius>,
-        stroke: impl Into<Stroke<'a>>,
-    ) {
-        let size: Size = size.into();
-        let bottom_left = bottom_left.into();
-
-        let path = Path::rounded_rectangle(bottom_left, size, radius.into());
-
-        self.stroke(path, stroke);
+    /// Sets the [`Easing`] of the dimming's fade.
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
     }
 
-    fn draw_fills<State, Renderer: geometry::Renderer>(
+    /// Draws the dimming outside of the [`Spotlight`] with the given progress
+    /// of its fade.
+    fn draw<Renderer: geometry::Renderer>(
         &self,
         frame: &mut Frame<Renderer>,
-        state: &InfiniteState<State>,
+        view: &View,
         center: Point,
+        progress: f32,
     ) {
-        self.fills
-            .iter()
-            .map(|(path, fill, anchor)| {
-                let path = transform_path(
-                    state,
-                    center,
-                    path,
-                    self.anchor.unwrap_or(*anchor),
-                    self.scale,
-                );
-                (path, *fill)
-            })
-            .for_each(|(path, fill)| frame.fill(&path, fill));
-    }
+        let opacity = self.easing.apply(progress);
+        let region = transform_path(view, center, &self.region, Anchor::None, true);
+        let size = frame.size();
 
-    fn draw_strokes<State, Renderer: geometry::Renderer>(
-        &self,
-        frame: &mut Frame<Renderer>,
-        state: &InfiniteState<State>,
-        center: Point,
-    ) {
-        self.strokes
-            .iter()
-            .map(|(path, stroke, anchor)| {
-                let path = transform_path(
-                    state,
-                    center,
-                    path,
-                    self.anchor.unwrap_or(*anchor),
-                    self.scale,
-                );
-                (path, *stroke)
-            })
-            .for_each(|(path, stroke)| frame.stroke(&path, stroke));
-    }
+        let path = Path::new(|builder| {
+            builder.rectangle(Point::ORIGIN, size);
+            append_path(builder, &region, |point| point);
+        });
 
-    fn draw_texts<State, Renderer: geometry::Renderer>(
-        &self,
-        frame: &mut Frame<Renderer>,
-        state: &InfiniteState<State>,
-        center: Point,
-    ) {
-        self.text
-            .iter()
-            .map(|(text, anchor)| {
-                transform_text(state, center, text, self.anchor.unwrap_or(*anchor))
-            })
-            .for_each(|text| frame.fill_text(text));
+        let fill = Fill {
+            style: fill::Style::Solid(self.dim.scale_alpha(opacity)),
+            rule: fill::Rule::EvenOdd,
+        };
+
+        frame.fill(&path, fill);
     }
+}
 
-    fn draw<State, Renderer: geometry::Renderer>(
-        &self,
-        frame: &mut Frame<Renderer>,
-        state: &InfiniteState<State>,
-        center: Point,
-    ) {
-        self.draw_fills(frame, state, center);
-        self.draw_strokes(frame, state, center);
-        self.draw_texts(frame, state, center);
+/// An item recorded in a [`Buffer`].
+#[derive(Debug, Clone)]
+enum Item<'a> {
+    Fill(Path, Fill, Anchor),
+    Stroke(Path, Stroke<'a>, Anchor),
+    /// A stroke whose dashes move at the given speed, in pixels per second.
+    MarchingAnts(Path, Stroke<'a>, f32, Anchor),
+    Text(Text, Anchor),
+    /// Text turned by the given angle about its position.
+    RotatedText(Text, Radians, Anchor),
+    /// Text wrapped within the region of a [`Path`].
+    TextBox(Path, String, TextStyle, Anchor),
+    /// The pieces of rich text and their offsets from its top-left corner.
+    RichText(Point, Vec<(Vector, Text)>, Anchor),
+    /// The lines of a [`Item::TextBox`] or [`Item::RichText`], once laid out.
+    Lines(Vec<Text>, Anchor),
+    /// Text on a rounded background with the given padding, color and radius.
+    Label(Text, Padding, Color, Radius, Anchor),
+    /// The background and text of a [`Item::Label`], once laid out.
+    Badge(Path, Fill, Text, Anchor),
+    /// A motif repeated at the given spacing over the visible region.
+    Pattern(Path, Fill, Size, Anchor),
+    /// A rectangle of the screen, relative to a point on the canvas.
+    ScreenRectangle(Point, Rectangle, Color, Anchor),
+}
+
+impl<'a> Item<'a> {
+    fn anchor(&self) -> Anchor {
+        match self {
+            Item::Fill(_, _, anchor)
+            | Item::Stroke(_, _, anchor)
+            | Item::MarchingAnts(_, _, _, anchor)
+            | Item::Text(_, anchor)
+            | Item::RotatedText(_, _, anchor)
+            | Item::TextBox(_, _, _, anchor)
+            | Item::RichText(_, _, anchor)
+            | Item::Lines(_, anchor)
+            | Item::Label(_, _, _, _, anchor)
+            | Item::Badge(_, _, _, anchor)
+            | Item::Pattern(_, _, _, anchor)
+            | Item::ScreenRectangle(_, _, _, anchor) => *anchor,
+        }
     }
 }
 
-/// Determines which directions the canvas can be scrolled
-#[derive(Debug, Default, Clone, Copy, PartialEq)]
-pub enum ScrollDirection {
-    /// Scroll in only X direction
-    X,
-    /// Scroll in only the Y direction
-    Y,
-    #[default]
-    /// Scroll in both x and y directions
-    Both,
-    /// No scroll in any direction. Scroll events are thus ignored.
-    None,
+#[derive(Debug, Clone)]
+/// A buffer which records the items on an [`Infinite`] canvas.
+///
+/// Items are drawn from the lowest z-index to the highest, see
+/// [`Buffer::item_z_index`]. Items with the same z-index are drawn in the order
+/// they were recorded, so later items are drawn over earlier ones.
+///
+/// Note: Iced draws the text of a layer over its shapes. Text is therefore
+/// drawn over the fills and strokes of the same layer whatever their order,
+/// including those of [`Buffer`]s with a higher [`Buffer::z_index`]. Only a
+/// cached [`Buffer`] starts a layer of its own, see [`Buffer::cache`].
+pub struct Buffer<'a> {
+    /// The recorded items with their z-index and id, sorted by z-index and
+    /// then in the order they were recorded.
+    items: Vec<(i32, Option<u64>, Item<'a>)>,
+    /// The z-index of items recorded from now on.
+    item_z_index: i32,
+    /// The id of items recorded from now on.
+    item_id: Option<u64>,
+    /// The z-index of the buffer among the buffers of a [`Program`].
+    z_index: i32,
+    /// The opacity all items are drawn with.
+    opacity: f32,
+    /// The font used for text recorded with the default font.
+    font: Option<Font>,
+    /// The size used for text recorded with the default size.
+    text_size: Option<Pixels>,
+    /// The color used for text recorded with the default color.
+    text_color: Option<Color>,
+    /// If `Some`, all items in this buffer inherit this anchor.
+    anchor: Option<Anchor>,
+    /// If true a scale transform is applied to all recorded Path.
+    scale: bool,
+    coordinates: Coordinates,
+    /// If true the buffer is skipped once the frame budget is exceeded.
+    low_priority: bool,
+    /// The combined transforms applied to items as they are recorded.
+    transforms: Vec<Transform>,
+    /// The geometry of the buffer kept between frames.
+    cache: Option<BufferCache>,
+    /// The zooms the buffer is drawn at.
+    zoom_range: (Bound<f32>, Bound<f32>),
 }
 
-/// A widget capable of drawing 2D graphics on an infinite Cartesian plane.
-pub struct Infinite<'a, P, Message, Theme = iced::Theme, Renderer = iced::Renderer>
-where
-    Theme: Catalog,
-    P: Program<Message, Theme, Renderer>,
-    Renderer: geometry::Renderer,
-{
-    width: Length,
-    height: Length,
-    direction: ScrollDirection,
-    allow_scale: bool,
-    scale_step: Option<f32>,
-    offset_step: Option<Vector>,
-    _message: PhantomData<Message>,
-    _renderer: PhantomData<Renderer>,
-    program: P,
-    style: <Theme as Catalog>::Class<'a>,
+impl<'a> Default for Buffer<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl<'a, P, Message, Theme, Renderer> Infinite<'a, P, Message, Theme, Renderer>
-where
-    Theme: Catalog,
-    P: Program<Message, Theme, Renderer>,
-    Renderer: geometry::Renderer,
-{
-    const DEFAULT_SIZE: f32 = 300.0;
+impl<'a> FromIterator<Buffer<'a>> for Buffer<'a> {
+    /// Merges the buffers into the first one, see [`Buffer::extend`].
+    fn from_iter<I: IntoIterator<Item = Buffer<'a>>>(iter: I) -> Self {
+        let mut buffers = iter.into_iter();
+        let mut buffer = buffers.next().unwrap_or_default();
 
-    /// Creates a new [`Infinite`].
-    pub fn new(program: P) -> Self {
-        Self {
-            width: Length::Fixed(Self::DEFAULT_SIZE),
-            height: Length::Fixed(Self::DEFAULT_SIZE),
-            direction: ScrollDirection::default(),
-            allow_scale: true,
-            scale_step: None,
-            offset_step: None,
-            program,
-            _message: PhantomData,
-            _renderer: PhantomData,
-            style: Theme::default(),
+        for other in buffers {
+            buffer.extend(other);
         }
+
+        buffer
     }
+}
 
-    /// Sets the height of the [`Infinite`].
-    pub fn height(mut self, height: impl Into<Length>) -> Self {
-        self.height = height.into();
-        self
+impl<'a> Buffer<'a> {
+    /// Creates a new [`Buffer`].
+    pub fn new() -> Self {
+        Self::with_capacity(0)
     }
 
-    /// Sets the width of the [`Infinite`].
-    pub fn width(mut self, width: impl Into<Length>) -> Self {
-        self.width = width.into();
-        self
+    /// Creates a new [`Buffer`] with room for at least `capacity` items, such
+    /// as fills, strokes and texts, before it reallocates.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            items: Vec::with_capacity(capacity),
+            item_z_index: 0,
+            item_id: None,
+            z_index: 0,
+            opacity: 1.0,
+            font: None,
+            text_size: None,
+            text_color: None,
+            anchor: None,
+            scale: true,
+            coordinates: Coordinates::Cartesian,
+            low_priority: false,
+            transforms: Vec::new(),
+            cache: None,
+            zoom_range: (Bound::Unbounded, Bound::Unbounded),
+        }
     }
 
-    /// Sets the supported scroll direction of the [`Infinite`].
-    pub fn scroll_direction(mut self, direction: ScrollDirection) -> Self {
-        self.direction = direction;
+    /// Creates a [`Buffer`] with all items having the same anchored.
+    ///
+    ///
+    /// After calling this function, the all stored items, both past and
+    /// future will have their anchors removed.
+    pub fn anchor_all(mut self, anchor: Anchor) -> Self {
+        self.anchor = Some(anchor);
         self
     }
 
-    /// Sets whether the [`Infinite`] can be zoomed in/out on.
-    pub fn zoom(mut self, allow: bool) -> Self {
-        self.allow_scale = allow;
+    /// Sets whether all items in the [`Buffer`] should be scale transformed
+    ///
+    /// The width of strokes is never scaled, so it need not be turned off to
+    /// keep outlines thin.
+    pub fn scale_all(mut self, scale: bool) -> Self {
+        self.scale = scale;
         self
     }
 
-    /// Sets the value of a single zoom on the [`Infinite`].
-    pub fn zoom_step(mut self, step: f32) -> Self {
-        self.scale_step = Some(step);
+    /// Sets the [`Coordinates`] the points of all items in the [`Buffer`] are
+    /// given in.
+    pub fn coordinates(mut self, coordinates: Coordinates) -> Self {
+        self.coordinates = coordinates;
         self
     }
 
-    /// Sets the value of a single scroll on the [`Infinite`].
-    pub fn scroll_step(mut self, step: Vector) -> Self {
-        self.offset_step = Some(step);
+    /// Sets the z-index of the [`Buffer`] among the [`Buffer`]s drawn by a
+    /// [`Program`]. Buffers with a higher z-index are drawn over those with a
+    /// lower one, except for text which is drawn over the shapes of its layer.
+    /// See [`Buffer`].
+    ///
+    /// Defaults to 0.
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
         self
     }
 
-    /// Sets  the style of the [`Infinite`].
-    pub fn style(mut self, style: impl Fn(&Theme, Status) -> Style + 'a) -> Self
-    where
-        Theme::Class<'a>: From<StyleFn<'a, Theme>>,
-    {
-        self.style = (Box::new(style) as StyleFn<'a, Theme>).into();
+    /// Sets the opacity all items of the [`Buffer`] are drawn with, from 0.0
+    /// for transparent to 1.0 for the colors they were recorded with.
+    ///
+    /// A translucent [`Buffer`] lets the items beneath it show through, as
+    /// for highlights and heatmaps.
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
         self
     }
-}
 
-impl<'a, P, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
-    for Infinite<'a, P, Message, Theme, Renderer>
-where
-    Theme: Catalog,
-    P: Program<Message, Theme, Renderer>,
-    Renderer: geometry::Renderer,
-{
-    fn size(&self) -> Size<Length> {
-        Size {
-            width: self.width,
-            height: self.height,
+    /// Sets the font of the text drawn on the [`Buffer`] which is recorded
+    /// with the default [`Font`].
+    ///
+    /// This also applies to the [`TextStyle`] of text boxes and rich text.
+    pub fn default_font(mut self, font: Font) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// Sets the size of the text drawn on the [`Buffer`] which is recorded
+    /// with the default size.
+    ///
+    /// This also applies to the [`TextStyle`] of text boxes and rich text.
+    pub fn default_text_size(mut self, size: impl Into<Pixels>) -> Self {
+        self.text_size = Some(size.into());
+        self
+    }
+
+    /// Sets the color of the text drawn on the [`Buffer`] which is recorded
+    /// with the default color.
+    ///
+    /// This also applies to the [`TextStyle`] of text boxes and rich text.
+    pub fn default_text_color(mut self, color: impl Into<Color>) -> Self {
+        self.text_color = Some(color.into());
+        self
+    }
+
+    /// Sets the z-index of the items recorded from now on. Items with a higher
+    /// z-index are drawn over those with a lower one, regardless of the order
+    /// they are recorded in. Text is the exception, as it is drawn over all
+    /// the shapes of its layer. See [`Buffer`].
+    ///
+    /// Defaults to 0.
+    pub fn item_z_index(&mut self, z_index: i32) {
+        self.item_z_index = z_index;
+    }
+
+    /// Sets the id of the items recorded from now on, or `None` for items
+    /// which are not picked.
+    ///
+    /// The [`Infinite`] finds the items with an id under the cursor and
+    /// notifies the [`Program`] when they are hovered or clicked, see
+    /// [`Program::on_item_hover`] and [`Program::on_item_click`]. Several
+    /// items can share an id to be picked as one.
+    ///
+    /// Defaults to `None`.
+    pub fn item_id(&mut self, id: impl Into<Option<u64>>) {
+        self.item_id = id.into();
+    }
+
+    /// Adds all items of `other` to the [`Buffer`], keeping their z-index.
+    /// Items with the same z-index are drawn after those already in the
+    /// [`Buffer`].
+    ///
+    /// The items are drawn with the settings of this [`Buffer`], such as its
+    /// [`Coordinates`], anchor and opacity, rather than those of `other`.
+    pub fn extend(&mut self, mut other: Buffer<'a>) {
+        self.append(&mut other)
+    }
+
+    /// Removes all items from the [`Buffer`], keeping its allocations and
+    /// settings so it can be reused, see [`Program::recycle`].
+    ///
+    /// The item z-index, item id and transforms are reset.
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.item_z_index = 0;
+        self.item_id = None;
+        self.transforms.clear();
+    }
+
+    /// Moves all items of `other` to the [`Buffer`], leaving `other` empty.
+    ///
+    /// See [`Buffer::extend`].
+    pub fn append(&mut self, other: &mut Buffer<'a>) {
+        self.items.append(&mut other.items);
+        // Stable, so earlier items stay first among those of the same z-index
+        self.items.sort_by_key(|(z_index, _, _)| *z_index);
+    }
+
+    /// Keeps the geometry of the [`Buffer`] in the given [`BufferCache`], so
+    /// it is not made anew every frame while the [`Infinite`] is scrolled.
+    ///
+    /// The geometry is reused until the [`BufferCache`] is
+    /// [cleared](BufferCache::clear), which should be done whenever the items
+    /// change, or until the [`Infinite`] is zoomed, rotated or resized. Large
+    /// buffers can be tessellated anew on a worker thread, see
+    /// [`Infinite::background_tessellation`].
+    ///
+    /// Only [`Buffer`]s whose items all move with the canvas, with
+    /// [`Anchor::None`], and which have no marching ants are cached. Others
+    /// are drawn as usual.
+    pub fn cache(mut self, cache: &BufferCache) -> Self {
+        self.cache = Some(cache.clone());
+        self
+    }
+
+    /// Sets the zooms of the [`Infinite`] at which the [`Buffer`] is drawn,
+    /// as in [`Detail::zoom`]. Outside of them, it is skipped.
+    ///
+    /// Buffers of the same items in more and less detail can be given
+    /// adjacent ranges, such as `..4.0` and `4.0..`, to swap between them as
+    /// the canvas is zoomed.
+    pub fn zoom_range(mut self, range: impl RangeBounds<f32>) -> Self {
+        self.zoom_range = (range.start_bound().cloned(), range.end_bound().cloned());
+        self
+    }
+
+    /// Sets whether the [`Buffer`] can be skipped when drawing exceeds the
+    /// frame budget of the [`Infinite`].
+    ///
+    /// See [`Infinite::frame_budget`].
+    pub fn low_priority(mut self, low_priority: bool) -> Self {
+        self.low_priority = low_priority;
+        self
+    }
+
+    /// Applies `transform` to all items recorded from now on, after any
+    /// transforms already pushed, until it is removed with
+    /// [`Buffer::pop_transform`].
+    ///
+    /// Items are transformed in the [`Coordinates`] of the [`Buffer`], before
+    /// they are placed on the [`Infinite`]. To rotate or scale an item about
+    /// its own pivot, translate the pivot to the origin first, as in
+    /// `Transform::translation(-x, -y).then_rotate(angle).then_translate(..)`.
+    ///
+    /// Only the positions of texts are transformed.
+    pub fn push_transform(&mut self, transform: Transform) {
+        let transform = match self.transforms.last() {
+            Some(current) => transform.then(current),
+            None => transform,
+        };
+
+        self.transforms.push(transform);
+    }
+
+    /// Removes the transform last added with [`Buffer::push_transform`].
+    pub fn pop_transform(&mut self) {
+        self.transforms.pop();
+    }
+
+    /// Applies `transform` to the items recorded by `f`, see
+    /// [`Buffer::push_transform`].
+    pub fn with_transform(&mut self, transform: Transform, f: impl FnOnce(&mut Self)) {
+        self.push_transform(transform);
+        f(self);
+        self.pop_transform();
+    }
+
+    /// Stores the current transform, item z-index and item id of the
+    /// [`Buffer`], runs `f` and restores them, like [`Frame::with_save`].
+    ///
+    /// Items recorded by `f` can be placed relative to a parent by changing
+    /// the transform with [`Buffer::translate`], [`Buffer::rotate`] and
+    /// [`Buffer::scale`]. Calls can be nested to draw hierarchical content.
+    pub fn with_save(&mut self, f: impl FnOnce(&mut Self)) {
+        let transforms = self.transforms.clone();
+        let item_z_index = self.item_z_index;
+        let item_id = self.item_id;
+        f(self);
+        self.transforms = transforms;
+        self.item_z_index = item_z_index;
+        self.item_id = item_id;
+    }
+
+    /// Moves the origin of the items recorded from now on by `translation`.
+    pub fn translate(&mut self, translation: Vector) {
+        self.apply_transform(Transform::translation(translation.x, translation.y));
+    }
+
+    /// Rotates the items recorded from now on by `angle` around the origin.
+    pub fn rotate(&mut self, angle: impl Into<Radians>) {
+        let angle = angle.into();
+        self.apply_transform(Transform::rotation(path::lyon_path::math::Angle::radians(
+            angle.0,
+        )));
+    }
+
+    /// Scales the items recorded from now on by `scale` around the origin.
+    pub fn scale(&mut self, scale: impl Into<f32>) {
+        let scale = scale.into();
+        self.apply_transform(Transform::scale(scale, scale));
+    }
+
+    /// Scales the items recorded from now on by a different amount along each
+    /// axis around the origin.
+    pub fn scale_nonuniform(&mut self, scale: impl Into<Vector>) {
+        let scale = scale.into();
+        self.apply_transform(Transform::scale(scale.x, scale.y));
+    }
+
+    /// Applies `transform` before the current transform of the [`Buffer`].
+    fn apply_transform(&mut self, transform: Transform) {
+        match self.transforms.last_mut() {
+            Some(current) => *current = transform.then(current),
+            None => self.transforms.push(transform),
         }
     }
 
-    fn tag(&self) -> tree::Tag {
-        tree::Tag::of::<InfiniteState<P::State>>()
+    /// Draws the characters of the given [`Text`] on the [`Infinite`] canvas with the anchor.
+    pub fn draw_text_anchored(&mut self, text: impl Into<Text>, anchor: Anchor) {
+        let text = self.transform_text(text.into());
+        self.record(Item::Text(text, anchor))
     }
 
-    fn state(&self) -> tree::State {
-        let state = self.program.init_state();
-        let mut state = InfiniteState::<P::State>::new(state);
+    /// Draws the characters of the given [`Text`] on the [`Infinite`] canvas using the anchor of the [`Buffer`].
+    pub fn draw_text(&mut self, text: impl Into<Text>) {
+        let text = self.transform_text(text.into());
+        self.record(Item::Text(text, self.anchor.unwrap_or_default()))
+    }
 
-        state.offset = self.program.init_scroll();
-        state.set_scale_level(self.program.init_zoom());
+    /// Draws a sequence of [`Span`](text::Span)s, each with its own color,
+    /// font and size, as a single text whose top-left corner is at `position`
+    /// with the anchor.
+    ///
+    /// The spans are laid out together, like a [`rich_text`] widget, with
+    /// `style` used for anything a span leaves unset. Lines only break at
+    /// line breaks in the spans.
+    ///
+    /// [`rich_text`]: iced::widget::rich_text()
+    pub fn draw_rich_text_anchored(
+        &mut self,
+        spans: &[text::Span<'_>],
+        position: impl Into<Point>,
+        style: TextStyle,
+        anchor: Anchor,
+    ) {
+        let position = self.transform_point(position.into());
+        let style = self.text_style(style);
+        self.record(Item::RichText(
+            position,
+            layout_spans(spans, &style),
+            anchor,
+        ))
+    }
 
-        tree::State::new(state)
+    /// Draws a sequence of [`Span`](text::Span)s, each with its own color,
+    /// font and size, as a single text whose top-left corner is at `position`
+    /// with the [`Buffer`]'s anchor.
+    ///
+    /// See [`Buffer::draw_rich_text_anchored`].
+    pub fn draw_rich_text(
+        &mut self,
+        spans: &[text::Span<'_>],
+        position: impl Into<Point>,
+        style: TextStyle,
+    ) {
+        let anchor = self.anchor.unwrap_or_default();
+        self.draw_rich_text_anchored(spans, position, style, anchor)
     }
 
-    fn on_event(
+    /// Draws the characters of the given [`Text`] on the [`Infinite`] canvas
+    /// turned by `angle` about its position, with the anchor.
+    ///
+    /// The angle is measured from the X axis towards the Y axis, so a quarter
+    /// turn gives text running up the Y axis, as for the label of an axis.
+    pub fn draw_text_rotated_anchored(
         &mut self,
-        state: &mut tree::Tree,
-        event: iced::Event,
-        layout: layout::Layout<'_>,
-        cursor: advanced::mouse::Cursor,
-        _renderer: &Renderer,
-        _clipboard: &mut dyn advanced::Clipboard,
-        shell: &mut advanced::Shell<'_, Message>,
-        _viewport: &Rectangle,
-    ) -> iced_event::Status {
-        let bounds = layout.bounds();
+        text: impl Into<Text>,
+        angle: impl Into<Radians>,
+        anchor: Anchor,
+    ) {
+        let text = self.transform_text(text.into());
+        self.record(Item::RotatedText(text, angle.into(), anchor))
+    }
 
-        let canvas_event = {
-            let state = state.state.downcast_ref::<InfiniteState<P::State>>();
+    /// Draws the characters of the given [`Text`] on the [`Infinite`] canvas
+    /// turned by `angle` about its position, using the anchor of the
+    /// [`Buffer`].
+    ///
+    /// See [`Buffer::draw_text_rotated_anchored`].
+    pub fn draw_text_rotated(&mut self, text: impl Into<Text>, angle: impl Into<Radians>) {
+        let anchor = self.anchor.unwrap_or_default();
+        self.draw_text_rotated_anchored(text, angle, anchor)
+    }
 
-            wrap_event(event.clone(), bounds, state.offset, state.scale)
-        };
+    /// Draws the given [`Text`] on a rounded background of the provided color
+    /// with the anchor, as for badges and tags.
+    ///
+    /// The background fits the text with `padding` around it. Like the text,
+    /// its size is in screen pixels and does not change with the zoom.
+    pub fn draw_label_anchored(
+        &mut self,
+        text: impl Into<Text>,
+        padding: impl Into<Padding>,
+        background: impl Into<Color>,
+        radius: impl Into<Radius>,
+        anchor: Anchor,
+    ) {
+        let text = self.transform_text(text.into());
+        self.record(Item::Label(
+            text,
+            padding.into(),
+            background.into(),
+            radius.into(),
+            anchor,
+        ))
+    }
 
-        if let Some(canvas_event) = canvas_event {
-            let state = state.state.downcast_mut::<InfiniteState<P::State>>();
-            let (cursor, infinite) = get_cursors(cursor, bounds, state.offset, state.scale);
+    /// Draws the given [`Text`] on a rounded background of the provided color
+    /// with the [`Buffer`]'s anchor, as for badges and tags.
+    ///
+    /// See [`Buffer::draw_label_anchored`].
+    pub fn draw_label(
+        &mut self,
+        text: impl Into<Text>,
+        padding: impl Into<Padding>,
+        background: impl Into<Color>,
+        radius: impl Into<Radius>,
+    ) {
+        let anchor = self.anchor.unwrap_or_default();
+        self.draw_label_anchored(text, padding, background, radius, anchor)
+    }
 
-            let (status, message) =
-                self.program
-                    .update(&mut state.state, canvas_event, bounds, cursor, infinite);
+    /// Fills a `region` of the screen, placed relative to the given
+    /// `position` on the [`Infinite`] canvas, with the provided color and
+    /// anchor.
+    ///
+    /// Like text, the `region` is in screen pixels and does not change with
+    /// the zoom, with its Y axis pointing down. This lays out highlights and
+    /// carets around text, see [`TextEditor`](crate::text_edit::TextEditor).
+    pub fn fill_screen_rectangle_anchored(
+        &mut self,
+        position: Point,
+        region: Rectangle,
+        color: impl Into<Color>,
+        anchor: Anchor,
+    ) {
+        let position = self.transform_point(position);
+        self.record(Item::ScreenRectangle(
+            position,
+            region,
+            color.into(),
+            anchor,
+        ))
+    }
+
+    /// Fills a `region` of the screen, placed relative to the given
+    /// `position` on the [`Infinite`] canvas, with the provided color and the
+    /// [`Buffer`]'s anchor.
+    ///
+    /// See [`Buffer::fill_screen_rectangle_anchored`].
+    pub fn fill_screen_rectangle(
+        &mut self,
+        position: Point,
+        region: Rectangle,
+        color: impl Into<Color>,
+    ) {
+        let anchor = self.anchor.unwrap_or_default();
+        self.fill_screen_rectangle_anchored(position, region, color, anchor)
+    }
+
+    /// Draws the `content` wrapped within the given region of the
+    /// [`Infinite`] canvas, given by its bottom-left corner, with the
+    /// provided style and anchor.
+    ///
+    /// Like other text, the size of the text does not change with the zoom,
+    /// so the lines are wrapped anew as the region grows or shrinks on the
+    /// screen. Lines which do not fit in the region are left out.
+    pub fn draw_text_box_anchored(
+        &mut self,
+        content: impl Into<String>,
+        bounds: Rectangle,
+        style: TextStyle,
+        anchor: Anchor,
+    ) {
+        let region = self.transform_region(Path::rectangle(bounds.position(), bounds.size()));
+        let style = self.text_style(style);
+        self.record(Item::TextBox(region, content.into(), style, anchor))
+    }
+
+    /// Draws the `content` wrapped within the given region of the
+    /// [`Infinite`] canvas, given by its bottom-left corner, with the
+    /// provided style and the [`Buffer`]'s anchor.
+    ///
+    /// See [`Buffer::draw_text_box_anchored`].
+    pub fn draw_text_box(
+        &mut self,
+        content: impl Into<String>,
+        bounds: Rectangle,
+        style: TextStyle,
+    ) {
+        let anchor = self.anchor.unwrap_or_default();
+        self.draw_text_box_anchored(content, bounds, style, anchor)
+    }
+
+    /// Draws the fill of the given [`Path`] on the [`Infinite`] canvas with an anchor by filling it with the provided style.
+    pub fn fill_anchored(&mut self, path: Path, fill: impl Into<Fill>, anchor: Anchor) {
+        let (path, fill) = self.transform_fill(path, fill.into());
+        self.record(Item::Fill(path, fill, anchor))
+    }
+
+    /// Draws the fill of the given [`Path`] on the [`Infinite`] canvas with the [`Buffer`]'s anchor by filling it with the provided style.
+    pub fn fill(&mut self, path: Path, fill: impl Into<Fill>) {
+        let (path, fill) = self.transform_fill(path, fill.into());
+        self.record(Item::Fill(path, fill, self.anchor.unwrap_or_default()))
+    }
+
+    /// Fills copies of the `motif` repeated every `spacing` units along each
+    /// axis over the whole visible region with the provided style and
+    /// anchor, see [`Buffer::fill_infinite_pattern`].
+    pub fn fill_infinite_pattern_anchored(
+        &mut self,
+        motif: Path,
+        spacing: impl Into<Size>,
+        fill: impl Into<Fill>,
+        anchor: Anchor,
+    ) {
+        let (motif, fill) = self.transform_fill(motif, fill.into());
+        self.record(Item::Pattern(motif, fill, spacing.into(), anchor))
+    }
+
+    /// Fills copies of the `motif` repeated every `spacing` units along each
+    /// axis over the whole visible region with the provided style and the
+    /// [`Buffer`]'s anchor, as for graph paper, dot grids and checkerboards.
+    ///
+    /// The `motif` is given for the copy at the origin, in Cartesian
+    /// co-ordinates. When zoomed out too far to draw every copy, only some
+    /// of the region is covered.
+    pub fn fill_infinite_pattern(
+        &mut self,
+        motif: Path,
+        spacing: impl Into<Size>,
+        fill: impl Into<Fill>,
+    ) {
+        let anchor = self.anchor.unwrap_or_default();
+        self.fill_infinite_pattern_anchored(motif, spacing, fill, anchor)
+    }
+
+    /// Draws the stroke of the given [`Path`] on the [`Infinite`] canvas with the provided style and anchor.
+    pub fn stroke_anchored(&mut self, path: Path, stroke: impl Into<Stroke<'a>>, anchor: Anchor) {
+        let (path, stroke) = self.transform_stroke(path, stroke.into());
+        self.record(Item::Stroke(path, stroke, anchor))
+    }
+
+    /// Draws the stroke of the given [`Path`] on the [`Infinite`] canvas with the provided style and the [`Buffer`]'s anchor.
+    ///
+    /// The width and dash pattern of the [`Stroke`] are in screen pixels,
+    /// whatever the zoom.
+    pub fn stroke(&mut self, path: Path, stroke: impl Into<Stroke<'a>>) {
+        let (path, stroke) = self.transform_stroke(path, stroke.into());
+        self.record(Item::Stroke(path, stroke, self.anchor.unwrap_or_default()))
+    }
+
+    /// Draws the stroke of the given [`Path`] as marching ants with the
+    /// provided style and anchor, see [`Buffer::stroke_marching_ants`].
+    pub fn stroke_marching_ants_anchored(
+        &mut self,
+        path: Path,
+        stroke: impl Into<Stroke<'a>>,
+        speed: f32,
+        anchor: Anchor,
+    ) {
+        let (path, stroke) = self.transform_stroke(path, stroke.into());
+        self.record(Item::MarchingAnts(path, stroke, speed, anchor))
+    }
+
+    /// Draws the stroke of the given [`Path`] as marching ants with the
+    /// provided style and the [`Buffer`]'s anchor: a dashed line whose dashes
+    /// move along it by `speed` pixels per second, as for selection outlines.
+    ///
+    /// The dash pattern of the [`Stroke`] is used, in screen pixels, or a
+    /// default pattern if it has none. The dashes only move while the
+    /// [`Infinite`] is redrawn, so the [`Program`] should report them through
+    /// [`Program::is_animating`].
+    pub fn stroke_marching_ants(&mut self, path: Path, stroke: impl Into<Stroke<'a>>, speed: f32) {
+        let anchor = self.anchor.unwrap_or_default();
+        self.stroke_marching_ants_anchored(path, stroke, speed, anchor)
+    }
+
+    /// Draws a rectangle given its bottom-left corner coordinate, [`Size`] and [`Anchor`] by filling it with the provided style.
+    pub fn fill_rectangle_anchored(
+        &mut self,
+        bottom_left: impl Into<Point>,
+        size: impl Into<Size>,
+        fill: impl Into<Fill>,
+        anchor: Anchor,
+    ) {
+        let size: Size = size.into();
+        let bottom_left = bottom_left.into();
+
+        let path = Path::rectangle(bottom_left, size);
+
+        self.fill_anchored(path, fill, anchor)
+    }
+
+    /// Draws a rectangle given its bottom-left corner coordinate and its [`Size`] by filling it with the provided style and the [`Buffer`]'s anchor.
+    pub fn fill_rectangle(
+        &mut self,
+        bottom_left: impl Into<Point>,
+        size: impl Into<Size>,
+        fill: impl Into<Fill>,
+    ) {
+        let size: Size = size.into();
+        let bottom_left = bottom_left.into();
+
+        let path = Path::rectangle(bottom_left, size);
+
+        self.fill_anchored(path, fill, self.anchor.unwrap_or_default())
+    }
+
+    /// Draws a rounded rectangle given its bottom-left corner coordinate, [`Size`] and [`Anchor`] by filling it with the provided style.
+    pub fn fill_rounded_rectangle_anchored(
+        &mut self,
+        bottom_left: impl Into<Point>,
+        size: impl Into<Size>,
+        radius: impl Into<Radius>,
+        fill: impl Into<Fill>,
+        anchor: Anchor,
+    ) {
+        let size: Size = size.into();
+        let bottom_left = bottom_left.into();
+
+        let path = Path::rounded_rectangle(bottom_left, size, radius.into());
+
+        self.fill_anchored(path, fill, anchor);
+    }
+
+    /// Draws a rounded rectangle given its bottom-left corner coordinate and its [`Size`] by filling it with the provided style and the [`Buffer`]'s anchor.
+    pub fn fill_rounded_rectangle(
+        &mut self,
+        bottom_left: impl Into<Point>,
+        size: impl Into<Size>,
+        radius: impl Into<Radius>,
+        fill: impl Into<Fill>,
+    ) {
+        let size: Size = size.into();
+        let bottom_left = bottom_left.into();
+
+        let path = Path::rounded_rectangle(bottom_left, size, radius.into());
+
+        self.fill(path, fill);
+    }
+
+    /// Draws the stroke of a rectangle with the provided style given its bottom-left corner coordinate and its [`Size`].
+    pub fn stroke_rect_anchored(
+        &mut self,
+        bottom_left: impl Into<Point>,
+        size: impl Into<Size>,
+        stroke: impl Into<Stroke<'a>>,
+        anchor: Anchor,
+    ) {
+        let size: Size = size.into();
+        let bottom_left = bottom_left.into();
+
+        let path = Path::rectangle(bottom_left, size);
+
+        self.stroke_anchored(path, stroke, anchor)
+    }
+
+    /// Draws the stroke of a rectangle with the provided style given its bottom-left corner coordinate and its [`Size`] and the [`Buffer`]'s anchor.
+    pub fn stroke_rectangle(
+        &mut self,
+        bottom_left: impl Into<Point>,
+        size: impl Into<Size>,
+        stroke: impl Into<Stroke<'a>>,
+    ) {
+        let size: Size = size.into();
+        let bottom_left = bottom_left.into();
+
+        let path = Path::rectangle(bottom_left, size);
+
+        self.stroke(path, stroke)
+    }
+
+    /// Draws the stroke of a rounded rectangle with the provided style given its bottom-left corner coordinate and its [`Size`].
+    pub fn stroke_rounded_rectangle_anchored(
+        &mut self,
+        bottom_left: impl Into<Point>,
+        size: impl Into<Size>,
+        radius: impl Into<Radius>,
+        stroke: impl Into<Stroke<'a>>,
+        anchor: Anchor,
+    ) {
+        let size: Size = size.into();
+        let bottom_left = bottom_left.into();
+
+        let path = Path::rounded_rectangle(bottom_left, size, radius.into());
+
+        self.stroke_anchored(path, stroke, anchor);
+    }
+
+    /// Draws the stroke of a rounded rectangle with the provided style given its bottom-left corner coordinate and its [`Size`] and the [`Buffer`]'s anchor.
+    pub fn stroke_rounded_rectangle(
+        &mut self,
+        bottom_left: impl Into<Point>,
+        size: impl Into<Size>,
+        radius: impl Into<Radius>,
+        stroke: impl Into<Stroke<'a>>,
+    ) {
+        let size: Size = size.into();
+        let bottom_left = bottom_left.into();
+
+        let path = Path::rounded_rectangle(bottom_left, size, radius.into());
+
+        self.stroke(path, stroke);
+    }
+
+    /// Draws a circle given its center, radius and [`Anchor`] by filling it
+    /// with the provided style.
+    pub fn fill_circle_anchored(
+        &mut self,
+        center: impl Into<Point>,
+        radius: f32,
+        fill: impl Into<Fill>,
+        anchor: Anchor,
+    ) {
+        let path = Path::circle(center.into(), radius);
+
+        self.fill_anchored(path, fill, anchor)
+    }
+
+    /// Draws a circle given its center and radius by filling it with the
+    /// provided style and the [`Buffer`]'s anchor.
+    pub fn fill_circle(&mut self, center: impl Into<Point>, radius: f32, fill: impl Into<Fill>) {
+        let path = Path::circle(center.into(), radius);
+
+        self.fill(path, fill)
+    }
+
+    /// Draws the stroke of a circle with the provided style given its center,
+    /// radius and [`Anchor`].
+    pub fn stroke_circle_anchored(
+        &mut self,
+        center: impl Into<Point>,
+        radius: f32,
+        stroke: impl Into<Stroke<'a>>,
+        anchor: Anchor,
+    ) {
+        let path = Path::circle(center.into(), radius);
+
+        self.stroke_anchored(path, stroke, anchor)
+    }
+
+    /// Draws the stroke of a circle with the provided style given its center
+    /// and radius and the [`Buffer`]'s anchor.
+    pub fn stroke_circle(
+        &mut self,
+        center: impl Into<Point>,
+        radius: f32,
+        stroke: impl Into<Stroke<'a>>,
+    ) {
+        let path = Path::circle(center.into(), radius);
+
+        self.stroke(path, stroke)
+    }
+
+    /// Draws a polygon through the given `points` and [`Anchor`] by filling it
+    /// with the provided style.
+    pub fn fill_polygon_anchored(
+        &mut self,
+        points: &[Point],
+        fill: impl Into<Fill>,
+        anchor: Anchor,
+    ) {
+        self.fill_anchored(polygon(points), fill, anchor)
+    }
+
+    /// Draws a polygon through the given `points` by filling it with the
+    /// provided style and the [`Buffer`]'s anchor.
+    ///
+    /// See [`regular_polygon`] for the points of a regular polygon.
+    pub fn fill_polygon(&mut self, points: &[Point], fill: impl Into<Fill>) {
+        self.fill(polygon(points), fill)
+    }
+
+    /// Draws the stroke of a polygon through the given `points` with the
+    /// provided style and anchor.
+    pub fn stroke_polygon_anchored(
+        &mut self,
+        points: &[Point],
+        stroke: impl Into<Stroke<'a>>,
+        anchor: Anchor,
+    ) {
+        self.stroke_anchored(polygon(points), stroke, anchor)
+    }
+
+    /// Draws the stroke of a polygon through the given `points` with the
+    /// provided style and the [`Buffer`]'s anchor.
+    ///
+    /// See [`regular_polygon`] for the points of a regular polygon.
+    pub fn stroke_polygon(&mut self, points: &[Point], stroke: impl Into<Stroke<'a>>) {
+        self.stroke(polygon(points), stroke)
+    }
+
+    /// Draws an ellipse given its center, its radii along the X and Y axes
+    /// and [`Anchor`] by filling it with the provided style.
+    pub fn fill_ellipse_anchored(
+        &mut self,
+        center: impl Into<Point>,
+        radii: impl Into<Vector>,
+        fill: impl Into<Fill>,
+        anchor: Anchor,
+    ) {
+        self.fill_anchored(ellipse(center.into(), radii.into()), fill, anchor)
+    }
+
+    /// Draws an ellipse given its center and its radii along the X and Y axes
+    /// by filling it with the provided style and the [`Buffer`]'s anchor.
+    pub fn fill_ellipse(
+        &mut self,
+        center: impl Into<Point>,
+        radii: impl Into<Vector>,
+        fill: impl Into<Fill>,
+    ) {
+        self.fill(ellipse(center.into(), radii.into()), fill)
+    }
+
+    /// Draws the stroke of a circular arc with the provided style and anchor.
+    ///
+    /// Angles are measured from the X axis towards the Y axis.
+    pub fn stroke_arc_anchored(
+        &mut self,
+        center: impl Into<Point>,
+        radius: f32,
+        start_angle: impl Into<Radians>,
+        end_angle: impl Into<Radians>,
+        stroke: impl Into<Stroke<'a>>,
+        anchor: Anchor,
+    ) {
+        let path = arc(
+            center.into(),
+            radius,
+            start_angle.into(),
+            end_angle.into(),
+            false,
+        );
+
+        self.stroke_anchored(path, stroke, anchor)
+    }
+
+    /// Draws the stroke of a circular arc with the provided style and the
+    /// [`Buffer`]'s anchor.
+    ///
+    /// Angles are measured from the X axis towards the Y axis.
+    pub fn stroke_arc(
+        &mut self,
+        center: impl Into<Point>,
+        radius: f32,
+        start_angle: impl Into<Radians>,
+        end_angle: impl Into<Radians>,
+        stroke: impl Into<Stroke<'a>>,
+    ) {
+        let path = arc(
+            center.into(),
+            radius,
+            start_angle.into(),
+            end_angle.into(),
+            false,
+        );
+
+        self.stroke(path, stroke)
+    }
+
+    /// Draws a slice of a circle between two angles and [`Anchor`] by filling
+    /// it with the provided style.
+    ///
+    /// Angles are measured from the X axis towards the Y axis.
+    pub fn fill_pie_anchored(
+        &mut self,
+        center: impl Into<Point>,
+        radius: f32,
+        start_angle: impl Into<Radians>,
+        end_angle: impl Into<Radians>,
+        fill: impl Into<Fill>,
+        anchor: Anchor,
+    ) {
+        let path = arc(
+            center.into(),
+            radius,
+            start_angle.into(),
+            end_angle.into(),
+            true,
+        );
+
+        self.fill_anchored(path, fill, anchor)
+    }
+
+    /// Draws a slice of a circle between two angles by filling it with the
+    /// provided style and the [`Buffer`]'s anchor.
+    ///
+    /// Angles are measured from the X axis towards the Y axis.
+    pub fn fill_pie(
+        &mut self,
+        center: impl Into<Point>,
+        radius: f32,
+        start_angle: impl Into<Radians>,
+        end_angle: impl Into<Radians>,
+        fill: impl Into<Fill>,
+    ) {
+        let path = arc(
+            center.into(),
+            radius,
+            start_angle.into(),
+            end_angle.into(),
+            true,
+        );
+
+        self.fill(path, fill)
+    }
+
+    /// Draws an arrow from `from` to `to` with the provided style and anchor.
+    pub fn stroke_arrow_anchored(
+        &mut self,
+        from: impl Into<Point>,
+        to: impl Into<Point>,
+        stroke: impl Into<Stroke<'a>>,
+        style: ArrowStyle,
+        anchor: Anchor,
+    ) {
+        let (from, to) = (from.into(), to.into());
+
+        let line = Path::line(from, to);
+        let heads = [
+            Some(style.head(to, to - from)),
+            style.start_head.then(|| style.head(from, from - to)),
+        ];
+
+        self.record_arrow(line, heads, stroke.into(), style, anchor)
+    }
+
+    /// Draws an arrow from `from` to `to` with the provided style and the
+    /// [`Buffer`]'s anchor.
+    pub fn stroke_arrow(
+        &mut self,
+        from: impl Into<Point>,
+        to: impl Into<Point>,
+        stroke: impl Into<Stroke<'a>>,
+        style: ArrowStyle,
+    ) {
+        let anchor = self.anchor.unwrap_or_default();
+        self.stroke_arrow_anchored(from, to, stroke, style, anchor)
+    }
+
+    /// Draws an arrow along the cubic Bézier curve from `from` to `to` with the
+    /// given control points, with the provided style and anchor.
+    ///
+    /// Heads point along the curve at its ends.
+    #[allow(clippy::too_many_arguments)]
+    pub fn stroke_bezier_arrow_anchored(
+        &mut self,
+        from: impl Into<Point>,
+        control_a: impl Into<Point>,
+        control_b: impl Into<Point>,
+        to: impl Into<Point>,
+        stroke: impl Into<Stroke<'a>>,
+        style: ArrowStyle,
+        anchor: Anchor,
+    ) {
+        let (from, to) = (from.into(), to.into());
+        let (control_a, control_b) = (control_a.into(), control_b.into());
+
+        let curve = Path::new(|builder| {
+            builder.move_to(from);
+            builder.bezier_curve_to(control_a, control_b, to);
+        });
+
+        // The tangent at an end runs to its nearest distinct control point
+        let toward = |end: Point, controls: [Point; 3]| {
+            controls
+                .into_iter()
+                .find(|control| *control != end)
+                .map_or(Vector::ZERO, |control| end - control)
+        };
+
+        let heads = [
+            Some(style.head(to, toward(to, [control_b, control_a, from]))),
+            style
+                .start_head
+                .then(|| style.head(from, toward(from, [control_a, control_b, to]))),
+        ];
+
+        self.record_arrow(curve, heads, stroke.into(), style, anchor)
+    }
+
+    /// Draws an arrow along the cubic Bézier curve from `from` to `to` with the
+    /// given control points, with the provided style and the [`Buffer`]'s
+    /// anchor.
+    ///
+    /// Heads point along the curve at its ends.
+    pub fn stroke_bezier_arrow(
+        &mut self,
+        from: impl Into<Point>,
+        control_a: impl Into<Point>,
+        control_b: impl Into<Point>,
+        to: impl Into<Point>,
+        stroke: impl Into<Stroke<'a>>,
+        style: ArrowStyle,
+    ) {
+        let anchor = self.anchor.unwrap_or_default();
+        self.stroke_bezier_arrow_anchored(from, control_a, control_b, to, stroke, style, anchor)
+    }
+
+    /// Records the `line` of an arrow and its `heads`.
+    fn record_arrow(
+        &mut self,
+        line: Path,
+        heads: [Option<Path>; 2],
+        stroke: Stroke<'a>,
+        style: ArrowStyle,
+        anchor: Anchor,
+    ) {
+        self.stroke_anchored(line, stroke, anchor);
+
+        let stroke = Stroke {
+            line_dash: stroke::LineDash::default(),
+            ..stroke
+        };
+
+        for head in heads.into_iter().flatten() {
+            if style.closed {
+                let fill = Fill {
+                    style: stroke.style,
+                    ..Fill::default()
+                };
+
+                self.fill_anchored(head.clone(), fill, anchor);
+            }
+
+            self.stroke_anchored(head, stroke, anchor);
+        }
+    }
+
+    /// Draws the stroke of a polar grid around the origin with the provided
+    /// style and the [`Buffer`]'s anchor.
+    ///
+    /// The grid has `rings` evenly spaced circles out to `radius`, and
+    /// `spokes` evenly spaced lines from the origin to the outermost circle.
+    pub fn stroke_polar_grid(
+        &mut self,
+        radius: f32,
+        rings: usize,
+        spokes: usize,
+        stroke: impl Into<Stroke<'a>>,
+    ) {
+        let coordinates = self.coordinates;
+        let point = |radius: f32, angle: f32| match coordinates {
+            Coordinates::Cartesian => Point::new(radius * angle.cos(), radius * angle.sin()),
+            Coordinates::Polar => Point::new(radius, angle),
+        };
+
+        let path = Path::new(|builder| {
+            for ring in 1..=rings {
+                let radius = radius * ring as f32 / rings as f32;
+
+                match coordinates {
+                    Coordinates::Cartesian => builder.circle(Point::ORIGIN, radius),
+                    Coordinates::Polar => {
+                        builder.move_to(point(radius, 0.0));
+                        builder.line_to(point(radius, 2.0 * PI));
+                    }
+                }
+            }
+
+            for spoke in 0..spokes {
+                let angle = 2.0 * PI * spoke as f32 / spokes as f32;
+
+                builder.move_to(point(0.0, angle));
+                builder.line_to(point(radius, angle));
+            }
+        });
+
+        self.stroke(path, stroke);
+    }
+
+    /// Returns the region of the [`Infinite`] covered by the items of the
+    /// [`Buffer`], given by its bottom-left corner.
+    ///
+    /// Only items which move with the canvas, those with [`Anchor::None`], are
+    /// considered. The width of strokes and the extent of texts are in screen
+    /// pixels, so only the [`Path`]s and the positions of texts are counted.
+    ///
+    /// Returns [`None`] if the [`Buffer`] has no such items.
+    pub fn bounds(&self) -> Option<Rectangle> {
+        let unanchored = |anchor: &Anchor| self.anchor.unwrap_or(*anchor) == Anchor::None;
+
+        let regions = self
+            .items
+            .iter()
+            .map(|(_, _, item)| item)
+            .filter(|item| unanchored(&item.anchor()))
+            .filter_map(|item| match item {
+                Item::Fill(path, _, _)
+                | Item::Stroke(path, _, _)
+                | Item::MarchingAnts(path, _, _, _) => match self.coordinates {
+                    Coordinates::Cartesian => path_bounds(path),
+                    Coordinates::Polar => path_bounds(&polar_path(path, 1.0)),
+                },
+                Item::Text(text, _) => {
+                    Some(Rectangle::new(self.to_cartesian(text.position), Size::ZERO))
+                }
+                Item::TextBox(region, _, _, _) => match self.coordinates {
+                    Coordinates::Cartesian => path_bounds(region),
+                    Coordinates::Polar => path_bounds(&polar_path(region, 1.0)),
+                },
+                Item::RichText(position, _, _) => {
+                    Some(Rectangle::new(self.to_cartesian(*position), Size::ZERO))
+                }
+                Item::Label(text, ..) | Item::RotatedText(text, _, _) => {
+                    Some(Rectangle::new(self.to_cartesian(text.position), Size::ZERO))
+                }
+                Item::ScreenRectangle(position, ..) => {
+                    Some(Rectangle::new(self.to_cartesian(*position), Size::ZERO))
+                }
+                Item::Lines(..) | Item::Badge(..) | Item::Pattern(..) => None,
+            });
+
+        crate::geometry::union(regions)
+    }
+
+    /// Records an `item` with the current item z-index and id, after all
+    /// items with the same or a lower z-index.
+    fn record(&mut self, item: Item<'a>) {
+        let z_index = self.item_z_index;
+        let index = self.items.partition_point(|(z, _, _)| *z <= z_index);
+
+        self.items.insert(index, (z_index, self.item_id, item));
+    }
+
+    /// Applies the current transform of the [`Buffer`] to a `point`.
+    fn transform_point(&self, point: Point) -> Point {
+        match self.transforms.last() {
+            Some(transform) => {
+                let point =
+                    transform.transform_point(path::lyon_path::math::point(point.x, point.y));
+
+                Point::new(point.x, point.y)
+            }
+            None => point,
+        }
+    }
+
+    /// Applies the current transform of the [`Buffer`] to the endpoints of a
+    /// gradient `style`.
+    fn transform_gradient(&self, style: fill::Style) -> fill::Style {
+        match style {
+            fill::Style::Solid(_) => style,
+            fill::Style::Gradient(Gradient::Linear(linear)) => {
+                fill::Style::Gradient(Gradient::Linear(gradient::Linear {
+                    start: self.transform_point(linear.start),
+                    end: self.transform_point(linear.end),
+                    ..linear
+                }))
+            }
+        }
+    }
+
+    fn transform_fill(&self, path: Path, fill: Fill) -> (Path, Fill) {
+        match self.transforms.last() {
+            Some(transform) => {
+                let fill = Fill {
+                    style: self.transform_gradient(fill.style),
+                    ..fill
+                };
+
+                (path.transform(transform), fill)
+            }
+            None => (path, fill),
+        }
+    }
+
+    fn transform_stroke(&self, path: Path, stroke: Stroke<'a>) -> (Path, Stroke<'a>) {
+        match self.transforms.last() {
+            Some(transform) => {
+                let stroke = Stroke {
+                    style: self.transform_gradient(stroke.style),
+                    ..stroke
+                };
+
+                (path.transform(transform), stroke)
+            }
+            None => (path, stroke),
+        }
+    }
+
+    fn transform_region(&self, region: Path) -> Path {
+        match self.transforms.last() {
+            Some(transform) => region.transform(transform),
+            None => region,
+        }
+    }
+
+    fn transform_text(&self, text: Text) -> Text {
+        let defaults = Text::default();
+
+        Text {
+            position: self.transform_point(text.position),
+            color: self
+                .text_color
+                .filter(|_| text.color == defaults.color)
+                .unwrap_or(text.color),
+            size: self
+                .text_size
+                .filter(|_| text.size == defaults.size)
+                .unwrap_or(text.size),
+            font: self
+                .font
+                .filter(|_| text.font == defaults.font)
+                .unwrap_or(text.font),
+            ..text
+        }
+    }
+
+    /// Falls back to the text defaults of the [`Buffer`] for the parts of
+    /// `style` left at their default.
+    fn text_style(&self, style: TextStyle) -> TextStyle {
+        let defaults = TextStyle::default();
+
+        TextStyle {
+            color: self
+                .text_color
+                .filter(|_| style.color == defaults.color)
+                .unwrap_or(style.color),
+            size: self
+                .text_size
+                .filter(|_| style.size == defaults.size)
+                .unwrap_or(style.size),
+            font: self
+                .font
+                .filter(|_| style.font == defaults.font)
+                .unwrap_or(style.font),
+            ..style
+        }
+    }
+
+    /// Converts a point given in the [`Coordinates`] of the [`Buffer`] to
+    /// cartesian co-ordinates.
+    fn to_cartesian(&self, point: Point) -> Point {
+        match self.coordinates {
+            Coordinates::Cartesian => point,
+            Coordinates::Polar => polar_point(point),
+        }
+    }
+
+    /// Transforms a `path` of the [`Buffer`] to fit the view of the `context`.
+    fn transform(&self, context: &RenderContext, path: &Path, anchor: Anchor) -> Path {
+        let polar;
+        let path = match self.coordinates {
+            Coordinates::Cartesian => path,
+            Coordinates::Polar => {
+                let screen = matches!(self.anchor.unwrap_or(anchor), Anchor::Screen(..));
+                let scale = if self.scale && !screen {
+                    context.view.scale.x.max(context.view.scale.y)
+                } else {
+                    1.0
+                };
+
+                polar = polar_path(path, scale);
+                &polar
+            }
+        };
+
+        transform_path(
+            &context.view,
+            context.center,
+            path,
+            self.anchor.unwrap_or(anchor),
+            self.scale,
+        )
+    }
+
+    /// Transforms the endpoints of a gradient `style` of the [`Buffer`] to fit
+    /// the view of the `context`.
+    fn transform_style(
+        &self,
+        context: &RenderContext,
+        style: fill::Style,
+        anchor: Anchor,
+    ) -> fill::Style {
+        match fade_style(style, self.opacity) {
+            style @ fill::Style::Solid(_) => style,
+            fill::Style::Gradient(Gradient::Linear(linear)) => {
+                let (coarse, transform) = view_transform(
+                    &context.view,
+                    context.center,
+                    self.anchor.unwrap_or(anchor),
+                    self.scale,
+                );
+
+                let transform_point = |point: Point| {
+                    let point = self.to_cartesian(point) - coarse;
+                    let point =
+                        transform.transform_point(path::lyon_path::math::point(point.x, point.y));
+
+                    Point::new(point.x, point.y)
+                };
+
+                fill::Style::Gradient(Gradient::Linear(gradient::Linear {
+                    start: transform_point(linear.start),
+                    end: transform_point(linear.end),
+                    ..linear
+                }))
+            }
+        }
+    }
+
+    /// Transforms an `item` of the [`Buffer`] to fit the view of the
+    /// `context`.
+    fn transform_item(&self, context: &RenderContext, item: &Item<'a>) -> Item<'a> {
+        match item {
+            Item::Fill(path, fill, anchor) => {
+                let fill = Fill {
+                    style: self.transform_style(context, fill.style, *anchor),
+                    ..*fill
+                };
+
+                Item::Fill(self.transform(context, path, *anchor), fill, *anchor)
+            }
+            Item::Stroke(path, stroke, anchor) => {
+                let stroke = Stroke {
+                    style: self.transform_style(context, stroke.style, *anchor),
+                    ..*stroke
+                };
+
+                Item::Stroke(self.transform(context, path, *anchor), stroke, *anchor)
+            }
+            Item::MarchingAnts(path, stroke, speed, anchor) => {
+                let segments = if stroke.line_dash.segments.is_empty() {
+                    &MARCHING_ANTS_DASH[..]
+                } else {
+                    stroke.line_dash.segments
+                };
+
+                let path = self.transform(context, path, *anchor);
+                let phase = marching_ants_epoch().elapsed().as_secs_f32() * speed;
+
+                let stroke = Stroke {
+                    style: self.transform_style(context, stroke.style, *anchor),
+                    line_dash: stroke::LineDash::default(),
+                    ..*stroke
+                };
+
+                Item::Stroke(dash(&path, segments, phase), stroke, *anchor)
+            }
+            Item::Text(text, anchor) => {
+                let text = Text {
+                    position: self.to_cartesian(text.position),
+                    color: text.color.scale_alpha(self.opacity),
+                    ..text.clone()
+                };
+
+                let text = transform_text(
+                    &context.view,
+                    context.center,
+                    &text,
+                    self.anchor.unwrap_or(*anchor),
+                );
+
+                Item::Text(text, *anchor)
+            }
+            Item::RotatedText(text, angle, anchor) => {
+                let text = Text {
+                    position: self.to_cartesian(text.position),
+                    color: text.color.scale_alpha(self.opacity),
+                    ..text.clone()
+                };
+
+                let text = transform_text(
+                    &context.view,
+                    context.center,
+                    &text,
+                    self.anchor.unwrap_or(*anchor),
+                );
+
+                // The angle on the screen, where the Y axis points down
+                let rotation = match self.anchor.unwrap_or(*anchor) {
+                    Anchor::Screen(..) => 0.0,
+                    _ => context.view.rotation,
+                };
+                let angle = context.view.y_axis.sign() * angle.0 - rotation;
+
+                Item::RotatedText(text, Radians(angle), *anchor)
+            }
+            Item::Pattern(motif, fill, spacing, anchor) => {
+                let fill = Fill {
+                    style: self.transform_style(context, fill.style, *anchor),
+                    ..*fill
+                };
+
+                let anchor = self.anchor.unwrap_or(*anchor);
+                let (coarse, transform) =
+                    view_transform(&context.view, context.center, anchor, self.scale);
+
+                // The region of the pane in the co-ordinates of the motif
+                let region = transform.inverse().and_then(|inverse| {
+                    let size = Vector::new(2.0 * context.center.x, 2.0 * context.center.y);
+                    let corners = [
+                        Point::ORIGIN,
+                        Point::new(size.x, 0.0),
+                        Point::new(0.0, size.y),
+                        Point::ORIGIN + size,
+                    ]
+                    .map(|corner| {
+                        let corner = inverse
+                            .transform_point(path::lyon_path::math::point(corner.x, corner.y));
+
+                        Point::new(corner.x, corner.y) + coarse
+                    });
+
+                    crate::geometry::bounds(&corners)
+                });
+
+                let path = region
+                    .map(|region| repeat_pattern(motif, *spacing, region))
+                    .unwrap_or_else(|| Path::new(|_| {}));
+
+                Item::Fill(
+                    transform_path(&context.view, context.center, &path, anchor, self.scale),
+                    fill,
+                    anchor,
+                )
+            }
+            Item::TextBox(region, content, style, anchor) => {
+                let region = self.transform(context, region, *anchor);
+                let style = TextStyle {
+                    color: style.color.scale_alpha(self.opacity),
+                    ..*style
+                };
+
+                let lines = path_bounds(&region)
+                    .map(|region| wrap_text(content, region, &style))
+                    .unwrap_or_default();
+
+                Item::Lines(lines, *anchor)
+            }
+            Item::RichText(position, pieces, anchor) => {
+                let position = translate_point(
+                    &context.view,
+                    context.center,
+                    self.to_cartesian(*position),
+                    self.anchor.unwrap_or(*anchor),
+                );
+
+                let lines = pieces
+                    .iter()
+                    .map(|(offset, piece)| Text {
+                        position: position + *offset,
+                        color: piece.color.scale_alpha(self.opacity),
+                        ..piece.clone()
+                    })
+                    .collect();
+
+                Item::Lines(lines, *anchor)
+            }
+            Item::Label(text, padding, background, radius, anchor) => {
+                let text = Text {
+                    position: self.to_cartesian(text.position),
+                    color: text.color.scale_alpha(self.opacity),
+                    ..text.clone()
+                };
+                let text = transform_text(
+                    &context.view,
+                    context.center,
+                    &text,
+                    self.anchor.unwrap_or(*anchor),
+                );
+
+                let size = min_text_bounds(&text.content, Size::INFINITY, text.size);
+                let x = match text.horizontal_alignment {
+                    alignment::Horizontal::Left => text.position.x,
+                    alignment::Horizontal::Center => text.position.x - size.width / 2.0,
+                    alignment::Horizontal::Right => text.position.x - size.width,
+                };
+                let y = match text.vertical_alignment {
+                    alignment::Vertical::Top => text.position.y,
+                    alignment::Vertical::Center => text.position.y - size.height / 2.0,
+                    alignment::Vertical::Bottom => text.position.y - size.height,
+                };
+
+                let region = Rectangle::new(Point::new(x, y), size).expand(*padding);
+                let path = Path::rounded_rectangle(region.position(), region.size(), *radius);
+                let fill = Fill::from(background.scale_alpha(self.opacity));
+
+                Item::Badge(path, fill, text, *anchor)
+            }
+            Item::ScreenRectangle(position, region, color, anchor) => {
+                let position = translate_point(
+                    &context.view,
+                    context.center,
+                    self.to_cartesian(*position),
+                    self.anchor.unwrap_or(*anchor),
+                );
+
+                let path =
+                    Path::rectangle(position + Vector::new(region.x, region.y), region.size());
+
+                Item::Fill(path, Fill::from(color.scale_alpha(self.opacity)), *anchor)
+            }
+            Item::Lines(lines, anchor) => Item::Lines(lines.clone(), *anchor),
+            Item::Badge(path, fill, text, anchor) => {
+                Item::Badge(path.clone(), *fill, text.clone(), *anchor)
+            }
+        }
+    }
+
+    /// Transforms the items of the [`Buffer`] to fit the view of the
+    /// `context`, from the lowest z-index to the highest.
+    fn transform_items(&self, context: &RenderContext) -> Vec<Item<'a>> {
+        self.items
+            .iter()
+            .map(|(_, _, item)| self.transform_item(context, item))
+            .collect()
+    }
+
+    /// Returns true if the [`Buffer`] has items which change over time.
+    fn is_animated(&self) -> bool {
+        self.items
+            .iter()
+            .any(|(_, _, item)| matches!(item, Item::MarchingAnts(..)))
+    }
+
+    /// Adds the areas of the items of the [`Buffer`] with an id to `hits`,
+    /// from the lowest z-index to the highest.
+    fn hits(&self, hits: &mut Vec<Hit>) {
+        let region = |path: &Path| match self.coordinates {
+            Coordinates::Cartesian => path_bounds(path),
+            Coordinates::Polar => path_bounds(&polar_path(path, 1.0)),
+        };
+        // The region of a text on the screen, relative to its position
+        let text_region = |text: &Text| {
+            let size = min_text_bounds(&text.content, Size::INFINITY, text.size);
+            let x = match text.horizontal_alignment {
+                alignment::Horizontal::Left => 0.0,
+                alignment::Horizontal::Center => -size.width / 2.0,
+                alignment::Horizontal::Right => -size.width,
+            };
+            let y = match text.vertical_alignment {
+                alignment::Vertical::Top => 0.0,
+                alignment::Vertical::Center => -size.height / 2.0,
+                alignment::Vertical::Bottom => -size.height,
+            };
+
+            Rectangle::new(Point::new(x, y), size)
+        };
+
+        for (_, id, item) in &self.items {
+            let Some(id) = *id else {
+                continue;
+            };
+
+            let area = match item {
+                Item::Fill(path, _, _) | Item::TextBox(path, _, _, _) => {
+                    region(path).map(|region| HitArea::Region(region, 0.0))
+                }
+                Item::Stroke(path, stroke, _) | Item::MarchingAnts(path, stroke, _, _) => {
+                    region(path).map(|region| HitArea::Region(region, stroke.width / 2.0))
+                }
+                Item::Text(text, _) | Item::RotatedText(text, _, _) => Some(HitArea::Text(
+                    self.to_cartesian(text.position),
+                    text_region(text),
+                )),
+                Item::Label(text, padding, ..) => Some(HitArea::Text(
+                    self.to_cartesian(text.position),
+                    text_region(text).expand(*padding),
+                )),
+                Item::RichText(position, pieces, _) => pieces
+                    .iter()
+                    .map(|(offset, text)| {
+                        let size = min_text_bounds(&text.content, Size::INFINITY, text.size);
+
+                        Rectangle::new(Point::ORIGIN + *offset, size)
+                    })
+                    .reduce(|a, b| a.union(&b))
+                    .map(|region| HitArea::Text(self.to_cartesian(*position), region)),
+                Item::ScreenRectangle(position, region, ..) => {
+                    Some(HitArea::Text(self.to_cartesian(*position), *region))
+                }
+                Item::Lines(..) | Item::Badge(..) | Item::Pattern(..) => None,
+            };
+
+            hits.extend(area.map(|area| Hit {
+                id,
+                anchor: self.anchor.unwrap_or(item.anchor()),
+                scale: self.scale,
+                area,
+            }));
+        }
+    }
+
+    /// Returns the [`BufferCache`] of the [`Buffer`] if its geometry can be
+    /// kept between frames.
+    fn cacheable(&self) -> Option<&BufferCache> {
+        // Patterns cover only the region visible when they were drawn
+        let moving = self.items.iter().all(|(_, _, item)| {
+            self.anchor.unwrap_or(item.anchor()) == Anchor::None
+                && !matches!(item, Item::Pattern(..))
+        });
+
+        self.cache
+            .as_ref()
+            .filter(|_| moving && !self.is_animated())
+    }
+
+    /// Draws the items of the [`Buffer`] from the lowest z-index to the
+    /// highest.
+    fn draw<Renderer: geometry::Renderer>(
+        &self,
+        frame: &mut Frame<Renderer>,
+        context: &RenderContext,
+    ) {
+        draw_items(frame, context, &self.transform_items(context))
+    }
+}
+
+/// The area of an item with an id, to find the items under the cursor, see
+/// [`Buffer::item_id`].
+#[derive(Debug, Clone)]
+struct Hit {
+    id: u64,
+    anchor: Anchor,
+    /// Whether the area is zoomed with the canvas, see
+    /// [`Buffer::scale_all`].
+    scale: bool,
+    area: HitArea,
+}
+
+#[derive(Debug, Clone)]
+enum HitArea {
+    /// The bounds of a path on the canvas, grown by a margin in pixels for
+    /// the width of its stroke.
+    Region(Rectangle, f32),
+    /// The position of a text on the canvas and its region on the screen,
+    /// relative to the position.
+    Text(Point, Rectangle),
+}
+
+impl Hit {
+    /// Returns whether `point`, relative to the top-left corner of the pane,
+    /// lies within the item when drawn with the `view` around `center`.
+    fn contains(&self, view: &View, center: Point, point: Point) -> bool {
+        match self.area {
+            HitArea::Region(region, margin) => {
+                let (coarse, transform) = view_transform(view, center, self.anchor, self.scale);
+
+                let corners = [
+                    region.position(),
+                    Point::new(region.x + region.width, region.y),
+                    Point::new(region.x, region.y + region.height),
+                    region.position() + Vector::from(region.size()),
+                ]
+                .map(|corner| {
+                    let corner = corner - coarse;
+                    let corner =
+                        transform.transform_point(path::lyon_path::math::point(corner.x, corner.y));
+
+                    Point::new(corner.x, corner.y)
+                });
+
+                crate::geometry::bounds(&corners)
+                    .is_some_and(|bounds| bounds.expand(margin).contains(point))
+            }
+            HitArea::Text(position, region) => {
+                let position = translate_point(view, center, position, self.anchor);
+
+                Rectangle::new(position + Vector::new(region.x, region.y), region.size())
+                    .contains(point)
+            }
+        }
+    }
+}
+
+/// Draws `items` transformed by [`Buffer::transform_items`] on the `frame`.
+fn draw_items<Renderer: geometry::Renderer>(
+    frame: &mut Frame<Renderer>,
+    context: &RenderContext,
+    items: &[Item<'_>],
+) {
+    for item in items {
+        match item {
+            Item::Fill(path, fill, _) => fill_path(frame, context, path, *fill),
+            Item::Stroke(path, stroke, _) => frame.stroke(path, *stroke),
+            Item::Text(text, _) => frame.fill_text(text.clone()),
+            Item::RotatedText(text, angle, _) => frame.with_save(|frame| {
+                frame.translate(Vector::new(text.position.x, text.position.y));
+                frame.rotate(*angle);
+                frame.fill_text(Text {
+                    position: Point::ORIGIN,
+                    ..text.clone()
+                });
+            }),
+            Item::Lines(lines, _) => lines.iter().for_each(|line| frame.fill_text(line.clone())),
+            Item::Badge(path, fill, text, _) => {
+                fill_path(frame, context, path, *fill);
+                frame.fill_text(text.clone());
+            }
+            Item::MarchingAnts(..)
+            | Item::TextBox(..)
+            | Item::RichText(..)
+            | Item::Label(..)
+            | Item::Pattern(..)
+            | Item::ScreenRectangle(..) => {}
+        }
+    }
+}
+
+/// The identifier of a shape in a [`Scene`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ShapeId(u64);
+
+/// A shape of a [`Scene`] and its items as last transformed.
+#[derive(Debug)]
+struct Shape {
+    buffer: Buffer<'static>,
+    /// The view and center the items were transformed for.
+    cache: RefCell<Option<(View, Point, Vec<Item<'static>>)>>,
+}
+
+impl Shape {
+    fn new(buffer: Buffer<'static>) -> Self {
+        Self {
+            buffer,
+            cache: RefCell::new(None),
+        }
+    }
+
+    /// Draws the shape, transforming its items only if the view changed
+    /// since the last draw.
+    fn draw<Renderer: geometry::Renderer>(
+        &self,
+        frame: &mut Frame<Renderer>,
+        context: &RenderContext,
+    ) {
+        if self.buffer.is_animated() {
+            return self.buffer.draw(frame, context);
+        }
+
+        let mut cache = self.cache.borrow_mut();
+
+        let stale = cache
+            .as_ref()
+            .is_none_or(|(view, center, _)| *view != context.view || *center != context.center);
+
+        if stale {
+            let items = self.buffer.transform_items(context);
+            *cache = Some((context.view, context.center, items));
+        }
+
+        if let Some((_, _, items)) = cache.as_ref() {
+            draw_items(frame, context, items);
+        }
+    }
+}
+
+/// A retained set of shapes drawn on an [`Infinite`], see [`Program::scene`].
+///
+/// Each shape is recorded once as a [`Buffer`] and kept until it is updated
+/// or removed through its [`ShapeId`]. Unlike the [`Buffer`]s returned by
+/// [`Program::draw`], shapes are not recorded anew every frame and are only
+/// transformed again when they change or the view of the [`Infinite`] moves.
+///
+/// Shapes are drawn along with the [`Buffer`]s of [`Program::draw`] by their
+/// [`Buffer::z_index`], before any buffers with the same z-index. Shapes with
+/// the same z-index are drawn in the order they were inserted.
+#[derive(Debug, Default)]
+pub struct Scene {
+    shapes: BTreeMap<ShapeId, Shape>,
+    next_id: u64,
+}
+
+impl Scene {
+    /// Creates an empty [`Scene`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a shape made of the items of `buffer`, returning its
+    /// [`ShapeId`].
+    pub fn insert(&mut self, buffer: Buffer<'static>) -> ShapeId {
+        let id = ShapeId(self.next_id);
+        self.next_id += 1;
+
+        self.shapes.insert(id, Shape::new(buffer));
+
+        id
+    }
+
+    /// Replaces the items of the shape with the given [`ShapeId`] with those
+    /// of `buffer`, keeping its place in the drawing order.
+    ///
+    /// Returns the previous [`Buffer`] of the shape, or `None` if there is no
+    /// such shape, in which case nothing is inserted.
+    pub fn update(&mut self, id: ShapeId, buffer: Buffer<'static>) -> Option<Buffer<'static>> {
+        let shape = self.shapes.get_mut(&id)?;
+
+        Some(std::mem::replace(shape, Shape::new(buffer)).buffer)
+    }
+
+    /// Removes the shape with the given [`ShapeId`], returning its
+    /// [`Buffer`].
+    pub fn remove(&mut self, id: ShapeId) -> Option<Buffer<'static>> {
+        self.shapes.remove(&id).map(|shape| shape.buffer)
+    }
+
+    /// Returns the [`Buffer`] of the shape with the given [`ShapeId`].
+    pub fn get(&self, id: ShapeId) -> Option<&Buffer<'static>> {
+        self.shapes.get(&id).map(|shape| &shape.buffer)
+    }
+
+    /// Returns true if the [`Scene`] has a shape with the given [`ShapeId`].
+    pub fn contains(&self, id: ShapeId) -> bool {
+        self.shapes.contains_key(&id)
+    }
+
+    /// Returns the [`ShapeId`]s of the shapes in the order they were
+    /// inserted.
+    pub fn ids(&self) -> impl Iterator<Item = ShapeId> + '_ {
+        self.shapes.keys().copied()
+    }
+
+    /// Returns the number of shapes in the [`Scene`].
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    /// Returns true if the [`Scene`] has no shapes.
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    /// Removes all shapes from the [`Scene`].
+    pub fn clear(&mut self) {
+        self.shapes.clear();
+    }
+
+    /// Returns the region covered by the shapes, given by its bottom-left
+    /// corner, see [`Buffer::bounds`].
+    pub fn bounds(&self) -> Option<Rectangle> {
+        crate::geometry::union(
+            self.shapes
+                .values()
+                .filter_map(|shape| shape.buffer.bounds()),
+        )
+    }
+}
+
+/// A [`Buffer`] of [`Program::draw`] or a shape of a [`Scene`], to be drawn
+/// in the order of their z-index.
+enum Layer<'b> {
+    Buffer(&'b Buffer<'static>),
+    Shape(&'b Shape),
+}
+
+impl Layer<'_> {
+    fn buffer(&self) -> &Buffer<'static> {
+        match self {
+            Layer::Buffer(buffer) => buffer,
+            Layer::Shape(shape) => &shape.buffer,
+        }
+    }
+
+    fn draw<Renderer: geometry::Renderer>(
+        &self,
+        frame: &mut Frame<Renderer>,
+        context: &RenderContext,
+    ) {
+        match self {
+            Layer::Buffer(buffer) => buffer.draw(frame, context),
+            Layer::Shape(shape) => shape.draw(frame, context),
+        }
+    }
+}
+
+/// A handle to the geometry of a [`Buffer`] which the [`Infinite`] keeps
+/// between frames, see [`Buffer::cache`].
+///
+/// Clones of a [`BufferCache`] refer to the same geometry.
+#[derive(Debug, Clone)]
+pub struct BufferCache {
+    id: u64,
+    generation: Arc<AtomicU64>,
+}
+
+impl Default for BufferCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BufferCache {
+    /// Creates a new [`BufferCache`].
+    pub fn new() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+        Self {
+            id: NEXT_ID.fetch_add(1, atomic::Ordering::Relaxed),
+            generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Clears the [`BufferCache`], so the geometry of the [`Buffer`] using it
+    /// is made anew from its items on the next draw.
+    ///
+    /// Call this whenever the items of the [`Buffer`] change.
+    pub fn clear(&self) {
+        self.generation.fetch_add(1, atomic::Ordering::Relaxed);
+    }
+
+    fn generation(&self) -> u64 {
+        self.generation.load(atomic::Ordering::Relaxed)
+    }
+}
+
+/// The geometry of a cached [`Buffer`] and what it was made for.
+struct CachedGeometry<T> {
+    generation: u64,
+    size: Size,
+    antialiasing: Antialiasing,
+    view: View,
+    group: cache::Group,
+    geometry: T,
+}
+
+impl<T> CachedGeometry<T> {
+    /// Returns whether the geometry can be drawn for the `generation` of its
+    /// [`BufferCache`] in the view of the `context`, only moved on the screen.
+    fn fits(&self, generation: u64, context: &RenderContext, size: Size) -> bool {
+        let view = context.view;
+
+        let fits = self.generation == generation
+            && self.size == size
+            && self.antialiasing == context.antialiasing
+            && self.view.scale == view.scale
+            && self.view.rotation == view.rotation
+            && self.view.y_axis == view.y_axis;
+
+        // Cached geometry spans a pane in every direction around the view
+        let offset = self.view.offset - view.rebased(self.view.origin).offset;
+
+        fits && offset.x.abs() <= self.size.width && offset.y.abs() <= self.size.height
+    }
+
+    /// Returns the transformation which places the geometry in the view of
+    /// the `context`, or `None` if it cannot be placed there.
+    ///
+    /// Geometry made for another zoom is scaled to it, along with the width
+    /// of its strokes. It cannot be rotated or scaled along a single axis.
+    fn transformation(&self, context: &RenderContext) -> Option<Transformation> {
+        let view = context.view;
+        let scale = view.scale.x / self.view.scale.x;
+
+        let placeable = self.view.rotation == view.rotation
+            && self.view.y_axis == view.y_axis
+            && scale.is_finite()
+            && (view.scale.y / self.view.scale.y - scale).abs() <= scale * PRESET_EPSILON;
+
+        if !placeable {
+            return None;
+        }
+
+        // The screen positions of a point near the origin of the geometry
+        let point = Point::new(self.view.origin.x as f32, self.view.origin.y as f32);
+        let center = Point::new(self.size.width / 2.0, self.size.height / 2.0);
+        let before = translate_point(&self.view, center, point, Anchor::None);
+        let after = translate_point(&view, context.center, point, Anchor::None);
+        let translation = (after - Point::ORIGIN) - (before - Point::ORIGIN) * scale;
+
+        // The geometry spans a pane in every direction around its view
+        Some(
+            Transformation::translate(translation.x, translation.y)
+                * Transformation::scale(scale)
+                * Transformation::translate(-self.size.width, -self.size.height),
+        )
+    }
+}
+
+/// The geometry of a cached [`Buffer`] being tessellated on a worker thread
+/// and what it is made for, see [`Infinite::background_tessellation`].
+struct Tessellation<T> {
+    generation: u64,
+    size: Size,
+    antialiasing: Antialiasing,
+    view: View,
+    worker: JoinHandle<T>,
+}
+
+/// What the last frame of an [`Infinite`] was drawn for, to tell whether
+/// the geometry tessellated on worker threads may be out of date.
+#[derive(Debug, Clone)]
+struct DrawnFrame {
+    bounds: Rectangle,
+    /// The views of the first and second panes.
+    views: (View, View),
+    /// The caches of the buffers tessellated on worker threads, with the
+    /// generation their geometry was made for.
+    caches: Vec<(BufferCache, u64)>,
+}
+
+/// The geometry of the items of a pane as last drawn, reused while neither
+/// the view nor the version of the content changes, see
+/// [`Program::content_version`].
+struct ContentGeometry<T> {
+    version: u64,
+    size: Size,
+    antialiasing: Antialiasing,
+    view: View,
+    /// Whether the geometry drawn after it goes in its own layer.
+    layered: bool,
+    pieces: Vec<Piece<T>>,
+}
+
+impl<T> ContentGeometry<T> {
+    /// Returns whether the geometry can be drawn again for the `version` of
+    /// the content.
+    fn fits(&self, version: u64, context: &RenderContext, size: Size) -> bool {
+        self.version == version
+            && self.size == size
+            && self.antialiasing == context.antialiasing
+            && self.view == context.view
+    }
+}
+
+/// A piece of cached geometry, with the transformation and clip it is drawn
+/// with.
+type Piece<T> = (T, Transformation, Option<Rectangle>);
+
+/// The cached geometry of each pane of an [`Infinite`].
+#[derive(Default)]
+struct GeometryCaches {
+    /// The geometry of each cached [`Buffer`] of each pane.
+    buffers: RefCell<HashMap<(u64, Pane), Box<dyn Any>>>,
+    /// The geometry of the content of each pane, see
+    /// [`Program::content_version`].
+    content: RefCell<HashMap<Pane, Box<dyn Any>>>,
+    /// The geometry of cached [`Buffer`]s being tessellated on worker
+    /// threads, by the same keys as `buffers`.
+    pending: RefCell<HashMap<(u64, Pane), Box<dyn Any>>>,
+    /// What the last frame was drawn for, if every [`Buffer`] tessellated on
+    /// a worker thread was drawn with geometry made for it.
+    settled: RefCell<Option<DrawnFrame>>,
+}
+
+impl GeometryCaches {
+    /// Returns whether the geometry tessellated on worker threads is up to
+    /// date for a frame drawn in `bounds` with the `views` of the panes.
+    fn is_settled(&self, bounds: Rectangle, views: (View, View)) -> bool {
+        self.settled.borrow().as_ref().is_some_and(|frame| {
+            frame.bounds == bounds
+                && frame.views == views
+                && frame
+                    .caches
+                    .iter()
+                    .all(|(cache, generation)| cache.generation() == *generation)
+        })
+    }
+}
+
+impl Clone for GeometryCaches {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl std::fmt::Debug for GeometryCaches {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GeometryCaches")
+            .field("buffers", &self.buffers.borrow().len())
+            .field("content", &self.content.borrow().len())
+            .field("pending", &self.pending.borrow().len())
+            .field("settled", &self.settled.borrow())
+            .finish()
+    }
+}
+
+/// Draws `geometry` with the given `transformation`, in a layer clipped to
+/// `clip` if there is one.
+fn draw_geometry<Renderer: geometry::Renderer>(
+    renderer: &mut Renderer,
+    geometry: Renderer::Geometry,
+    transformation: Transformation,
+    clip: Option<Rectangle>,
+) {
+    let draw = |renderer: &mut Renderer| {
+        renderer.with_transformation(transformation, |renderer| renderer.draw_geometry(geometry))
+    };
+
+    match clip {
+        Some(clip) => renderer.with_layer(clip, draw),
+        None => draw(renderer),
+    }
+}
+
+/// Draws `geometry` like [`draw_geometry`], keeping it as a [`Piece`] in
+/// `pieces` if there are any.
+fn draw_piece<Renderer: geometry::Renderer>(
+    renderer: &mut Renderer,
+    geometry: Renderer::Geometry,
+    transformation: Transformation,
+    clip: Option<Rectangle>,
+    pieces: Option<&mut Vec<Piece<<Renderer::Geometry as Cached>::Cache>>>,
+) {
+    let Some(pieces) = pieces else {
+        return draw_geometry(renderer, geometry, transformation, clip);
+    };
+
+    let cache = geometry.cache(cache::Group::unique(), None);
+
+    draw_geometry(
+        renderer,
+        Renderer::Geometry::load(&cache),
+        transformation,
+        clip,
+    );
+    pieces.push((cache, transformation, clip));
+}
+
+/// Fills a `path` on the `frame`, feathering its edge if the `context` asks
+/// for it and the `fill` is an opaque color.
+fn fill_path<Renderer: geometry::Renderer>(
+    frame: &mut Frame<Renderer>,
+    context: &RenderContext,
+    path: &Path,
+    fill: Fill,
+) {
+    frame.fill(path, fill);
+
+    match (context.antialiasing, fill.style) {
+        (Antialiasing::Feathered, fill::Style::Solid(color)) if color.a >= 1.0 => {
+            let feather = Stroke::default()
+                .with_width(FEATHER_WIDTH)
+                .with_color(color.scale_alpha(0.5));
+
+            frame.stroke(path, feather);
+        }
+        _ => {}
+    }
+}
+
+/// Determines which directions the canvas can be scrolled
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ScrollDirection {
+    /// Scroll in only X direction
+    X,
+    /// Scroll in only the Y direction
+    Y,
+    #[default]
+    /// Scroll in both x and y directions
+    Both,
+    /// No scroll in any direction. Scroll events are thus ignored.
+    None,
+}
+
+/// The mouse button and keyboard modifiers which scroll an [`Infinite`] by
+/// dragging it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PanTrigger {
+    button: mouse::Button,
+    modifiers: Option<keyboard::Modifiers>,
+}
+
+impl PanTrigger {
+    fn is_triggered(&self, button: mouse::Button, modifiers: keyboard::Modifiers) -> bool {
+        button == self.button
+            && self
+                .modifiers
+                .is_none_or(|required| modifiers.contains(required))
+    }
+}
+
+/// Determines how the edges of items on an [`Infinite`] are smoothed.
+///
+/// Iced enables antialiasing for a whole application, so an [`Infinite`]
+/// cannot turn it on by itself.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Antialiasing {
+    /// Items are drawn as is, relying on the antialiasing of the application.
+    #[default]
+    Application,
+    /// The edges of fills with an opaque color are feathered with a
+    /// translucent outline.
+    ///
+    /// This approximates antialiasing for applications which do not enable
+    /// it, and should not be combined with it. The outline straddles the
+    /// edge, so feathered fills grow by half a pixel on every side. Strokes,
+    /// gradients and translucent fills are drawn as is, as an outline would
+    /// blend over them twice.
+    Feathered,
+}
+
+/// Determines when an [`Infinite`] processes its keyboard shortcuts.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum KeyboardScope {
+    /// Shortcuts are processed only while the [`Infinite`] is hovered on.
+    #[default]
+    Hovered,
+    /// Shortcuts are processed only while the [`Infinite`] is focused.
+    ///
+    /// An [`Infinite`] is focused by pressing on it or through focus
+    /// operations, like those of a [`Task`]. It loses focus when pressing
+    /// anywhere else, or pressing Tab or Escape while the [`Program`] ignores
+    /// them.
+    Focused,
+    /// Shortcuts are always processed while the [`Infinite`] is visible.
+    Always,
+}
+
+/// Determines whether scrolling the mouse wheel over an [`Infinite`] scrolls
+/// or zooms it.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum WheelBehavior {
+    /// The wheel scrolls, and zooms while Shift is held.
+    #[default]
+    Pan,
+    /// The wheel zooms, and scrolls while Shift is held, as in maps and CAD
+    /// tools.
+    Zoom,
+}
+
+impl WheelBehavior {
+    fn zooms(self, modifiers: keyboard::Modifiers) -> bool {
+        match self {
+            Self::Pan => modifiers.shift(),
+            Self::Zoom => !modifiers.shift(),
+        }
+    }
+}
+
+/// Determines the direction in which the Y axis of an [`Infinite`] points.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum YDirection {
+    /// The Y axis points up, as in cartesian coordinates.
+    #[default]
+    Up,
+    /// The Y axis points down, as in screen coordinates.
+    Down,
+}
+
+impl YDirection {
+    /// Returns the sign of a Y coordinate of the [`Infinite`] on the screen.
+    fn sign(self) -> f32 {
+        match self {
+            Self::Up => -1.0,
+            Self::Down => 1.0,
+        }
+    }
+}
+
+/// Determines which axes of an [`Infinite`] are scaled by a zoom.
+///
+/// The zoom reported to the [`Program`] is that of the X axis, unless only
+/// the Y axis is zoomed.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ZoomAxes {
+    /// Both axes are zoomed together.
+    #[default]
+    Both,
+    /// Only the X axis is zoomed, stretching the canvas horizontally.
+    X,
+    /// Only the Y axis is zoomed, stretching the canvas vertically.
+    Y,
+}
+
+impl ZoomAxes {
+    /// Returns the scale `levels` with the zoomed axes moved by `diff`.
+    fn add(self, levels: Vector, diff: f32) -> Vector {
+        match self {
+            Self::Both => levels + Vector::new(diff, diff),
+            Self::X => Vector::new(levels.x + diff, levels.y),
+            Self::Y => Vector::new(levels.x, levels.y + diff),
+        }
+    }
+
+    /// Returns the scale `levels` with the zoomed axes set to `level`.
+    fn with(self, levels: Vector, level: f32) -> Vector {
+        match self {
+            Self::Both => Vector::new(level, level),
+            Self::X => Vector::new(level, levels.y),
+            Self::Y => Vector::new(levels.x, level),
+        }
+    }
+
+    /// Returns the single zoom value of per-axis `values`.
+    fn get(self, values: Vector) -> f32 {
+        match self {
+            Self::Both | Self::X => values.x,
+            Self::Y => values.y,
+        }
+    }
+}
+
+/// A key pressed together with an exact set of modifiers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyBinding {
+    /// The key of the binding.
+    pub key: keyboard::Key,
+    /// The modifiers which must be held, and no others.
+    pub modifiers: keyboard::Modifiers,
+}
+
+impl KeyBinding {
+    /// Creates a new [`KeyBinding`].
+    pub fn new(key: impl Into<keyboard::Key>, modifiers: keyboard::Modifiers) -> Self {
+        Self {
+            key: key.into(),
+            modifiers,
+        }
+    }
+
+    /// Creates a new [`KeyBinding`] from a named key.
+    pub fn named(key: keyboard::key::Named, modifiers: keyboard::Modifiers) -> Self {
+        Self::new(keyboard::Key::Named(key), modifiers)
+    }
+
+    /// Creates a new [`KeyBinding`] from a character.
+    pub fn character(character: &str, modifiers: keyboard::Modifiers) -> Self {
+        Self::new(keyboard::Key::Character(character.into()), modifiers)
+    }
+
+    fn matches(&self, key: &keyboard::Key, modifiers: keyboard::Modifiers) -> bool {
+        if self.modifiers != modifiers {
+            return false;
+        }
+
+        match (&self.key, key) {
+            (keyboard::Key::Character(a), keyboard::Key::Character(b)) => a.eq_ignore_ascii_case(b),
+            (a, b) => a == b,
+        }
+    }
+}
+
+/// The keyboard shortcuts of an [`Infinite`].
+///
+/// A shortcut set to [`None`] is disabled. The default shortcuts are listed
+/// in the [module documentation](self).
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyBindings {
+    /// Scrolls up by a single scroll step.
+    pub pan_up: Option<KeyBinding>,
+    /// Scrolls down by a single scroll step.
+    pub pan_down: Option<KeyBinding>,
+    /// Scrolls left by a single scroll step.
+    pub pan_left: Option<KeyBinding>,
+    /// Scrolls right by a single scroll step.
+    pub pan_right: Option<KeyBinding>,
+    /// Scrolls up by half of the visible canvas.
+    pub page_up: Option<KeyBinding>,
+    /// Scrolls down by half of the visible canvas.
+    pub page_down: Option<KeyBinding>,
+    /// Scrolls left by half of the visible canvas.
+    pub page_left: Option<KeyBinding>,
+    /// Scrolls right by half of the visible canvas.
+    pub page_right: Option<KeyBinding>,
+    /// Zooms in around the cursor.
+    pub zoom_in: Option<KeyBinding>,
+    /// Zooms out around the cursor.
+    pub zoom_out: Option<KeyBinding>,
+    /// Zooms in around the origin.
+    pub zoom_in_origin: Option<KeyBinding>,
+    /// Zooms out around the origin.
+    pub zoom_out_origin: Option<KeyBinding>,
+    /// Zooms to the next zoom preset.
+    pub next_preset: Option<KeyBinding>,
+    /// Zooms to the previous zoom preset.
+    pub previous_preset: Option<KeyBinding>,
+    /// Resets the scroll.
+    pub reset_scroll: Option<KeyBinding>,
+    /// Resets the zoom.
+    pub reset_zoom: Option<KeyBinding>,
+    /// Resets both the scroll and the zoom.
+    pub reset_all: Option<KeyBinding>,
+}
+
+impl KeyBindings {
+    /// Returns [`KeyBindings`] with every shortcut disabled.
+    pub fn none() -> Self {
+        Self {
+            pan_up: None,
+            pan_down: None,
+            pan_left: None,
+            pan_right: None,
+            page_up: None,
+            page_down: None,
+            page_left: None,
+            page_right: None,
+            zoom_in: None,
+            zoom_out: None,
+            zoom_in_origin: None,
+            zoom_out_origin: None,
+            next_preset: None,
+            previous_preset: None,
+            reset_scroll: None,
+            reset_zoom: None,
+            reset_all: None,
+        }
+    }
+
+    /// Returns the action bound to `key` pressed with `modifiers`.
+    fn action(&self, key: &keyboard::Key, modifiers: keyboard::Modifiers) -> Option<KeyAction> {
+        [
+            (&self.pan_up, KeyAction::PanUp),
+            (&self.pan_down, KeyAction::PanDown),
+            (&self.pan_left, KeyAction::PanLeft),
+            (&self.pan_right, KeyAction::PanRight),
+            (&self.page_up, KeyAction::PageUp),
+            (&self.page_down, KeyAction::PageDown),
+            (&self.page_left, KeyAction::PageLeft),
+            (&self.page_right, KeyAction::PageRight),
+            (&self.zoom_in, KeyAction::ZoomIn),
+            (&self.zoom_out, KeyAction::ZoomOut),
+            (&self.zoom_in_origin, KeyAction::ZoomInOrigin),
+            (&self.zoom_out_origin, KeyAction::ZoomOutOrigin),
+            (&self.next_preset, KeyAction::NextPreset),
+            (&self.previous_preset, KeyAction::PreviousPreset),
+            (&self.reset_scroll, KeyAction::ResetScroll),
+            (&self.reset_zoom, KeyAction::ResetZoom),
+            (&self.reset_all, KeyAction::ResetAll),
+        ]
+        .into_iter()
+        .find_map(|(binding, action)| {
+            binding
+                .as_ref()
+                .is_some_and(|binding| binding.matches(key, modifiers))
+                .then_some(action)
+        })
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        use keyboard::key::Named;
+        use keyboard::Modifiers;
+
+        let command_shift = Modifiers::COMMAND | Modifiers::SHIFT;
+
+        Self {
+            pan_up: Some(KeyBinding::named(Named::ArrowUp, Modifiers::COMMAND)),
+            pan_down: Some(KeyBinding::named(Named::ArrowDown, Modifiers::COMMAND)),
+            pan_left: Some(KeyBinding::named(Named::ArrowLeft, Modifiers::COMMAND)),
+            pan_right: Some(KeyBinding::named(Named::ArrowRight, Modifiers::COMMAND)),
+            page_up: Some(KeyBinding::named(Named::PageUp, Modifiers::empty())),
+            page_down: Some(KeyBinding::named(Named::PageDown, Modifiers::empty())),
+            page_left: Some(KeyBinding::named(Named::PageUp, Modifiers::SHIFT)),
+            page_right: Some(KeyBinding::named(Named::PageDown, Modifiers::SHIFT)),
+            zoom_in: Some(KeyBinding::named(Named::ArrowUp, Modifiers::SHIFT)),
+            zoom_out: Some(KeyBinding::named(Named::ArrowDown, Modifiers::SHIFT)),
+            zoom_in_origin: Some(KeyBinding::named(Named::ArrowUp, command_shift)),
+            zoom_out_origin: Some(KeyBinding::named(Named::ArrowDown, command_shift)),
+            next_preset: Some(KeyBinding::character("0", Modifiers::COMMAND)),
+            previous_preset: Some(KeyBinding::character("0", command_shift)),
+            reset_scroll: Some(KeyBinding::named(Named::Home, Modifiers::empty())),
+            reset_zoom: Some(KeyBinding::named(Named::Home, Modifiers::SHIFT)),
+            reset_all: Some(KeyBinding::named(Named::Home, Modifiers::COMMAND)),
+        }
+    }
+}
+
+/// Determines how the view of an [`Infinite`] moves towards a new scroll or
+/// zoom.
+///
+/// While the view moves, the cursors given to the [`Program`] and the focal
+/// point of zooms follow the view as it is drawn, not the scroll and zoom it
+/// is moving towards.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum CameraMotion {
+    /// Scrolls and zooms are applied immediately.
+    #[default]
+    Immediate,
+    /// Scrolls and zooms settle into place like a damped spring, see
+    /// [`Easing::Spring`].
+    ///
+    /// A higher stiffness settles faster, in about `6.0 / stiffness` seconds.
+    /// This suits follow-cameras and settling after a drag.
+    Spring {
+        /// The stiffness of the spring.
+        stiffness: f32,
+    },
+    /// Scrolls and zooms are interpolated over a fixed duration.
+    Eased {
+        /// How long a single scroll or zoom takes.
+        duration: Duration,
+        /// The [`Easing`] of the interpolation.
+        easing: Easing,
+    },
+}
+
+impl CameraMotion {
+    /// A [`CameraMotion::Spring`] with a moderate stiffness.
+    pub const SPRING: Self = Self::Spring { stiffness: 12.0 };
+
+    /// A [`CameraMotion::Eased`] which is quick and decelerates smoothly.
+    pub const SMOOTH: Self = Self::Eased {
+        duration: Duration::from_millis(200),
+        easing: Easing::CubicOut,
+    };
+}
+
+/// Determines how an [`Infinite`] scrolls while the cursor nears its border
+/// during a drag captured by the [`Program`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdgePanConfig {
+    /// The distance, in pixels, from the border at which scrolling starts.
+    pub margin: f32,
+    /// The speed, in pixels per second, of scrolling with the cursor at or
+    /// past the border.
+    pub speed: f32,
+}
+
+impl Default for EdgePanConfig {
+    fn default() -> Self {
+        Self {
+            margin: 32.0,
+            speed: 600.0,
+        }
+    }
+}
+
+/// Determines how the grid of an [`Infinite`] is drawn, see
+/// [`Infinite::grid`].
+///
+/// The distance between grid lines is always 1, 2 or 5 times a power of ten
+/// units. As the [`Infinite`] is zoomed, lines are subdivided or merged to
+/// keep them between `min_spacing` and `max_spacing` pixels apart. Every
+/// fifth line is a major line.
+///
+/// A [`GridPattern::Hex`] grid has cells of a fixed size instead, and is
+/// hidden once its cells are less than `min_spacing` pixels across.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridConfig {
+    /// The shape of the cells of the grid.
+    pub pattern: GridPattern,
+    /// The smallest distance, in pixels, between two grid lines.
+    pub min_spacing: f32,
+    /// The largest distance, in pixels, between two grid lines.
+    ///
+    /// To always be kept, it should be at least 2.5 times `min_spacing`.
+    /// Otherwise, it takes precedence over `min_spacing`.
+    pub max_spacing: f32,
+}
+
+impl GridConfig {
+    /// Returns the distance, in units, between grid lines at the given zoom.
+    fn step(&self, scale: f32) -> f32 {
+        let step = nice_step(self.min_spacing / scale);
+
+        if step * scale > self.max_spacing {
+            nice_floor(self.max_spacing / scale)
+        } else {
+            step
+        }
+    }
+}
+
+impl Default for GridConfig {
+    fn default() -> Self {
+        Self {
+            pattern: GridPattern::default(),
+            min_spacing: 20.0,
+            max_spacing: 50.0,
+        }
+    }
+}
+
+/// The shape of the cells of a grid, see [`GridConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GridPattern {
+    /// Square cells, made of horizontal and vertical lines.
+    #[default]
+    Square,
+    /// Diamond cells twice as wide as they are tall, made of lines with a
+    /// slope of one half, as used by isometric tile maps.
+    Isometric,
+    /// Hexagonal cells of the given [`HexLayout`].
+    Hex(HexLayout),
+}
+
+/// The layout of a grid of pointy-topped hexagons centered on the origin.
+///
+/// Cells are addressed by axial co-ordinates `(q, r)`: `q` increases along
+/// the X axis and `r` along the diagonal rows of the grid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HexLayout {
+    /// The distance, in units, from the center of a cell to its corners.
+    pub size: f32,
+}
+
+impl HexLayout {
+    /// Creates a new [`HexLayout`] with cells of the given `size`.
+    pub fn new(size: f32) -> Self {
+        Self { size }
+    }
+
+    /// Returns the center of the cell at the axial co-ordinates `(q, r)`.
+    pub fn to_point(self, q: i32, r: i32) -> Point {
+        let (q, r) = (q as f32, r as f32);
+        let sqrt_3 = 3.0_f32.sqrt();
+
+        Point::new(self.size * sqrt_3 * (q + r / 2.0), self.size * 1.5 * r)
+    }
+
+    /// Returns the axial co-ordinates of the cell containing `point`.
+    pub fn to_axial(self, point: Point) -> (i32, i32) {
+        let q = (3.0_f32.sqrt() / 3.0 * point.x - point.y / 3.0) / self.size;
+        let r = (2.0 / 3.0 * point.y) / self.size;
+
+        // Round in cube co-ordinates, where `q + r + s == 0`, fixing up the
+        // component which moved the most
+        let s = -q - r;
+        let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+
+        if dq > dr && dq > ds {
+            rq = -rr - rs;
+        } else if dr > ds {
+            rr = -rq - rs;
+        }
+
+        (rq as i32, rr as i32)
+    }
+
+    /// Returns the corners of the cell at the axial co-ordinates `(q, r)`, in
+    /// order around the cell.
+    pub fn corners(self, q: i32, r: i32) -> [Point; 6] {
+        let center = self.to_point(q, r);
+
+        std::array::from_fn(|corner| {
+            let angle = std::f32::consts::FRAC_PI_3 * corner as f32 + std::f32::consts::FRAC_PI_6;
+
+            Point::new(
+                center.x + self.size * angle.cos(),
+                center.y + self.size * angle.sin(),
+            )
+        })
+    }
+
+    /// Returns the outline of the cell at the axial co-ordinates `(q, r)`.
+    pub fn cell(self, q: i32, r: i32) -> Path {
+        let corners = self.corners(q, r);
+
+        Path::new(|builder| {
+            builder.move_to(corners[0]);
+
+            for corner in &corners[1..] {
+                builder.line_to(*corner);
+            }
+
+            builder.close();
+        })
+    }
+}
+
+/// Determines how the axes of an [`Infinite`] are drawn, see
+/// [`Infinite::show_axes`].
+///
+/// Like the grid, the distance between ticks is always 1, 2 or 5 times a
+/// power of ten units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxesConfig {
+    /// The width, in pixels, of the axes.
+    pub width: f32,
+    /// The smallest distance, in pixels, between two ticks.
+    pub tick_spacing: f32,
+    /// Whether the ticks are labelled with their co-ordinate.
+    pub labels: bool,
+}
+
+impl Default for AxesConfig {
+    fn default() -> Self {
+        Self {
+            width: 1.5,
+            tick_spacing: 60.0,
+            labels: true,
+        }
+    }
+}
+
+/// Determines which details of its scroll and zoom an [`Infinite`] shows, and
+/// how.
+#[derive(Debug, Clone, Copy)]
+pub struct HudConfig {
+    /// The corner the zoom is shown in once zoomed, or `None` to hide it.
+    pub zoom: Option<Corner>,
+    /// The corner the scroll is shown in once scrolled, or `None` to hide it.
+    pub scroll: Option<Corner>,
+    /// The corner the position of the cursor on the canvas is shown in while
+    /// it hovers, or `None` to hide it.
+    ///
+    /// The position does not fade out.
+    pub cursor: Option<Corner>,
+    /// Formats the shown [`Readout`]s, or `None` for the default formatting.
+    pub format: Option<fn(Readout) -> String>,
+    /// How long the details stay after the last scroll or zoom before fading
+    /// out, or `None` to always show them.
+    pub fade_out: Option<Duration>,
+}
+
+impl HudConfig {
+    /// A [`HudConfig`] showing no details.
+    pub const HIDDEN: Self = Self {
+        zoom: None,
+        scroll: None,
+        cursor: None,
+        format: None,
+        fade_out: None,
+    };
+
+    /// Returns the opacity of the details at `now`, given the time of the
+    /// last scroll or zoom.
+    fn opacity(&self, last_change: Option<Instant>, now: Instant) -> f32 {
+        let Some(timeout) = self.fade_out else {
+            return 1.0;
+        };
+
+        let Some(at) = last_change else {
+            return 0.0;
+        };
+
+        let elapsed = now.saturating_duration_since(at);
+
+        if elapsed <= timeout {
+            1.0
+        } else {
+            let fade = (elapsed - timeout).as_secs_f32() / HUD_FADE.as_secs_f32();
+            (1.0 - fade).max(0.0)
+        }
+    }
+
+    /// Returns true if the details are fading out at `now`.
+    fn is_fading(&self, last_change: Option<Instant>, now: Instant) -> bool {
+        match (self.fade_out, last_change) {
+            (Some(timeout), Some(at)) => {
+                let elapsed = now.saturating_duration_since(at);
+                elapsed >= timeout && elapsed < timeout + HUD_FADE
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Default for HudConfig {
+    fn default() -> Self {
+        Self {
+            zoom: Some(Corner::BottomRight),
+            scroll: Some(Corner::BottomLeft),
+            cursor: None,
+            format: None,
+            fade_out: None,
+        }
+    }
+}
+
+/// A corner of an [`Infinite`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Corner {
+    /// The top-left corner.
+    TopLeft,
+    /// The top-right corner.
+    TopRight,
+    /// The bottom-left corner.
+    BottomLeft,
+    /// The bottom-right corner.
+    BottomRight,
+}
+
+/// A value shown in the details of an [`Infinite`], see [`HudConfig`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Readout {
+    /// The zoom factors of the X and Y axes.
+    Zoom(Vector),
+    /// The scroll, with the Y axis pointing like that of the [`Infinite`].
+    Scroll(Vector),
+    /// The position of the cursor on the canvas.
+    Cursor(Point),
+}
+
+/// Determines how an [`Infinite`] is split into two panes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Split {
+    /// The panes are placed side by side with a vertical divider between them.
+    Horizontal,
+    /// The panes are stacked on top of each other with a horizontal divider
+    /// between them.
+    Vertical,
+}
+
+/// A snapshot of the scroll and zoom of an [`Infinite`].
+///
+/// With the `serde` feature, a [`Viewport`] can be saved and later applied
+/// with [`restore`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "ViewportRepr", into = "ViewportRepr")
+)]
+pub struct Viewport {
+    /// The scroll of the [`Infinite`], like that of [`Program::init_scroll`].
+    pub offset: Vector,
+    /// The zoom factors of the X and Y axes of the [`Infinite`], where 1.0 is
+    /// the unzoomed canvas. See [`ZoomAxes`].
+    pub scale: Vector,
+    /// The counter-clockwise rotation of the [`Infinite`].
+    pub rotation: Radians,
+    /// The direction of the Y axis of the [`Infinite`].
+    pub y_axis: YDirection,
+    /// The bounds of the [`Infinite`] on the screen.
+    pub bounds: Rectangle,
+}
+
+impl Viewport {
+    /// Returns the region of the [`Infinite`] currently visible, given by its
+    /// bottom-left corner.
+    ///
+    /// The region of a rotated [`Infinite`] is the smallest one containing
+    /// all of its view.
+    pub fn visible(&self) -> Rectangle {
+        let bounds = self.bounds;
+
+        let corners = [
+            bounds.position(),
+            Point::new(bounds.x + bounds.width, bounds.y),
+            Point::new(bounds.x, bounds.y + bounds.height),
+            bounds.position() + Vector::from(bounds.size()),
+        ]
+        .map(|corner| self.to_world(corner));
+
+        crate::geometry::bounds(&corners).unwrap_or_default()
+    }
+
+    /// Returns the [`Chunk`]s of the given `size` which are at least partly
+    /// visible, ordered by column and then row.
+    ///
+    /// When more than 4096 chunks are visible, as when zoomed out far, no
+    /// chunks are returned.
+    pub fn visible_chunks(&self, size: f32) -> Vec<Chunk> {
+        let visible = self.visible();
+        let range = |start: f32, length: f32| {
+            let first = (start / size).floor();
+            let last = ((start + length) / size).ceil() - 1.0;
+
+            (first as i32, last.max(first) as i32)
+        };
+
+        let (left, right) = range(visible.x, visible.width);
+        let (bottom, top) = range(visible.y, visible.height);
+
+        let count =
+            (i64::from(right) - i64::from(left) + 1) * (i64::from(top) - i64::from(bottom) + 1);
+
+        if size.is_nan() || size <= 0.0 || count > MAX_CHUNKS {
+            return Vec::new();
+        }
+
+        (left..=right)
+            .flat_map(|x| (bottom..=top).map(move |y| Chunk { x, y }))
+            .collect()
+    }
+
+    /// Converts a point on the screen to its position on the [`Infinite`].
+    pub fn to_world(&self, point: Point) -> Point {
+        let point = rotate(point - self.bounds.center() + self.offset, -self.rotation.0);
+
+        let sign = self.y_axis.sign();
+
+        Point::new(point.x / self.scale.x, sign * point.y / self.scale.y)
+    }
+
+    /// Converts a point on the [`Infinite`] to its position on the screen.
+    pub fn to_screen(&self, point: Point) -> Point {
+        let center = self.bounds.center() - self.offset;
+        let sign = self.y_axis.sign();
+        let point = Vector::new(point.x * self.scale.x, sign * point.y * self.scale.y);
+
+        center + rotate(point, self.rotation.0)
+    }
+}
+
+/// The serialized form of a [`Viewport`], as iced types are not serializable.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ViewportRepr {
+    offset: [f32; 2],
+    scale: [f32; 2],
+    /// The rotation in radians.
+    rotation: f32,
+    y_axis: YDirection,
+    /// The position and size of the bounds, as `[x, y, width, height]`.
+    bounds: [f32; 4],
+}
+
+#[cfg(feature = "serde")]
+impl From<ViewportRepr> for Viewport {
+    fn from(repr: ViewportRepr) -> Self {
+        let [x, y, width, height] = repr.bounds;
+
+        Self {
+            offset: Vector::new(repr.offset[0], repr.offset[1]),
+            scale: Vector::new(repr.scale[0], repr.scale[1]),
+            rotation: Radians(repr.rotation),
+            y_axis: repr.y_axis,
+            bounds: Rectangle::new(Point::new(x, y), Size::new(width, height)),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Viewport> for ViewportRepr {
+    fn from(viewport: Viewport) -> Self {
+        let bounds = viewport.bounds;
+
+        Self {
+            offset: [viewport.offset.x, viewport.offset.y],
+            scale: [viewport.scale.x, viewport.scale.y],
+            rotation: viewport.rotation.0,
+            y_axis: viewport.y_axis,
+            bounds: [bounds.x, bounds.y, bounds.width, bounds.height],
+        }
+    }
+}
+
+/// Where an overlay of a [`Program`] is placed, see [`Program::overlay`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OverlayAnchor {
+    /// A point on the [`Infinite`], which the overlay follows as the canvas
+    /// is scrolled and zoomed.
+    World(Point),
+    /// A point of the pane, in pixels from its top-left corner.
+    Pane(Point),
+}
+
+impl OverlayAnchor {
+    /// Returns the position, in the layout of an overlay, of the anchor with
+    /// the `viewport` and `translation` given to [`Program::overlay`].
+    pub fn position(self, viewport: &Viewport, translation: Vector) -> Point {
+        let position = match self {
+            Self::World(point) => viewport.to_screen(point),
+            Self::Pane(point) => point + Vector::new(viewport.bounds.x, viewport.bounds.y),
+        };
+
+        position + translation
+    }
+}
+
+/// An entry of the context menu of an [`Infinite`], see
+/// [`Program::context_menu`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MenuEntry<Message> {
+    /// The text of the entry.
+    pub label: String,
+    /// The message published once the entry is chosen.
+    pub message: Message,
+}
+
+impl<Message> MenuEntry<Message> {
+    /// Creates a new [`MenuEntry`] with the given `label`, which publishes
+    /// `message` once chosen.
+    pub fn new(label: impl Into<String>, message: Message) -> Self {
+        Self {
+            label: label.into(),
+            message,
+        }
+    }
+}
+
+/// A tooltip of an [`Infinite`], see [`Program::tooltip`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tooltip {
+    /// The text of the tooltip.
+    pub content: String,
+    /// The point on the [`Infinite`] the tooltip is shown above, or [`None`]
+    /// for it to follow the cursor.
+    pub anchor: Option<Point>,
+}
+
+impl Tooltip {
+    /// Creates a new [`Tooltip`] with the given `content`, which follows the
+    /// cursor.
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            anchor: None,
+        }
+    }
+
+    /// Sets the point on the [`Infinite`] the [`Tooltip`] is shown above,
+    /// keeping it there as the canvas is scrolled and zoomed.
+    pub fn anchor(mut self, point: Point) -> Self {
+        self.anchor = Some(point);
+        self
+    }
+}
+
+#[cfg(feature = "tiles")]
+pub use tile_renderer::TileRenderer;
+#[cfg(not(feature = "tiles"))]
+use tile_renderer::TileRenderer;
+
+mod tile_renderer {
+    use iced::advanced;
+    use iced::{Radians, Rectangle};
+
+    /// A renderer which can draw the images of map tiles, see the `tiles`
+    /// module.
+    ///
+    /// Without the `tiles` feature, the trait is private and every renderer
+    /// implements it by drawing nothing.
+    pub trait TileRenderer {
+        /// Draws the image of a tile within `bounds` on the screen, rotated
+        /// clockwise by `rotation` around its center.
+        fn draw_tile(
+            &mut self,
+            handle: &advanced::image::Handle,
+            bounds: Rectangle,
+            rotation: Radians,
+            opacity: f32,
+        );
+    }
+
+    #[cfg(feature = "tiles")]
+    impl<R> TileRenderer for R
+    where
+        R: advanced::image::Renderer<Handle = advanced::image::Handle>,
+    {
+        fn draw_tile(
+            &mut self,
+            handle: &advanced::image::Handle,
+            bounds: Rectangle,
+            rotation: Radians,
+            opacity: f32,
+        ) {
+            let image = advanced::image::Image::new(handle.clone())
+                .rotation(rotation)
+                .opacity(opacity);
+
+            self.draw_image(image, bounds);
+        }
+    }
+
+    #[cfg(not(feature = "tiles"))]
+    impl<R> TileRenderer for R {
+        fn draw_tile(&mut self, _: &advanced::image::Handle, _: Rectangle, _: Radians, _: f32) {}
+    }
+}
+
+/// A widget capable of drawing 2D graphics on an infinite Cartesian plane.
+pub struct Infinite<'a, P, Message, Theme = iced::Theme, Renderer = iced::Renderer>
+where
+    Theme: Catalog,
+    P: Program<Message, Theme, Renderer>,
+    Renderer: geometry::Renderer,
+{
+    width: Length,
+    height: Length,
+    direction: ScrollDirection,
+    allow_scale: bool,
+    allow_rotation: bool,
+    zoom_axes: ZoomAxes,
+    y_axis: YDirection,
+    rebase_origin: bool,
+    scale_step: Option<f32>,
+    wheel_step: Option<Vector>,
+    keyboard_step: Option<Vector>,
+    split: Option<Split>,
+    split_ratio: f32,
+    link_panes: bool,
+    camera_motion: CameraMotion,
+    keyboard_scope: KeyboardScope,
+    zoom_presets: Vec<f32>,
+    scroll_chaining: bool,
+    scroll_multiplier: f32,
+    invert_scroll: ScrollDirection,
+    wheel_behavior: WheelBehavior,
+    double_click_zoom: bool,
+    tooltip_delay: Duration,
+    antialiasing: Antialiasing,
+    frame_budget: Option<Duration>,
+    background_tessellation: Option<usize>,
+    pan_trigger: Option<PanTrigger>,
+    zoom_box: bool,
+    grid: Option<GridConfig>,
+    axes: Option<AxesConfig>,
+    pixel_grid: Option<f32>,
+    chunk_size: Option<f32>,
+    #[cfg(feature = "tiles")]
+    tiles: Option<&'a crate::tiles::TileLayer>,
+    rulers: bool,
+    scale_bar: bool,
+    debug_overlay: bool,
+    hud: HudConfig,
+    scroll_bounds: Option<Rectangle>,
+    elastic_overscroll: bool,
+    auto_pan: Option<EdgePanConfig>,
+    key_bindings: KeyBindings,
+    id: Option<Id>,
+    _message: PhantomData<Message>,
+    _renderer: PhantomData<Renderer>,
+    program: P,
+    style: <Theme as Catalog>::Class<'a>,
+}
+
+impl<'a, P, Message, Theme, Renderer> Infinite<'a, P, Message, Theme, Renderer>
+where
+    Theme: Catalog,
+    P: Program<Message, Theme, Renderer>,
+    Renderer: geometry::Renderer,
+{
+    const DEFAULT_SIZE: f32 = 300.0;
+
+    /// Creates a new [`Infinite`].
+    pub fn new(program: P) -> Self {
+        Self {
+            width: Length::Fixed(Self::DEFAULT_SIZE),
+            height: Length::Fixed(Self::DEFAULT_SIZE),
+            direction: ScrollDirection::default(),
+            allow_scale: true,
+            allow_rotation: false,
+            zoom_axes: ZoomAxes::default(),
+            y_axis: YDirection::default(),
+            rebase_origin: false,
+            scale_step: None,
+            wheel_step: None,
+            keyboard_step: None,
+            split: None,
+            split_ratio: 0.5,
+            link_panes: false,
+            camera_motion: CameraMotion::default(),
+            keyboard_scope: KeyboardScope::default(),
+            zoom_presets: ZOOM_PRESETS.to_vec(),
+            scroll_chaining: false,
+            scroll_multiplier: 1.0,
+            invert_scroll: ScrollDirection::None,
+            wheel_behavior: WheelBehavior::default(),
+            double_click_zoom: false,
+            tooltip_delay: TOOLTIP_DELAY,
+            antialiasing: Antialiasing::default(),
+            frame_budget: None,
+            background_tessellation: None,
+            pan_trigger: None,
+            zoom_box: false,
+            grid: None,
+            axes: None,
+            pixel_grid: None,
+            chunk_size: None,
+            #[cfg(feature = "tiles")]
+            tiles: None,
+            rulers: false,
+            scale_bar: false,
+            debug_overlay: false,
+            hud: HudConfig::default(),
+            scroll_bounds: None,
+            elastic_overscroll: false,
+            auto_pan: None,
+            key_bindings: KeyBindings::default(),
+            id: None,
+            program,
+            _message: PhantomData,
+            _renderer: PhantomData,
+            style: Theme::default(),
+        }
+    }
+
+    /// Sets the height of the [`Infinite`].
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    /// Sets the width of the [`Infinite`].
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Sets the supported scroll direction of the [`Infinite`].
+    pub fn scroll_direction(mut self, direction: ScrollDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Sets whether the [`Infinite`] can be scrolled by dragging it with the
+    /// left mouse button.
+    ///
+    /// Only drags the [`Program`] does not capture scroll the [`Infinite`],
+    /// so dragging an empty part of the canvas scrolls it.
+    ///
+    /// This is a shorthand for [`Infinite::pan_trigger`] with the left mouse
+    /// button and no modifiers.
+    pub fn drag_pan(mut self, allow: bool) -> Self {
+        self.pan_trigger = allow.then_some(PanTrigger {
+            button: mouse::Button::Left,
+            modifiers: None,
+        });
+        self
+    }
+
+    /// Sets the mouse button and optional keyboard modifiers which scroll the
+    /// [`Infinite`] by dragging it.
+    ///
+    /// Without modifiers, only drags the [`Program`] does not capture scroll
+    /// the [`Infinite`]. With modifiers, holding them while dragging always
+    /// scrolls the [`Infinite`], even over items the [`Program`] would
+    /// otherwise capture.
+    pub fn pan_trigger(
+        mut self,
+        button: mouse::Button,
+        modifiers: Option<keyboard::Modifiers>,
+    ) -> Self {
+        self.pan_trigger = Some(PanTrigger { button, modifiers });
+        self
+    }
+
+    /// Sets whether the [`Infinite`] can be zoomed in/out on.
+    pub fn zoom(mut self, allow: bool) -> Self {
+        self.allow_scale = allow;
+        self
+    }
+
+    /// Sets the direction in which the Y axis of the [`Infinite`] points.
+    ///
+    /// With [`YDirection::Down`], items are drawn with respect to their
+    /// top-left point, like on the regular Iced canvas.
+    pub fn y_axis(mut self, direction: YDirection) -> Self {
+        self.y_axis = direction;
+        self
+    }
+
+    /// Sets whether the [`Infinite`] moves the origin of its view along with
+    /// the scroll.
+    ///
+    /// Far from the origin, points lose precision and items on the
+    /// [`Infinite`] visibly jitter when scrolled or zoomed. With a moving
+    /// origin, kept in double precision, items are placed relative to the
+    /// origin of the view and stay sharp at any scroll. Items themselves are
+    /// still as precise as the points they are drawn at.
+    ///
+    /// Note: Only the drawing uses the double precision origin. The cursor
+    /// position on the canvas, as in [`InputContext::infinite_cursor`], the
+    /// scroll given to [`Program::on_scroll`] and the [`Viewport`] are still
+    /// single precision, so they lose precision as far from the origin as
+    /// before.
+    pub fn rebase_origin(mut self, rebase: bool) -> Self {
+        self.rebase_origin = rebase;
+        self
+    }
+
+    /// Sets whether the [`Infinite`] can be rotated by twisting two fingers
+    /// on touch devices.
+    ///
+    /// The [`Infinite`] can always be rotated through [`rotate_to`]. Items are
+    /// rotated with the canvas, but text stays upright.
+    pub fn rotate(mut self, allow: bool) -> Self {
+        self.allow_rotation = allow;
+        self
+    }
+
+    /// Sets which axes of the [`Infinite`] are scaled by a zoom.
+    ///
+    /// Zooming a single axis stretches the canvas along it, like the time
+    /// axis of a plot.
+    pub fn zoom_axes(mut self, axes: ZoomAxes) -> Self {
+        self.zoom_axes = axes;
+        self
+    }
+
+    /// Sets whether a rectangle can be dragged out while holding Cmd(Ctrl) to
+    /// zoom the [`Infinite`] to fit it.
+    pub fn zoom_box(mut self, allow: bool) -> Self {
+        self.zoom_box = allow;
+        self
+    }
+
+    /// Draws a grid behind the items of the [`Infinite`], see [`GridConfig`].
+    pub fn grid(mut self, config: GridConfig) -> Self {
+        self.grid = Some(config);
+        self
+    }
+
+    /// Draws the X and Y axes through the origin behind the items of the
+    /// [`Infinite`], with ticks labelled with their co-ordinate. See
+    /// [`AxesConfig`].
+    pub fn show_axes(mut self, config: AxesConfig) -> Self {
+        self.axes = Some(config);
+        self
+    }
+
+    /// Draws lines between every unit over the items of the [`Infinite`]
+    /// once it is zoomed to at least `min_zoom`, like the pixel grid of an
+    /// image editor.
+    ///
+    /// A `min_zoom` of around 8.0 keeps the lines from crowding the items.
+    pub fn pixel_grid(mut self, min_zoom: f32) -> Self {
+        self.pixel_grid = Some(min_zoom);
+        self
+    }
+
+    /// Splits the [`Infinite`] into square [`Chunk`]s of the given `size`,
+    /// in units, and notifies the [`Program`] whenever the visible chunks
+    /// change, see [`Program::on_region_needed`].
+    ///
+    /// The chunks of the pane being interacted with are reported.
+    pub fn chunks(mut self, size: f32) -> Self {
+        self.chunk_size = Some(size);
+        self
+    }
+
+    /// Draws the map tiles of the [`TileLayer`](crate::tiles::TileLayer)
+    /// beneath the grid and the items of the [`Infinite`].
+    ///
+    /// The map is drawn with north at the top whatever the [`YDirection`] of
+    /// the [`Infinite`], see the `tiles` module.
+    #[cfg(feature = "tiles")]
+    pub fn tiles(mut self, layer: &'a crate::tiles::TileLayer) -> Self {
+        self.tiles = Some(layer);
+        self
+    }
+
+    /// Sets whether rulers are shown along the top and left edges of the
+    /// [`Infinite`].
+    ///
+    /// The rulers measure the canvas as if it were not rotated. Dragging out
+    /// of the top ruler adds a horizontal [`Guide`] and dragging out of the
+    /// left ruler a vertical one. Guides are moved by dragging them, and
+    /// removed by dragging them back onto a ruler, see
+    /// [`Program::on_guides_change`].
+    pub fn rulers(mut self, show: bool) -> Self {
+        self.rulers = show;
+        self
+    }
+
+    /// Sets which details of its scroll and zoom the [`Infinite`] shows, and
+    /// how. See [`HudConfig`].
+    pub fn hud(mut self, config: HudConfig) -> Self {
+        self.hud = config;
+        self
+    }
+
+    /// Sets whether a scale bar is shown in the top-right corner of the
+    /// [`Infinite`].
+    ///
+    /// The bar spans a round number of units along the X axis, like 1, 2, 5
+    /// or 10 units, and its length follows the zoom.
+    pub fn scale_bar(mut self, show: bool) -> Self {
+        self.scale_bar = show;
+        self
+    }
+
+    /// Sets whether an overlay of how each frame was drawn is shown in the
+    /// top-left corner of the [`Infinite`], to find what slows drawing down.
+    ///
+    /// The overlay shows how long the items took to prepare, how many
+    /// buffers, paths and texts were drawn, how many items were culled by
+    /// their [zoom range](Buffer::zoom_range) or the
+    /// [frame budget](Infinite::frame_budget), and the current scroll and
+    /// zoom.
+    pub fn debug_overlay(mut self, show: bool) -> Self {
+        self.debug_overlay = show;
+        self
+    }
+
+    /// Sets whether double clicking or double tapping the [`Infinite`] zooms in
+    /// by a single zoom step around the cursor.
+    ///
+    /// Double clicks captured by the [`Program`], see
+    /// [`Event::DoubleClick`](event::Event::DoubleClick), do not zoom.
+    pub fn double_click_zoom(mut self, allow: bool) -> Self {
+        self.double_click_zoom = allow;
+        self
+    }
+
+    /// Sets how long the cursor rests over the [`Infinite`] before the
+    /// tooltip of the [`Program`] shows, see [`Program::tooltip`].
+    pub fn tooltip_delay(mut self, delay: Duration) -> Self {
+        self.tooltip_delay = delay;
+        self
+    }
+
+    /// Sets the value of a single zoom on the [`Infinite`].
+    pub fn zoom_step(mut self, step: f32) -> Self {
+        self.scale_step = Some(step);
+        self
+    }
+
+    /// Sets whether mouse scrolls the [`Infinite`] cannot consume are passed
+    /// on to its parent.
+    ///
+    /// A scroll cannot be consumed when none of its direction is supported by
+    /// the [`ScrollDirection`] of the [`Infinite`], when the view is already
+    /// at the edge of its [`Infinite::scroll_bounds`], or when zooming is not
+    /// allowed. This lets an [`Infinite`] nested inside another, like a detail
+    /// canvas in an overlay, hand scrolls over to the outer [`Infinite`].
+    pub fn scroll_chaining(mut self, chain: bool) -> Self {
+        self.scroll_chaining = chain;
+        self
+    }
+
+    /// Sets the value of a single scroll on the [`Infinite`], for both the
+    /// mouse wheel and the keyboard.
+    ///
+    /// See [`Infinite::wheel_step`] and [`Infinite::keyboard_step`] to set
+    /// them separately.
+    pub fn scroll_step(mut self, step: Vector) -> Self {
+        self.wheel_step = Some(step);
+        self.keyboard_step = Some(step);
+        self
+    }
+
+    /// Sets the distance scrolled along each axis by a single line of a
+    /// mouse wheel, or a single trackpad scroll.
+    ///
+    /// By default, a line scrolls 100 pixels and trackpad scrolls are used
+    /// as is.
+    pub fn wheel_step(mut self, step: Vector) -> Self {
+        self.wheel_step = Some(step);
+        self
+    }
+
+    /// Sets the distance scrolled along each axis by a single press of the
+    /// keyboard scroll shortcuts.
+    ///
+    /// By default, a press scrolls 25 pixels along either axis.
+    pub fn keyboard_step(mut self, step: Vector) -> Self {
+        self.keyboard_step = Some(step);
+        self
+    }
+
+    /// Sets the factor mouse wheel and trackpad scrolls of the [`Infinite`]
+    /// are multiplied by.
+    ///
+    /// Scrolls are multiplied after the [`Infinite::wheel_step`] is applied.
+    pub fn scroll_multiplier(mut self, multiplier: f32) -> Self {
+        self.scroll_multiplier = multiplier;
+        self
+    }
+
+    /// Sets the directions in which mouse wheel and trackpad scrolls of the
+    /// [`Infinite`] are inverted.
+    ///
+    /// By default, no direction is inverted.
+    pub fn invert_scroll(mut self, direction: ScrollDirection) -> Self {
+        self.invert_scroll = direction;
+        self
+    }
+
+    /// Sets whether the mouse wheel scrolls or zooms the [`Infinite`].
+    ///
+    /// Holding Cmd(Ctrl) while zooming with the wheel zooms around the origin
+    /// instead of the cursor.
+    pub fn wheel_behavior(mut self, behavior: WheelBehavior) -> Self {
+        self.wheel_behavior = behavior;
+        self
+    }
+
+    /// Restricts scrolling so the view never leaves the `region` of the
+    /// [`Infinite`], given by its bottom-left corner.
+    ///
+    /// When zoomed out far enough for the whole region to fit, the region is
+    /// centered in the view instead.
+    /// The bounds are kept on the unrotated canvas, see [`Infinite::rotate`].
+    pub fn scroll_bounds(mut self, region: Rectangle) -> Self {
+        self.scroll_bounds = Some(region);
+        self
+    }
+
+    /// Sets whether the view can be scrolled slightly past the
+    /// [`Infinite::scroll_bounds`], springing back once released.
+    pub fn elastic_overscroll(mut self, elastic: bool) -> Self {
+        self.elastic_overscroll = elastic;
+        self
+    }
+
+    /// Scrolls the [`Infinite`] towards the border the cursor nears while
+    /// dragging something the [`Program`] captured, like a node being moved.
+    ///
+    /// The scroll is reported through [`Program::on_scroll`].
+    pub fn auto_pan(mut self, config: EdgePanConfig) -> Self {
+        self.auto_pan = Some(config);
+        self
+    }
+
+    /// Splits the [`Infinite`] into two panes showing the same [`Program`].
+    ///
+    /// The pane being hovered on receives all events. The panes are
+    /// separated by a divider which can be dragged to resize them.
+    pub fn split(mut self, split: Split) -> Self {
+        self.split = Some(split);
+        self
+    }
+
+    /// Sets the initial fraction of the [`Infinite`] taken up by the first
+    /// pane of a split.
+    ///
+    /// The ratio is clamped between 0.1 and 0.9.
+    pub fn split_ratio(mut self, ratio: f32) -> Self {
+        self.split_ratio = ratio.clamp(MIN_SPLIT_RATIO, MAX_SPLIT_RATIO);
+        self
+    }
+
+    /// Sets whether the panes of a split [`Infinite`] share the same scroll
+    /// and zoom.
+    pub fn link_panes(mut self, link: bool) -> Self {
+        self.link_panes = link;
+        self
+    }
+
+    /// Sets how the view of the [`Infinite`] moves towards a new scroll or
+    /// zoom.
+    pub fn camera_motion(mut self, motion: CameraMotion) -> Self {
+        self.camera_motion = motion;
+        self
+    }
+
+    /// Sets the zoom presets of the [`Infinite`].
+    ///
+    /// A preset is a zoom factor, where 1.0 is the unzoomed canvas. The
+    /// presets are cycled through in ascending order. Presets which are not
+    /// positive are ignored.
+    pub fn zoom_presets(mut self, presets: &[f32]) -> Self {
+        let mut presets: Vec<f32> = presets
+            .iter()
+            .copied()
+            .filter(|preset| preset.is_finite() && *preset > 0.0)
+            .collect();
+        presets.sort_by(f32::total_cmp);
+        presets.dedup();
+
+        self.zoom_presets = presets;
+        self
+    }
+
+    /// Sets how the edges of items on the [`Infinite`] are smoothed.
+    pub fn antialiasing(mut self, antialiasing: Antialiasing) -> Self {
+        self.antialiasing = antialiasing;
+        self
+    }
+
+    /// Sets the time budget for drawing a single frame of the [`Infinite`].
+    ///
+    /// Once drawing a frame takes longer than the budget, the remaining
+    /// low priority [`Buffer`]s of the frame are skipped instead of stalling
+    /// the application. See [`Buffer::low_priority`].
+    pub fn frame_budget(mut self, budget: Duration) -> Self {
+        self.frame_budget = Some(budget);
+        self
+    }
+
+    /// Tessellates cached [`Buffer`]s with at least `min_items` items on a
+    /// worker thread, see [`Buffer::cache`].
+    ///
+    /// Once such a [`Buffer`] has to be made anew, as its [`BufferCache`] was
+    /// cleared or the [`Infinite`] was zoomed, its last geometry is still
+    /// drawn, scaled to the new zoom, until the new geometry is ready and
+    /// takes its place. Scrolling and zooming therefore stay responsive
+    /// while large buffers are tessellated, at the cost of their strokes
+    /// being scaled and their changes showing a few frames late.
+    ///
+    /// Geometry made for another rotation, or for a zoom of only one axis,
+    /// cannot be scaled to the view, so the [`Buffer`] is left out until its
+    /// new geometry is ready, as it is before it is first tessellated.
+    pub fn background_tessellation(mut self, min_items: usize) -> Self {
+        self.background_tessellation = Some(min_items);
+        self
+    }
+
+    /// Sets the [`Id`] of the [`Infinite`].
+    ///
+    /// The [`Id`] is used to control the [`Infinite`] from an application,
+    /// see [`next_zoom_preset`] for example.
+    pub fn id(mut self, id: Id) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Sets when the [`Infinite`] processes its keyboard shortcuts.
+    pub fn keyboard_scope(mut self, scope: KeyboardScope) -> Self {
+        self.keyboard_scope = scope;
+        self
+    }
+
+    /// Sets the keyboard shortcuts of the [`Infinite`].
+    ///
+    /// Use [`KeyBindings::none`] to disable keyboard handling entirely.
+    pub fn key_bindings(mut self, bindings: KeyBindings) -> Self {
+        self.key_bindings = bindings;
+        self
+    }
+
+    /// Sets  the style of the [`Infinite`].
+    pub fn style(mut self, style: impl Fn(&Theme, Status) -> Style + 'a) -> Self
+    where
+        Theme::Class<'a>: From<StyleFn<'a, Theme>>,
+    {
+        self.style = (Box::new(style) as StyleFn<'a, Theme>).into();
+        self
+    }
+
+    /// Scrolls the [`Infinite`] by `diff`, keeping the view within its
+    /// [`Infinite::scroll_bounds`].
+    ///
+    /// The [`Program`] is given the cursor translated with the new scroll.
+    /// Returns the change in offset which was actually applied.
+    fn scroll_by(
+        &self,
+        state: &mut InfiniteState<P::State>,
+        shell: &mut advanced::Shell<'_, Message>,
+        bounds: Rectangle,
+        cursor: Cursor,
+        diff: Vector,
+    ) -> Vector {
+        let scroll = state.scroll();
+        let target = scroll + diff;
+
+        let diff = match self.scroll_bounds {
+            Some(region) => {
+                let (min, max) = offset_range(state.scale, state.y_axis, bounds.size(), region);
+
+                let offset = if self.elastic_overscroll {
+                    Vector::new(
+                        overscroll(scroll.x, target.x, min.x, max.x),
+                        overscroll(scroll.y, target.y, min.y, max.y),
+                    )
+                } else {
+                    Vector::new(target.x.clamp(min.x, max.x), target.y.clamp(min.y, max.y))
+                };
+
+                offset - scroll
+            }
+            None => diff,
+        };
+
+        if diff == Vector::ZERO {
+            return diff;
+        }
+
+        self.begin_gesture(state, shell, bounds, Gesture::Scroll);
+        state.offset = state.offset + diff;
+
+        let cursors = get_cursors(cursor, bounds, self.pointer_view(state));
+        let context = InputContext::new(bounds, cursors, state.keyboard_modifier);
+
+        let scroll = state.scroll();
+        if let Some(msg) = self
+            .program
+            .on_scroll(&mut state.state, context, scroll, diff)
+        {
+            shell.publish(msg);
+        }
+
+        diff
+    }
+
+    /// Moves a view outside of the [`Infinite::scroll_bounds`], like one
+    /// left by a zoom, back within them.
+    ///
+    /// Returns true if the view is still springing back from an overscroll.
+    fn settle_overscroll(
+        &self,
+        state: &mut InfiniteState<P::State>,
+        shell: &mut advanced::Shell<'_, Message>,
+        bounds: Rectangle,
+        cursors: (Cursor, Cursor),
+        now: Instant,
+    ) -> bool {
+        let Some(region) = self.scroll_bounds else {
+            return false;
+        };
+
+        let (min, max) = offset_range(state.scale, state.y_axis, bounds.size(), region);
+        let scroll = state.scroll();
+        let clamped = Vector::new(scroll.x.clamp(min.x, max.x), scroll.y.clamp(min.y, max.y));
+
+        let last_release = state.overscroll.take();
+
+        if clamped == scroll {
+            return false;
+        }
+
+        let target = if self.elastic_overscroll {
+            // Overscrolls are held for as long as the view is being dragged
+            if state.pan.is_some() || !state.fingers.is_empty() {
+                return false;
+            }
+
+            let excess = scroll - clamped;
+
+            // The release starts over if the view moved since its last frame
+            let release = last_release
+                .filter(|(release, last)| {
+                    let moved = release.value(*last) - excess;
+                    moved.x.abs() < 0.5 && moved.y.abs() < 0.5
+                })
+                .map(|(release, _)| release)
+                .unwrap_or_else(|| {
+                    let mut release = Animated::new(excess)
+                        .duration(OVERSCROLL_RELEASE)
+                        .easing(Easing::CubicOut);
+                    release.animate_to(Vector::ZERO, now);
+                    release
+                });
+
+            if release.is_animating(now) {
+                let excess = release.value(now);
+                state.overscroll = Some((release, now));
+                clamped + excess
+            } else {
+                clamped
+            }
+        } else {
+            clamped
+        };
+
+        let diff = target - scroll;
+        state.offset = state.offset + diff;
+
+        let context = InputContext::new(bounds, cursors, state.keyboard_modifier);
+
+        let scroll = state.scroll();
+        if let Some(msg) = self
+            .program
+            .on_scroll(&mut state.state, context, scroll, diff)
+        {
+            shell.publish(msg);
+        }
+
+        state.overscroll.is_some()
+    }
+
+    /// Scrolls the [`Infinite`] towards the border the cursor nears during a
+    /// drag captured by the [`Program`].
+    ///
+    /// Returns true if the [`Infinite`] is still scrolling.
+    fn edge_pan(
+        &self,
+        state: &mut InfiniteState<P::State>,
+        shell: &mut advanced::Shell<'_, Message>,
+        bounds: Rectangle,
+        cursor: Cursor,
+        now: Instant,
+    ) -> bool {
+        let last_frame = state.edge_pan_frame.take();
+
+        let Some(config) = self.auto_pan else {
+            return false;
+        };
+
+        if !state.program_drag {
+            return false;
+        }
+
+        let Some(direction) = cursor
+            .position()
+            .map(|position| edge_direction(bounds, position, config.margin))
+        else {
+            return false;
+        };
+
+        let direction = match self.direction {
+            ScrollDirection::X => Vector::new(direction.x, 0.),
+            ScrollDirection::Y => Vector::new(0., direction.y),
+            ScrollDirection::Both => direction,
+            ScrollDirection::None => Vector::ZERO,
+        };
+
+        if direction == Vector::ZERO {
+            return false;
+        }
+
+        // The first frame only starts the clock
+        if let Some(last) = last_frame {
+            let elapsed = (now - last).min(CameraState::MAX_STEP);
+            let diff = direction * (config.speed * elapsed.as_secs_f32());
+
+            self.scroll_by(state, shell, bounds, cursor, diff);
+        }
+
+        state.edge_pan_frame = Some(now);
+
+        true
+    }
+
+    /// Marks a scroll or zoom `gesture` as ongoing, notifying the [`Program`]
+    /// if it just started.
+    fn begin_gesture(
+        &self,
+        state: &mut InfiniteState<P::State>,
+        shell: &mut advanced::Shell<'_, Message>,
+        bounds: Rectangle,
+        gesture: Gesture,
+    ) {
+        let now = Instant::now();
+
+        let last = match gesture {
+            Gesture::Scroll => &mut state.scroll_gesture,
+            Gesture::Zoom => &mut state.zoom_gesture,
+        };
+
+        if last.replace(now).is_none() {
+            let msg = match gesture {
+                Gesture::Scroll => {
+                    let scroll = state.scroll();
+                    self.program
+                        .on_scroll_start(&mut state.state, bounds, scroll)
+                }
+                Gesture::Zoom => {
+                    let zoom = self.zoom_axes.get(state.scale);
+                    self.program.on_zoom_start(&mut state.state, bounds, zoom)
+                }
+            };
+
+            if let Some(msg) = msg {
+                shell.publish(msg);
+            }
+        }
+
+        shell.request_redraw(window::RedrawRequest::At(now + GESTURE_IDLE));
+    }
+
+    /// Ends the scroll and zoom gestures which were released and idle for
+    /// long enough.
+    fn end_gestures(
+        &self,
+        state: &mut InfiniteState<P::State>,
+        shell: &mut advanced::Shell<'_, Message>,
+        bounds: Rectangle,
+        now: Instant,
+    ) {
+        let held = state.pan.is_some() || state.fingers.len() > 1;
+
+        for gesture in [Gesture::Scroll, Gesture::Zoom] {
+            let last = match gesture {
+                Gesture::Scroll => &mut state.scroll_gesture,
+                Gesture::Zoom => &mut state.zoom_gesture,
+            };
+
+            let Some(at) = *last else {
+                continue;
+            };
+
+            if held || now - at < GESTURE_IDLE {
+                let wake = if held { now } else { at };
+                shell.request_redraw(window::RedrawRequest::At(wake + GESTURE_IDLE));
+                continue;
+            }
+
+            *last = None;
+
+            let msg = match gesture {
+                Gesture::Scroll => {
+                    let scroll = state.scroll();
+                    self.program.on_scroll_end(&mut state.state, bounds, scroll)
+                }
+                Gesture::Zoom => {
+                    let zoom = self.zoom_axes.get(state.scale);
+                    self.program.on_zoom_end(&mut state.state, bounds, zoom)
+                }
+            };
+
+            if let Some(msg) = msg {
+                shell.publish(msg);
+            }
+        }
+    }
+
+    /// Notifies the [`Program`] of a double click at `position`, zooming in
+    /// if it does not capture it.
+    fn handle_double_click(
+        &self,
+        state: &mut InfiniteState<P::State>,
+        shell: &mut advanced::Shell<'_, Message>,
+        clipboard: &mut dyn advanced::Clipboard,
+        bounds: Rectangle,
+        position: Point,
+    ) {
+        let cursors = get_cursors(
+            Cursor::Available(position),
+            bounds,
+            self.pointer_view(state),
+        );
+        let Some(point) = cursors.1.position() else {
+            return;
+        };
+
+        let context = InputContext::new(bounds, cursors, state.keyboard_modifier);
+        let (status, message) = self.program.update(
+            &mut state.state,
+            Event::DoubleClick(point),
+            context,
+            clipboard,
+        );
+
+        if let Some(message) = message {
+            shell.publish(message);
+        }
+
+        if status == event::Status::Ignored && self.double_click_zoom && self.allow_scale {
+            let step = self.scale_step.unwrap_or(SCALE_STEP);
+
+            state.set_mouse_position(Some(point));
+            handle_scale(self, state, shell, bounds, cursors, step, false);
+        }
+    }
+
+    /// Shows the tooltip once the cursor rests over a point with one, see
+    /// [`Program::tooltip`].
+    fn handle_tooltip(
+        &self,
+        state: &mut InfiniteState<P::State>,
+        shell: &mut advanced::Shell<'_, Message>,
+        event: &iced::Event,
+        bounds: Rectangle,
+    ) {
+        let resting = match event {
+            iced::Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                bounds.contains(*position) && {
+                    let point = translate_cursor(bounds, self.pointer_view(state), *position);
+                    self.program.tooltip(&state.state, point).is_some()
+                }
+            }
+            iced::Event::Mouse(
+                mouse::Event::ButtonPressed(_)
+                | mouse::Event::WheelScrolled { .. }
+                | mouse::Event::CursorLeft,
+            )
+            | iced::Event::Touch(_) => false,
+            _ => return,
+        };
+
+        if state.tooltip.shown {
+            // The tooltip may change or follow the cursor
+            shell.invalidate_layout();
+        }
+
+        if !resting {
+            state.tooltip = TooltipState::default();
+        } else if state.tooltip.since.is_none() {
+            let now = Instant::now();
+            state.tooltip.since = Some(now);
+
+            shell.request_redraw(window::RedrawRequest::At(now + self.tooltip_delay));
+        }
+    }
+
+    /// Opens the context menu on a right click or a long press, see
+    /// [`Program::context_menu`].
+    ///
+    /// Returns a status if the event opened the menu.
+    fn handle_context_menu(
+        &self,
+        state: &mut InfiniteState<P::State>,
+        shell: &mut advanced::Shell<'_, Message>,
+        event: &iced::Event,
+        bounds: Rectangle,
+        cursor: Cursor,
+    ) -> Option<iced_event::Status> {
+        match event {
+            iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => {
+                let position = cursor.position_over(bounds)?;
+
+                self.open_menu(state, shell, bounds, position)
+                    .then_some(iced_event::Status::Captured)
+            }
+            iced::Event::Touch(touch::Event::FingerPressed { id, position }) => {
+                // A second finger starts a pinch instead
+                state.menu.press = match state.menu.press {
+                    None if bounds.contains(*position) => {
+                        let now = Instant::now();
+                        shell.request_redraw(window::RedrawRequest::At(now + LONG_PRESS));
+
+                        Some((*id, *position, now))
+                    }
+                    _ => None,
+                };
+
+                None
+            }
+            iced::Event::Touch(touch::Event::FingerMoved { id, position }) => {
+                let (finger, start, _) = state.menu.press?;
+
+                if finger == *id && start.distance(*position) > LONG_PRESS_DISTANCE {
+                    state.menu.press = None;
+                }
+
+                None
+            }
+            iced::Event::Touch(
+                touch::Event::FingerLifted { id, .. } | touch::Event::FingerLost { id, .. },
+            ) => {
+                if state.menu.press.is_some_and(|(finger, _, _)| finger == *id) {
+                    state.menu.press = None;
+                }
+
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Opens the context menu at the `position` on the screen, returning
+    /// whether the [`Program`] has any entries for it.
+    fn open_menu(
+        &self,
+        state: &mut InfiniteState<P::State>,
+        shell: &mut advanced::Shell<'_, Message>,
+        bounds: Rectangle,
+        position: Point,
+    ) -> bool {
+        let point = translate_cursor(bounds, self.pointer_view(state), position);
+
+        if self.program.context_menu(&state.state, point).is_empty() {
+            return false;
+        }
+
+        state.menu.open = Some((position - Vector::new(bounds.x, bounds.y), point));
+        state.menu.pressed = None;
+        // The overlay of the menu is laid out anew
+        shell.invalidate_layout();
+
+        true
+    }
+
+    /// Notifies the [`Program`] of the items hovered and clicked through a
+    /// mouse `event`, see [`Buffer::item_id`].
+    fn handle_picking(
+        &self,
+        state: &mut InfiniteState<P::State>,
+        shell: &mut advanced::Shell<'_, Message>,
+        event: mouse::Event,
+        bounds: Rectangle,
+        cursor: Cursor,
+    ) {
+        let bounds = self.active_bounds(bounds, state);
+        let pane = match self.split {
+            Some(_) => state.split.active,
+            None => Pane::First,
+        };
+        let view = self.pointer_view(state);
+
+        let cursor = cursor.position_over(bounds);
+        let hit = cursor.and_then(|cursor| {
+            let hits = state.picking.hits.borrow();
+
+            hits.get(&pane)?
+                .iter()
+                .rev()
+                .find(|hit| {
+                    let center = Point::new(bounds.width / 2.0, bounds.height / 2.0);
+
+                    hit.contains(&view, center, cursor - Vector::new(bounds.x, bounds.y))
+                })
+                .map(|hit| hit.id)
+        });
+
+        if let Some(cursor) = cursor {
+            state.picking.position = translate_cursor(bounds, view, cursor);
+        }
+
+        let position = state.picking.position;
+
+        if hit != state.picking.hovered {
+            state.picking.hovered = hit;
+
+            if let Some(msg) = self.program.on_item_hover(&mut state.state, hit, position) {
+                shell.publish(msg);
+            }
+        }
+
+        match event {
+            mouse::Event::ButtonPressed(mouse::Button::Left) => state.picking.pressed = hit,
+            mouse::Event::ButtonReleased(mouse::Button::Left) => {
+                let pressed = state.picking.pressed.take();
+
+                if let Some(id) = pressed.filter(|id| hit == Some(*id)) {
+                    if let Some(msg) = self.program.on_item_click(&mut state.state, id, position) {
+                        shell.publish(msg);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Executes a [`Command`] sent to the [`Infinite`].
+    fn execute(
+        &self,
+        state: &mut InfiniteState<P::State>,
+        shell: &mut advanced::Shell<'_, Message>,
+        bounds: Rectangle,
+        cursors: (Cursor, Cursor),
+        command: Command,
+    ) -> iced_event::Status {
+        match command {
+            Command::CycleZoom { forward } => {
+                let scale = self.zoom_axes.get(state.scale);
+
+                match cycle_preset(&self.zoom_presets, scale, forward) {
+                    Some(preset) => {
+                        let level = self.zoom_axes.with(state.scale_level, preset.ln());
+                        handle_zoom_to(self, state, shell, bounds, cursors, level)
+                    }
+                    None => iced_event::Status::Ignored,
+                }
+            }
+            Command::ZoomToPreset(index) => match self.zoom_presets.get(index) {
+                Some(preset) => {
+                    let level = self.zoom_axes.with(state.scale_level, preset.ln());
+                    handle_zoom_to(self, state, shell, bounds, cursors, level)
+                }
+                None => iced_event::Status::Ignored,
+            },
+            Command::PanByFraction(fraction) => {
+                let offset = Vector::new(fraction.x * bounds.width, -fraction.y * bounds.height);
+
+                let offset = match self.direction {
+                    ScrollDirection::X => Vector::new(offset.x, 0.),
+                    ScrollDirection::Y => Vector::new(0., offset.y),
+                    ScrollDirection::Both => offset,
+                    ScrollDirection::None => return iced_event::Status::Ignored,
+                };
+
+                self.scroll_by(state, shell, bounds, cursors.0, offset);
+
+                iced_event::Status::Captured
+            }
+            Command::ScrollTo(scroll) => {
+                self.scroll_by(state, shell, bounds, cursors.0, scroll - state.scroll());
+
+                iced_event::Status::Captured
+            }
+            Command::ZoomTo(zoom) => {
+                if !zoom.is_finite() || zoom <= 0.0 {
+                    return iced_event::Status::Ignored;
+                }
+
+                let level = self.zoom_axes.with(state.scale_level, zoom.ln());
+                handle_zoom_to(self, state, shell, bounds, cursors, level)
+            }
+            Command::RotateTo(angle) => {
+                if !angle.0.is_finite() {
+                    return iced_event::Status::Ignored;
+                }
+
+                let diff = angle.0 - state.rotation;
+                let offset_diff = state.rotate_around(diff, Vector::ZERO);
+
+                let context = InputContext::new(bounds, cursors, state.keyboard_modifier);
+
+                let scroll = state.scroll();
+                if let Some(msg) =
+                    self.program
+                        .on_scroll(&mut state.state, context, scroll, offset_diff)
+                {
+                    shell.publish(msg);
+                }
+
+                iced_event::Status::Captured
+            }
+            Command::Restore(viewport) => {
+                let scale = viewport.scale;
+
+                if [scale.x, scale.y]
+                    .iter()
+                    .any(|scale| !scale.is_finite() || *scale <= 0.0)
+                {
+                    return iced_event::Status::Ignored;
+                }
+
+                let level = Vector::new(scale.x.ln(), scale.y.ln());
+                handle_zoom_to(self, state, shell, bounds, cursors, level);
+                state.rotate_around(viewport.rotation.0 - state.rotation, Vector::ZERO);
+                self.scroll_by(
+                    state,
+                    shell,
+                    bounds,
+                    cursors.0,
+                    viewport.offset - state.scroll(),
+                );
+
+                iced_event::Status::Captured
+            }
+            Command::Fit { region, padding } => {
+                match region.or_else(|| self.program.content_bounds(&state.state)) {
+                    Some(region) => {
+                        handle_fit(self, state, shell, bounds, cursors, region, padding)
+                    }
+                    None => iced_event::Status::Ignored,
+                }
+            }
+            Command::Reset => {
+                let init_offset = self.program.init_scroll();
+                let init_scale = self.program.init_zoom();
+
+                state.reset_all(init_offset, init_scale);
+
+                let context = InputContext::new(bounds, cursors, state.keyboard_modifier);
+
+                if let Some(msg) =
+                    self.program
+                        .on_scroll_reset(&mut state.state, context, init_offset)
+                {
+                    shell.publish(msg);
+                }
+
+                if let Some(msg) = self
+                    .program
+                    .on_zoom_reset(&mut state.state, context, init_scale)
+                {
+                    shell.publish(msg);
+                }
+
+                iced_event::Status::Captured
+            }
+        }
+    }
+
+    /// Pans the [`Infinite`] by dragging on parts of it the [`Program`] did not
+    /// capture.
+    ///
+    /// Returns a status if the event was consumed by the drag.
+    fn handle_drag_pan(
+        &self,
+        state: &mut InfiniteState<P::State>,
+        shell: &mut advanced::Shell<'_, Message>,
+        event: &iced::Event,
+        bounds: Rectangle,
+        cursor: Cursor,
+    ) -> Option<iced_event::Status> {
+        match event {
+            iced::Event::Mouse(mouse::Event::ButtonPressed(button))
+                if self.pan_trigger.is_some_and(|trigger| {
+                    trigger.is_triggered(*button, state.keyboard_modifier)
+                }) =>
+            {
+                let position = cursor.position_over(bounds)?;
+                state.pan = Some(position);
+
+                Some(iced_event::Status::Captured)
+            }
+            iced::Event::Mouse(mouse::Event::ButtonReleased(button))
+                if state.pan.is_some()
+                    && self
+                        .pan_trigger
+                        .is_some_and(|trigger| trigger.button == *button) =>
+            {
+                state.pan = None;
+
+                Some(iced_event::Status::Captured)
+            }
+            iced::Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                let previous = state.pan.replace(*position)?;
+                let diff = *position - previous;
+
+                let offset = match self.direction {
+                    ScrollDirection::X => Vector::new(diff.x, 0.),
+                    ScrollDirection::Y => Vector::new(0., diff.y),
+                    ScrollDirection::Both => diff,
+                    ScrollDirection::None => Vector::ZERO,
+                };
+
+                self.scroll_by(state, shell, bounds, cursor, -offset);
+
+                let (_, infinite) = get_cursors(cursor, bounds, self.pointer_view(state));
+                state.set_mouse_position(infinite.position());
+
+                Some(iced_event::Status::Captured)
+            }
+            _ => None,
+        }
+    }
+
+    /// Zooms the [`Infinite`] to fit a rectangle dragged out while holding
+    /// Cmd(Ctrl).
+    ///
+    /// Returns a status if the event was consumed by the zoom box.
+    fn handle_zoom_box(
+        &self,
+        state: &mut InfiniteState<P::State>,
+        shell: &mut advanced::Shell<'_, Message>,
+        event: &iced::Event,
+        bounds: Rectangle,
+        cursor: Cursor,
+    ) -> Option<iced_event::Status> {
+        match event {
+            iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+                if state.keyboard_modifier.command() =>
+            {
+                let position = cursor.position_over(bounds)?;
+                state.zoom_box = Some((position, position));
+
+                Some(iced_event::Status::Captured)
+            }
+            iced::Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                let (start, _) = state.zoom_box?;
+                let end = Point::new(
+                    position.x.clamp(bounds.x, bounds.x + bounds.width),
+                    position.y.clamp(bounds.y, bounds.y + bounds.height),
+                );
+                state.zoom_box = Some((start, end));
+
+                Some(iced_event::Status::Captured)
+            }
+            iced::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                let (start, end) = state.zoom_box.take()?;
+                let rectangle = rectangle_between(start, end);
+
+                if rectangle.width < MIN_ZOOM_BOX || rectangle.height < MIN_ZOOM_BOX {
+                    return Some(iced_event::Status::Captured);
+                }
+
+                // The corners of a rotated canvas do not line up with the box
+                let viewport = self.pointer_view(state).viewport(bounds);
+                let corners = [
+                    rectangle.position(),
+                    Point::new(rectangle.x + rectangle.width, rectangle.y),
+                    Point::new(rectangle.x, rectangle.y + rectangle.height),
+                    rectangle.position() + Vector::from(rectangle.size()),
+                ]
+                .map(|corner| viewport.to_world(corner));
+
+                let world = crate::geometry::bounds(&corners).unwrap_or_default();
+
+                let cursors = get_cursors(cursor, bounds, self.pointer_view(state));
+
+                Some(handle_fit(self, state, shell, bounds, cursors, world, 0.0))
+            }
+            _ => None,
+        }
+    }
+
+    /// Adds, moves and removes guides dragged out of the rulers.
+    ///
+    /// Returns a status if the event was consumed by the rulers.
+    fn handle_rulers(
+        &self,
+        state: &mut InfiniteState<P::State>,
+        shell: &mut advanced::Shell<'_, Message>,
+        event: &iced::Event,
+        bounds: Rectangle,
+        cursor: Cursor,
+    ) -> Option<iced_event::Status> {
+        let viewport = self.pointer_view(state).viewport(bounds);
+
+        match event {
+            iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let position = cursor.position_over(bounds)?;
+                let local = position - bounds.position();
+                let world = viewport.to_world(position);
+
+                let index = if local.x < RULER_SIZE && local.y < RULER_SIZE {
+                    return Some(iced_event::Status::Captured);
+                } else if local.y < RULER_SIZE {
+                    state.guides.push(Guide::Horizontal(world.y));
+                    state.guides.len() - 1
+                } else if local.x < RULER_SIZE {
+                    state.guides.push(Guide::Vertical(world.x));
+                    state.guides.len() - 1
+                } else {
+                    guide_at(&state.guides, &viewport, position)?
+                };
+
+                state.guide_drag = Some(index);
+
+                Some(iced_event::Status::Captured)
+            }
+            iced::Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                let index = state.guide_drag?;
+                let world = viewport.to_world(*position);
+
+                state.guides[index] = match state.guides[index] {
+                    Guide::Horizontal(_) => Guide::Horizontal(world.y),
+                    Guide::Vertical(_) => Guide::Vertical(world.x),
+                };
+
+                Some(iced_event::Status::Captured)
+            }
+            iced::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                let index = state.guide_drag.take()?;
+
+                // Guides dropped back onto their ruler are removed
+                let removed = cursor.position().is_none_or(|position| {
+                    let local = position - bounds.position();
+
+                    match state.guides[index] {
+                        Guide::Horizontal(_) => local.y < RULER_SIZE,
+                        Guide::Vertical(_) => local.x < RULER_SIZE,
+                    }
+                });
+
+                if removed {
+                    state.guides.remove(index);
+                }
+
+                if let Some(msg) = self
+                    .program
+                    .on_guides_change(&mut state.state, &state.guides)
+                {
+                    shell.publish(msg);
+                }
+
+                Some(iced_event::Status::Captured)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the bounds of the pane receiving events.
+    fn active_bounds<State>(&self, bounds: Rectangle, state: &InfiniteState<State>) -> Rectangle {
+        match self.split {
+            Some(split) => {
+                let (first, second, _) = split_bounds(bounds, split, state.split.ratio);
+
+                match state.split.active {
+                    Pane::First => first,
+                    Pane::Second => second,
+                }
+            }
+            None => bounds,
+        }
+    }
+
+    /// Returns the views the first and second panes are drawn with.
+    fn drawn_views<State>(&self, state: &InfiniteState<State>) -> (View, View) {
+        match self.split {
+            Some(_) => state.pane_views(self.link_panes, self.camera_motion),
+            None => {
+                let view = state.camera_view(self.camera_motion);
+                (view, view)
+            }
+        }
+    }
+
+    /// Returns the view the pane receiving events is drawn with, which
+    /// pointer input is translated with.
+    ///
+    /// While the camera moves, see [`CameraMotion`], it lags behind the
+    /// scroll and zoom of the pane.
+    fn pointer_view<State>(&self, state: &InfiniteState<State>) -> View {
+        state.camera_view(self.camera_motion)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn draw_pane(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &Style,
+        state: &InfiniteState<P::State>,
+        pane: Pane,
+        view: View,
+        bounds: Rectangle,
+        cursor: Cursor,
+    ) where
+        Renderer: TileRenderer + 'static,
+        Renderer::Frame: Send,
+        <Renderer::Geometry as Cached>::Cache: 'static,
+    {
+        let position = bounds.position();
+        let started = Instant::now();
+
+        renderer.with_translation(Vector::new(position.x, position.y), |renderer| {
+            let mut frame = Frame::new(renderer, bounds.size());
+            let center = frame.center();
+
+            let (cursor, infinite) = get_cursors(cursor, bounds, view);
+
+            let context = RenderContext {
+                view,
+                center,
+                antialiasing: self.antialiasing,
+            };
+
+            // Whether the geometry after a cached layer goes in its own layer
+            let mut layered = false;
+
+            #[cfg(feature = "tiles")]
+            if let Some(tiles) = self.tiles {
+                let viewport = view.viewport(Rectangle::with_size(bounds.size()));
+
+                renderer.with_layer(Rectangle::with_size(bounds.size()), |renderer| {
+                    tiles.draw(renderer, &viewport);
+                });
+
+                // Images go over the meshes of their layer, so the geometry
+                // goes in a layer above them
+                layered = true;
+            }
+
+            type Content<Renderer> =
+                ContentGeometry<<<Renderer as geometry::Renderer>::Geometry as Cached>::Cache>;
+
+            let version = self.program.content_version(&state.state);
+            let mut stats = FrameStats::default();
+
+            let previous = version.and_then(|version| {
+                state
+                    .geometry
+                    .content
+                    .borrow_mut()
+                    .remove(&pane)
+                    .and_then(|entry| entry.downcast::<Content<Renderer>>().ok())
+                    .filter(|entry| entry.fits(version, &context, bounds.size()))
+            });
+
+            if let Some(previous) = previous {
+                for (geometry, transformation, clip) in &previous.pieces {
+                    let geometry = Renderer::Geometry::load(geometry);
+                    draw_geometry(renderer, geometry, *transformation, *clip);
+                }
+
+                layered = layered || previous.layered;
+                stats.reused = true;
+                state.geometry.content.borrow_mut().insert(pane, previous);
+            } else {
+                let visible = view.viewport(bounds).visible();
+                let zoom = self.zoom_axes.get(view.scale);
+
+                let buffers: Vec<Buffer<'static>> = self.program.draw(
+                    &state.state,
+                    theme,
+                    DrawContext {
+                        bounds,
+                        cursor,
+                        infinite_cursor: infinite,
+                        center: Point::ORIGIN - view.scroll(),
+                        modifiers: state.keyboard_modifier,
+                        visible,
+                        detail: Detail::new(zoom),
+                    },
+                );
+
+                let mut pieces = version.map(|_| Vec::new());
+                // Whether every item was drawn as it will be on the next frame
+                let mut reusable = true;
+
+                if let Some(config) = &self.grid {
+                    draw_grid(&mut frame, style, config, view, bounds.size());
+                }
+
+                if let Some(config) = &self.axes {
+                    draw_axes(&mut frame, style, config, view, bounds.size());
+                }
+
+                let shapes = self
+                    .program
+                    .scene(&state.state)
+                    .into_iter()
+                    .flat_map(|scene| scene.shapes.values());
+
+                let mut layers: Vec<Layer> = shapes
+                    .map(Layer::Shape)
+                    .chain(buffers.iter().map(Layer::Buffer))
+                    .collect();
+
+                layers.sort_by_key(|layer| layer.buffer().z_index);
+
+                let mut cached = Vec::new();
+                let mut hits = Vec::new();
+
+                for layer in &layers {
+                    let over_budget = self
+                        .frame_budget
+                        .is_some_and(|budget| started.elapsed() > budget);
+
+                    if layer.buffer().low_priority && over_budget {
+                        reusable = false;
+                        stats.culled += layer.buffer().items.len();
+                        continue;
+                    }
+
+                    if !layer.buffer().zoom_range.contains(&zoom) {
+                        stats.culled += layer.buffer().items.len();
+                        continue;
+                    }
+
+                    stats.add(layer.buffer());
+                    layer.buffer().hits(&mut hits);
+
+                    reusable = reusable && !layer.buffer().is_animated();
+
+                    let Some(cache) = layer.buffer().cacheable() else {
+                        layer.draw(&mut frame, &context);
+                        continue;
+                    };
+
+                    // Draw what came before, so the cached geometry goes over it
+                    let mut below = Frame::new(renderer, bounds.size());
+                    std::mem::swap(&mut frame, &mut below);
+
+                    let clip = layered.then_some(Rectangle::with_size(bounds.size()));
+                    draw_piece(
+                        renderer,
+                        below.into_geometry(),
+                        Transformation::IDENTITY,
+                        clip,
+                        pieces.as_mut(),
+                    );
+
+                    let piece = self.draw_cached(
+                        renderer,
+                        state,
+                        pane,
+                        layer,
+                        cache,
+                        &context,
+                        bounds.size(),
+                    );
+                    if let Some((pieces, piece)) = pieces.as_mut().zip(piece) {
+                        pieces.push(piece);
+                    }
+                    cached.push(cache.id);
+                    layered = true;
+                }
+
+                state
+                    .geometry
+                    .buffers
+                    .borrow_mut()
+                    .retain(|(id, other), _| *other != pane || cached.contains(id));
+
+                let mut pending = state.geometry.pending.borrow_mut();
+                pending.retain(|(id, other), _| *other != pane || cached.contains(id));
+
+                // The geometry of the pane changes once the workers are done
+                reusable = reusable && !pending.keys().any(|(_, other)| *other == pane);
+                drop(pending);
+
+                state.picking.hits.borrow_mut().insert(pane, hits);
+                self.program.recycle(&state.state, buffers);
+
+                if let Some((version, mut pieces)) = version.zip(pieces) {
+                    // The items are drawn apart from what goes over them, to be
+                    // drawn again on their own
+                    let mut content = Frame::new(renderer, bounds.size());
+                    std::mem::swap(&mut frame, &mut content);
+
+                    let clip = layered.then_some(Rectangle::with_size(bounds.size()));
+                    draw_piece(
+                        renderer,
+                        content.into_geometry(),
+                        Transformation::IDENTITY,
+                        clip,
+                        Some(&mut pieces),
+                    );
+
+                    if reusable {
+                        let entry: Box<Content<Renderer>> = Box::new(ContentGeometry {
+                            version,
+                            size: bounds.size(),
+                            antialiasing: self.antialiasing,
+                            view,
+                            layered,
+                            pieces,
+                        });
+
+                        state.geometry.content.borrow_mut().insert(pane, entry);
+                    }
+                }
+            }
+
+            let prepared = started.elapsed();
+
+            if let Some(min_zoom) = self.pixel_grid {
+                if view.scale.x.min(view.scale.y) >= min_zoom {
+                    draw_pixel_grid(&mut frame, style, view, bounds.size());
+                }
+            }
+
+            if let Some(spotlight) = &state.spotlight.spotlight {
+                spotlight.draw(&mut frame, &view, center, state.spotlight.progress);
+            }
+
+            if self.rulers {
+                draw_guides(&mut frame, style, &state.guides, view, bounds.size());
+                draw_rulers(&mut frame, style, view, bounds.size());
+            }
+
+            if self.scale_bar {
+                let top = if self.rulers { RULER_SIZE } else { 0.0 };
+                draw_scale_bar(&mut frame, style, view.scale.x, bounds.size(), top);
+            }
+
+            let opacity = self.hud.opacity(state.last_view_change, Instant::now());
+            let mut details = Vec::new();
+
+            if opacity > 0.0 {
+                let scroll = view.scroll();
+
+                if let Some(corner) = self.hud.zoom.filter(|_| view.scale_level != Vector::ZERO) {
+                    let content = match self.hud.format {
+                        Some(format) => format(Readout::Zoom(view.scale)),
+                        None => self.zoom_details(view),
+                    };
+
+                    details.push((corner, content, opacity));
+                }
+
+                if let Some(corner) = self.hud.scroll.filter(|_| scroll != Vector::ZERO) {
+                    let scroll = Vector::new(scroll.x, view.y_axis.sign() * scroll.y);
+
+                    let content = match self.hud.format {
+                        Some(format) => format(Readout::Scroll(scroll)),
+                        None => format!("x: {:.1}, y: {:.1}", scroll.x, scroll.y),
+                    };
+
+                    details.push((corner, content, opacity));
+                }
+            }
+
+            // The position of the cursor is shown for as long as it hovers
+            let position = infinite.position().filter(|_| cursor.is_over(bounds));
+
+            if let Some((corner, position)) = self.hud.cursor.zip(position) {
+                let content = match self.hud.format {
+                    Some(format) => format(Readout::Cursor(position)),
+                    None => format!("x: {:.2}, y: {:.2}", position.x, position.y),
+                };
+
+                details.push((corner, content, 1.0));
+            }
+
+            if self.debug_overlay {
+                let scroll = view.scroll();
+                let reused = if stats.reused { " (reused)" } else { "" };
+
+                let lines = [
+                    format!("Frame: {:.2} ms{reused}", prepared.as_secs_f64() * 1000.0),
+                    format!(
+                        "Buffers: {}, paths: {}, text: {}",
+                        stats.buffers, stats.paths, stats.texts
+                    ),
+                    format!("Culled: {} items", stats.culled),
+                    format!(
+                        "Offset: {:.1}, {:.1}, scale: {:.2}, {:.2}",
+                        scroll.x,
+                        view.y_axis.sign() * scroll.y,
+                        view.scale.x,
+                        view.scale.y
+                    ),
+                ];
+
+                details.extend(lines.map(|line| (Corner::TopLeft, line, 1.0)));
+            }
+
+            let inset = if self.rulers { RULER_SIZE } else { 0.0 };
+            draw_details(&mut frame, style, details, bounds.size(), inset);
+
+            let clip = layered.then_some(Rectangle::with_size(bounds.size()));
+            draw_geometry(
+                renderer,
+                frame.into_geometry(),
+                Transformation::IDENTITY,
+                clip,
+            );
+        });
+    }
+
+    /// Draws the geometry of a cached `layer`, making it anew if the
+    /// [`BufferCache`] was cleared or the view changed too much since.
+    ///
+    /// Returns the piece drawn, if the `layer` had any geometry to draw.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_cached(
+        &self,
+        renderer: &mut Renderer,
+        state: &InfiniteState<P::State>,
+        pane: Pane,
+        layer: &Layer,
+        cache: &BufferCache,
+        context: &RenderContext,
+        size: Size,
+    ) -> Option<Piece<<Renderer::Geometry as Cached>::Cache>>
+    where
+        Renderer: 'static,
+        Renderer::Frame: Send,
+        <Renderer::Geometry as Cached>::Cache: 'static,
+    {
+        type Entry<Renderer> =
+            CachedGeometry<<<Renderer as geometry::Renderer>::Geometry as Cached>::Cache>;
+
+        let mut caches = state.geometry.buffers.borrow_mut();
+        let generation = cache.generation();
+
+        let mut entry = caches
+            .remove(&(cache.id, pane))
+            .and_then(|entry| entry.downcast::<Entry<Renderer>>().ok());
+
+        let background = self
+            .background_tessellation
+            .is_some_and(|min_items| layer.buffer().items.len() >= min_items);
+
+        if background {
+            entry =
+                self.swap_tessellated(renderer, state, pane, layer, cache, context, size, entry);
+
+            if let Some(frame) = state.geometry.settled.borrow_mut().as_mut() {
+                frame.caches.push((cache.clone(), generation));
+            }
+        } else if !entry
+            .as_ref()
+            .is_some_and(|entry| entry.fits(generation, context, size))
+        {
+            let (group, previous) = match entry {
+                Some(entry) => (entry.group, Some(entry.geometry)),
+                None => (cache::Group::unique(), None),
+            };
+
+            // Span a pane in every direction, to be scrolled over
+            let mut frame = Frame::new(renderer, size * 3.0);
+            frame.translate(Vector::new(size.width, size.height));
+            layer.draw(&mut frame, context);
+
+            entry = Some(Box::new(CachedGeometry {
+                generation,
+                size,
+                antialiasing: context.antialiasing,
+                view: context.view,
+                group,
+                geometry: frame.into_geometry().cache(group, previous),
+            }));
+        }
+
+        let entry = entry?;
+        let transformation = entry.transformation(context);
+
+        let piece = transformation.map(|transformation| {
+            let geometry = Renderer::Geometry::load(&entry.geometry);
+
+            // The geometry spans past the pane, so it is clipped by a layer of
+            // its own
+            let clip = Rectangle::with_size(size);
+            draw_geometry(renderer, geometry, transformation, Some(clip));
+
+            (entry.geometry.clone(), transformation, Some(clip))
+        });
+
+        caches.insert((cache.id, pane), entry);
+
+        piece
+    }
+
+    /// Takes the geometry of a cached `layer` tessellated on a worker thread
+    /// in place of its `entry` once it is ready, and starts tessellating the
+    /// `layer` anew if neither fits the view, see
+    /// [`Infinite::background_tessellation`].
+    ///
+    /// Returns the geometry to draw the `layer` with.
+    #[allow(clippy::too_many_arguments)]
+    fn swap_tessellated(
+        &self,
+        renderer: &Renderer,
+        state: &InfiniteState<P::State>,
+        pane: Pane,
+        layer: &Layer,
+        cache: &BufferCache,
+        context: &RenderContext,
+        size: Size,
+        mut entry: Option<Box<CachedGeometry<<Renderer::Geometry as Cached>::Cache>>>,
+    ) -> Option<Box<CachedGeometry<<Renderer::Geometry as Cached>::Cache>>>
+    where
+        Renderer: 'static,
+        Renderer::Frame: Send,
+    {
+        let mut pending = state.geometry.pending.borrow_mut();
+        let generation = cache.generation();
+
+        let mut job = pending
+            .remove(&(cache.id, pane))
+            .and_then(|job| job.downcast::<Tessellation<Frame<Renderer>>>().ok());
+
+        if let Some(finished) = job.take_if(|job| job.worker.is_finished()) {
+            let Tessellation {
+                generation,
+                size,
+                antialiasing,
+                view,
+                worker,
+            } = *finished;
+
+            // A worker which panicked is started again below
+            if let Ok(frame) = worker.join() {
+                let (group, previous) = match entry {
+                    Some(entry) => (entry.group, Some(entry.geometry)),
+                    None => (cache::Group::unique(), None),
+                };
+
+                entry = Some(Box::new(CachedGeometry {
+                    generation,
+                    size,
+                    antialiasing,
+                    view,
+                    group,
+                    geometry: frame.into_geometry().cache(group, previous),
+                }));
+            }
+        }
+
+        let fits = entry
+            .as_ref()
+            .is_some_and(|entry| entry.fits(generation, context, size));
+
+        // A single worker runs for each layer, whose geometry is taken even
+        // if the view moved on in the meantime
+        if !fits && job.is_none() {
+            // Span a pane in every direction, to be scrolled over
+            let mut frame = Frame::new(renderer, size * 3.0);
+            frame.translate(Vector::new(size.width, size.height));
+
+            let buffer = layer.buffer().clone();
+            let context = *context;
+
+            job = Some(Box::new(Tessellation {
+                generation,
+                size,
+                antialiasing: context.antialiasing,
+                view: context.view,
+                worker: thread::spawn(move || {
+                    buffer.draw(&mut frame, &context);
+                    frame
+                }),
+            }));
+        }
+
+        if let Some(job) = job {
+            pending.insert((cache.id, pane), job);
+        }
+
+        entry
+    }
+
+    /// Returns the default readout of the zoom of a `view` in the details.
+    fn zoom_details(&self, view: View) -> String {
+        let level = self.zoom_axes.get(view.scale_level);
+
+        let preset = self
+            .zoom_presets
+            .iter()
+            .position(|preset| (preset.ln() - level).abs() < PRESET_EPSILON);
+
+        match preset {
+            Some(index) => format!(
+                "{:.0}% ({}/{})",
+                self.zoom_presets[index] * 100.,
+                index + 1,
+                self.zoom_presets.len()
+            ),
+            None if view.scale_level.x != view.scale_level.y => format!(
+                "x: {:.0}%, y: {:.0}%",
+                view.scale_level.x * 100.,
+                view.scale_level.y * 100.
+            ),
+            None => format!("{:.0}%", level * 100.),
+        }
+    }
+
+    /// Processes an event, see [`Widget::on_event`].
+    #[allow(clippy::too_many_arguments)]
+    fn handle_event(
+        &mut self,
+        state: &mut tree::Tree,
+        event: iced::Event,
+        layout: layout::Layout<'_>,
+        cursor: advanced::mouse::Cursor,
+        _renderer: &Renderer,
+        clipboard: &mut dyn advanced::Clipboard,
+        shell: &mut advanced::Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> iced_event::Status {
+        let bounds = layout.bounds();
+
+        if let iced::Event::Window(window::Event::RedrawRequested(now)) = event {
+            let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+            let target = self.program.spotlight(&state.state);
+            let fading = state.spotlight.animate(target, now);
+            let moving = state.follow_camera(self.camera_motion, now);
+
+            let bounds = self.active_bounds(bounds, state);
+            let cursors = get_cursors(cursor, bounds, self.pointer_view(state));
+
+            let resting = state
+                .tooltip
+                .since
+                .is_some_and(|since| now - since >= self.tooltip_delay);
+
+            if resting && !state.tooltip.shown {
+                state.tooltip.shown = true;
+                shell.invalidate_layout();
+            }
+
+            if let Some((_, position, at)) = state.menu.press {
+                if now - at >= LONG_PRESS {
+                    state.menu.press = None;
+                    self.open_menu(state, shell, bounds, position);
+                }
+            }
+
+            let settling = self.settle_overscroll(state, shell, bounds, cursors, now);
+            let panning = self.edge_pan(state, shell, bounds, cursor, now);
+
+            if let Some(request) = self.program.tick(&mut state.state, now) {
+                shell.request_redraw(request);
+            }
+
+            self.end_gestures(state, shell, bounds, now);
+
+            // Workers cannot ask for a redraw once they are done, so frames
+            // are drawn until the geometry they tessellate is swapped in
+            let tessellating = self.background_tessellation.is_some()
+                && !state
+                    .geometry
+                    .is_settled(layout.bounds(), self.drawn_views(state));
+
+            if fading
+                || moving
+                || settling
+                || panning
+                || tessellating
+                || self.hud.is_fading(state.last_view_change, now)
+                || self.program.is_animating(&state.state, now)
+            {
+                shell.request_redraw(window::RedrawRequest::NextFrame);
+            }
+        }
+
+        match event {
+            iced::Event::Mouse(mouse::Event::ButtonPressed(_))
+            | iced::Event::Touch(touch::Event::FingerPressed { .. }) => {
+                let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+                state.focused = cursor.is_over(bounds);
+            }
+            iced::Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+                state.keyboard_modifier = modifiers;
+            }
+            iced::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+            | iced::Event::Touch(
+                touch::Event::FingerLifted { .. } | touch::Event::FingerLost { .. },
+            ) => {
+                let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+                state.program_drag = false;
+            }
+            // Releases may never arrive once the window loses focus
+            iced::Event::Window(window::Event::Unfocused) => {
+                let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+                state.pan = None;
+                state.zoom_box = None;
+                state.guide_drag = None;
+                state.program_drag = false;
+                state.fingers.clear();
+            }
+            iced::Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+                state.screen_cursor = Some(position);
+
+                // Starts the edge pan, which then keeps itself going
+                if self.auto_pan.is_some() && state.program_drag && state.edge_pan_frame.is_none() {
+                    shell.request_redraw(window::RedrawRequest::NextFrame);
+                }
+            }
+            _ => {}
+        }
+
+        let bounds = match self.split {
+            Some(split) => {
+                let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+
+                if let Some(status) =
+                    handle_split(state, split, self.link_panes, &event, cursor, bounds)
+                {
+                    return status;
+                }
+
+                self.active_bounds(bounds, state)
+            }
+            None => bounds,
+        };
+
+        {
+            let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+            self.handle_tooltip(state, shell, &event, bounds);
+        }
+
+        let (cursor, capturing) = {
+            let state = state.state.downcast_ref::<InfiniteState<P::State>>();
+            let capturing = self.program.captures_pointer(&state.state);
+
+            (state.pointer(cursor, capturing), capturing)
+        };
+
+        let pressed = match event {
+            iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                cursor.position_over(bounds)
+            }
+            iced::Event::Touch(touch::Event::FingerPressed { position, .. }) => {
+                Some(position).filter(|position| bounds.contains(*position))
+            }
+            _ => None,
+        };
+
+        if let Some(position) = pressed {
+            let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+
+            if state.is_double_press(position, Instant::now()) {
+                self.handle_double_click(state, shell, clipboard, bounds, position);
+            }
+        }
+
+        if self.rulers {
+            let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+
+            if let Some(status) = self.handle_rulers(state, shell, &event, bounds, cursor) {
+                return status;
+            }
+        }
+
+        if !capturing {
+            let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+
+            // Ongoing drag pans and those triggered with modifiers take
+            // precedence over the program
+            let explicit = self
+                .pan_trigger
+                .is_some_and(|trigger| trigger.modifiers.is_some());
+
+            if state.pan.is_some() || explicit {
+                if let Some(status) = self.handle_drag_pan(state, shell, &event, bounds, cursor) {
+                    return status;
+                }
+            }
+
+            if self.zoom_box && self.allow_scale {
+                if let Some(status) = self.handle_zoom_box(state, shell, &event, bounds, cursor) {
+                    return status;
+                }
+            }
+        }
+
+        let canvas_event = {
+            let state = state.state.downcast_ref::<InfiniteState<P::State>>();
+
+            wrap_event(event.clone(), bounds, self.pointer_view(state))
+        };
+
+        if let Some(canvas_event) = canvas_event {
+            let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+            let cursors = get_cursors(cursor, bounds, self.pointer_view(state));
+            let context = InputContext::new(bounds, cursors, state.keyboard_modifier);
+
+            let (status, message) =
+                self.program
+                    .update(&mut state.state, canvas_event, context, clipboard);
+
+            if let Some(message) = message {
+                shell.publish(message);
+            }
+
+            if status == event::Status::Captured {
+                if matches!(
+                    event,
+                    iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+                        | iced::Event::Touch(touch::Event::FingerPressed { .. })
+                ) {
+                    state.program_drag = true;
+                }
+
+                return status.into();
+            }
+        }
+
+        // Unless the program handles them, Escape drops focus and Tab moves
+        // it on to other widgets
+        if let iced::Event::Keyboard(keyboard::Event::KeyPressed {
+            key: keyboard::Key::Named(keyboard::key::Named::Escape | keyboard::key::Named::Tab),
+            ..
+        }) = event
+        {
+            let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+            state.focused = false;
+        }
+
+        // The pointer belongs to the program until it releases it
+        if capturing && matches!(event, iced::Event::Mouse(_)) {
+            return iced_event::Status::Captured;
+        }
+
+        {
+            let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+
+            if let Some(status) = self.handle_context_menu(state, shell, &event, bounds, cursor) {
+                return status;
+            }
+        }
+
+        if self.pan_trigger.is_some() {
+            let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+
+            if let Some(status) = self.handle_drag_pan(state, shell, &event, bounds, cursor) {
+                return status;
+            }
+        }
+
+        let in_scope = match event {
+            iced::Event::Keyboard(_) => match self.keyboard_scope {
+                KeyboardScope::Hovered => cursor.is_over(bounds),
+                KeyboardScope::Focused => {
+                    state
+                        .state
+                        .downcast_ref::<InfiniteState<P::State>>()
+                        .focused
+                }
+                KeyboardScope::Always => true,
+            },
+            // Fingers are tracked by position
+            iced::Event::Touch(_) => true,
+            _ => cursor.is_over(bounds),
+        };
+
+        if !in_scope {
+            return iced_event::Status::Ignored;
+        }
+
+        match event {
+            iced::Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
+                let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+                let (cursor, infinite) = get_cursors(cursor, bounds, self.pointer_view(state));
+                let modifiers = state.keyboard_modifier;
+                let scale_step = self.scale_step.unwrap_or(SCALE_STEP);
+
+                match delta {
+                    // Zoom
+                    mouse::ScrollDelta::Lines { y, .. } | mouse::ScrollDelta::Pixels { y, .. }
+                        if self.wheel_behavior.zooms(modifiers) =>
+                    {
+                        if !self.allow_scale {
+                            return iced_event::Status::Ignored;
+                        };
+                        let step = if y < 0. { -scale_step } else { scale_step };
+                        let origin = modifiers.command();
+                        handle_scale(self, state, shell, bounds, (cursor, infinite), step, origin)
+                    }
+
+                    // Translation
+                    mouse::ScrollDelta::Pixels { x, y } | mouse::ScrollDelta::Lines { x, y } => {
+                        let (x, y) = match (self.wheel_step, delta) {
+                            (Some(step), mouse::ScrollDelta::Lines { .. }) => {
+                                (x * step.x, y * step.y)
+                            }
+                            // Trackpads report distances, only their direction is kept
+                            (Some(step), mouse::ScrollDelta::Pixels { .. }) => {
+                                (axis_step(x, step.x), axis_step(y, step.y))
+                            }
+                            (None, mouse::ScrollDelta::Lines { .. }) => {
+                                (x * LINE_SCROLL, y * LINE_SCROLL)
+                            }
+                            (None, mouse::ScrollDelta::Pixels { .. }) => (x, y),
+                        };
+                        let mult = self.scroll_multiplier;
+                        let offset = match self.direction {
+                            ScrollDirection::X => Vector::new(x, 0.),
+                            ScrollDirection::Y => Vector::new(0., y),
+                            ScrollDirection::Both => Vector::new(x, y),
+                            ScrollDirection::None => return iced_event::Status::Ignored,
+                        } * mult;
+                        let offset = match self.invert_scroll {
+                            ScrollDirection::X => Vector::new(-offset.x, offset.y),
+                            ScrollDirection::Y => Vector::new(offset.x, -offset.y),
+                            ScrollDirection::Both => -offset,
+                            ScrollDirection::None => offset,
+                        };
+
+                        let moved = self.scroll_by(state, shell, bounds, cursor, -offset);
+
+                        if self.scroll_chaining && moved == Vector::ZERO {
+                            return iced_event::Status::Ignored;
+                        }
+
+                        iced_event::Status::Captured
+                    }
+                }
+            }
+
+            iced::Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
+                let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+                let (cursor, infinite) = get_cursors(cursor, bounds, self.pointer_view(state));
+                let (offset_x, offset_y) = match self.keyboard_step {
+                    Some(offset) => (offset.x, offset.y),
+                    None => (OFFSET_STEP, OFFSET_STEP),
+                };
+                let scale_step = self.scale_step.unwrap_or(SCALE_STEP);
+
+                match self.key_bindings.action(&key, modifiers) {
+                    // Zoom
+                    Some(KeyAction::ZoomInOrigin) => {
+                        if !self.allow_scale {
+                            return iced_event::Status::Ignored;
+                        };
+                        handle_scale(
+                            self,
+                            state,
+                            shell,
+                            bounds,
+                            (cursor, infinite),
+                            scale_step,
+                            true,
+                        )
+                    }
+
+                    Some(KeyAction::ZoomOutOrigin) => {
+                        if !self.allow_scale {
+                            return iced_event::Status::Ignored;
+                        };
+                        handle_scale(
+                            self,
+                            state,
+                            shell,
+                            bounds,
+                            (cursor, infinite),
+                            -scale_step,
+                            true,
+                        )
+                    }
+
+                    Some(KeyAction::ZoomIn) => {
+                        if !self.allow_scale {
+                            return iced_event::Status::Ignored;
+                        };
+                        handle_scale(
+                            self,
+                            state,
+                            shell,
+                            bounds,
+                            (cursor, infinite),
+                            scale_step,
+                            false,
+                        )
+                    }
+
+                    Some(KeyAction::ZoomOut) => {
+                        if !self.allow_scale {
+                            return iced_event::Status::Ignored;
+                        };
+                        handle_scale(
+                            self,
+                            state,
+                            shell,
+                            bounds,
+                            (cursor, infinite),
+                            -scale_step,
+                            false,
+                        )
+                    }
+
+                    // Translations
+                    Some(KeyAction::PanUp) => {
+                        let offset = match self.direction {
+                            ScrollDirection::X => Vector::new(0., 0.),
+                            ScrollDirection::Y => Vector::new(0., offset_y),
+                            ScrollDirection::Both => Vector::new(0., offset_y),
+                            ScrollDirection::None => return iced_event::Status::Ignored,
+                        };
+                        let offset =
+                            Vector::new(offset.x / state.scale.x, offset.y / state.scale.y);
+
+                        self.scroll_by(state, shell, bounds, cursor, -offset);
+
+                        iced_event::Status::Captured
+                    }
+
+                    Some(KeyAction::PanDown) => {
+                        let offset = match self.direction {
+                            ScrollDirection::X => Vector::new(0., 0.),
+                            ScrollDirection::Y => Vector::new(0., offset_y),
+                            ScrollDirection::Both => Vector::new(0., offset_y),
+                            ScrollDirection::None => return iced_event::Status::Ignored,
+                        };
+                        let offset =
+                            Vector::new(offset.x / state.scale.x, offset.y / state.scale.y);
+
+                        self.scroll_by(state, shell, bounds, cursor, offset);
+
+                        iced_event::Status::Captured
+                    }
+
+                    Some(KeyAction::PanLeft) => {
+                        let offset = match self.direction {
+                            ScrollDirection::X => Vector::new(offset_x, 0.),
+                            ScrollDirection::Y => Vector::new(0., 0.),
+                            ScrollDirection::Both => Vector::new(offset_x, 0.),
+                            ScrollDirection::None => return iced_event::Status::Ignored,
+                        };
+                        let offset =
+                            Vector::new(offset.x / state.scale.x, offset.y / state.scale.y);
+
+                        self.scroll_by(state, shell, bounds, cursor, -offset);
+
+                        iced_event::Status::Captured
+                    }
+                    Some(KeyAction::PanRight) => {
+                        let offset = match self.direction {
+                            ScrollDirection::X => Vector::new(offset_x, 0.),
+                            ScrollDirection::Y => Vector::new(0., 0.),
+                            ScrollDirection::Both => Vector::new(offset_x, 0.),
+                            ScrollDirection::None => return iced_event::Status::Ignored,
+                        };
+                        let offset =
+                            Vector::new(offset.x / state.scale.x, offset.y / state.scale.y);
+
+                        self.scroll_by(state, shell, bounds, cursor, offset);
+
+                        iced_event::Status::Captured
+                    }
+
+                    // Pages
+                    Some(
+                        action @ (KeyAction::PageUp
+                        | KeyAction::PageDown
+                        | KeyAction::PageLeft
+                        | KeyAction::PageRight),
+                    ) => {
+                        let fraction = match action {
+                            KeyAction::PageUp => Vector::new(0., PAGE_FRACTION),
+                            KeyAction::PageDown => Vector::new(0., -PAGE_FRACTION),
+                            KeyAction::PageLeft => Vector::new(-PAGE_FRACTION, 0.),
+                            _ => Vector::new(PAGE_FRACTION, 0.),
+                        };
+
+                        let command = Command::PanByFraction(fraction);
+                        self.execute(state, shell, bounds, (cursor, infinite), command)
+                    }
+
+                    // Zoom presets
+                    Some(action @ (KeyAction::NextPreset | KeyAction::PreviousPreset)) => {
+                        if !self.allow_scale || self.zoom_presets.is_empty() {
+                            return iced_event::Status::Ignored;
+                        };
+
+                        let command = Command::CycleZoom {
+                            forward: action == KeyAction::NextPreset,
+                        };
+
+                        self.execute(state, shell, bounds, (cursor, infinite), command)
+                    }
+
+                    // Resets
+                    Some(KeyAction::ResetAll) => {
+                        self.execute(state, shell, bounds, (cursor, infinite), Command::Reset)
+                    }
+
+                    Some(KeyAction::ResetZoom) => {
+                        let init = self.program.init_zoom();
+                        state.reset_scale(init);
+
+                        let context =
+                            InputContext::new(bounds, (cursor, infinite), state.keyboard_modifier);
+                        let zoom = self.zoom_axes.get(state.scale);
+                        let msg = self.program.on_zoom_reset(&mut state.state, context, zoom);
+
+                        if let Some(msg) = msg {
+                            shell.publish(msg);
+                        }
+                        iced_event::Status::Captured
+                    }
+
+                    Some(KeyAction::ResetScroll) => {
+                        let init = self.program.init_scroll();
+                        state.reset_offset(init);
+
+                        let context =
+                            InputContext::new(bounds, (cursor, infinite), state.keyboard_modifier);
+                        let msg = self
+                            .program
+                            .on_scroll_reset(&mut state.state, context, init);
+
+                        if let Some(msg) = msg {
+                            shell.publish(msg);
+                        }
+
+                        iced_event::Status::Captured
+                    }
+
+                    None => iced_event::Status::Ignored,
+                }
+            }
+
+            iced::Event::Keyboard(keyboard::Event::ModifiersChanged(_)) => {
+                iced_event::Status::Captured
+            }
+
+            iced::Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+                let (_, cursor) = get_cursors(cursor, bounds, self.pointer_view(state));
+
+                state.set_mouse_position(cursor.position());
+
+                iced_event::Status::Captured
+            }
+
+            iced::Event::Mouse(mouse::Event::CursorLeft) => {
+                let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+                state.set_mouse_position(None);
+
+                iced_event::Status::Captured
+            }
+
+            iced::Event::Touch(touch::Event::FingerPressed { id, position }) => {
+                let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+
+                if !bounds.contains(position) {
+                    return iced_event::Status::Ignored;
+                }
+
+                if state.fingers.len() < 2 {
+                    state.fingers.push((id, position));
+                }
+
+                iced_event::Status::Captured
+            }
+
+            iced::Event::Touch(touch::Event::FingerMoved { id, position }) => {
+                let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+
+                let Some(index) = state.fingers.iter().position(|(finger, _)| *finger == id) else {
+                    return iced_event::Status::Ignored;
+                };
+
+                if state.fingers.len() < 2 {
+                    state.fingers[index].1 = position;
+                    return iced_event::Status::Ignored;
+                }
+
+                let other = state.fingers[1 - index].1;
+                let previous = state.fingers[index].1;
+                state.fingers[index].1 = position;
+
+                handle_pinch(self, state, shell, bounds, (previous, position), other)
+            }
+
+            iced::Event::Touch(
+                touch::Event::FingerLifted { id, .. } | touch::Event::FingerLost { id, .. },
+            ) => {
+                let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+                state.fingers.retain(|(finger, _)| *finger != id);
+
+                iced_event::Status::Captured
+            }
+
+            _ => iced_event::Status::Ignored,
+        }
+    }
+}
+
+impl<'a, P, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Infinite<'a, P, Message, Theme, Renderer>
+where
+    Theme: Catalog,
+    P: Program<Message, Theme, Renderer>,
+    Renderer: geometry::Renderer + TileRenderer + 'static,
+    Renderer::Frame: Send,
+    <Renderer::Geometry as Cached>::Cache: 'static,
+{
+    fn size(&self) -> Size<Length> {
+        Size {
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<InfiniteState<P::State>>()
+    }
+
+    fn state(&self) -> tree::State {
+        let state = self.program.init_state();
+        let mut state = InfiniteState::<P::State>::new(state);
+
+        state.offset = self.program.init_scroll();
+        let zoom = self.program.init_zoom();
+        state.set_scale_level(Vector::new(zoom, zoom));
+        state.split.ratio = self.split_ratio;
+        state.y_axis = self.y_axis;
+        state.split.inactive = state.view();
+        state.camera.view = state.view();
+
+        tree::State::new(state)
+    }
+
+    fn diff(&self, tree: &mut tree::Tree) {
+        let state = tree.state.downcast_mut::<InfiniteState<P::State>>();
+        state.set_y_axis(self.y_axis);
+    }
+
+    fn on_event(
+        &mut self,
+        state: &mut tree::Tree,
+        event: iced::Event,
+        layout: layout::Layout<'_>,
+        cursor: advanced::mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn advanced::Clipboard,
+        shell: &mut advanced::Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> iced_event::Status {
+        let before = state.state.downcast_ref::<InfiniteState<P::State>>().view();
+        let mouse_event = match event {
+            iced::Event::Mouse(event) => Some(event),
+            _ => None,
+        };
+
+        let status = self.handle_event(
+            state, event, layout, cursor, renderer, clipboard, shell, viewport,
+        );
+
+        let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+        let commanded = std::mem::take(&mut state.view_commanded);
+
+        if state.view() != before || commanded {
+            let now = Instant::now();
+            state.last_view_change = Some(now);
+
+            if let Some(timeout) = self.hud.fade_out {
+                shell.request_redraw(window::RedrawRequest::At(now + timeout));
+            }
+
+            let bounds = self.active_bounds(layout.bounds(), state);
+            let viewport = state.viewport(bounds);
+
+            if let Some(msg) = self.program.on_viewport_change(&mut state.state, viewport) {
+                shell.publish(msg);
+            }
+
+            state.picking.pressed = None;
+        }
+
+        if let Some(event) = mouse_event {
+            self.handle_picking(state, shell, event, layout.bounds(), cursor);
+        }
+
+        if let Some(size) = self.chunk_size {
+            let bounds = self.active_bounds(layout.bounds(), state);
+            let visible = state.viewport(bounds).visible_chunks(size);
+
+            if visible != state.chunks {
+                let needed: Vec<Chunk> = visible
+                    .iter()
+                    .filter(|chunk| state.chunks.binary_search(chunk).is_err())
+                    .copied()
+                    .collect();
+
+                state.chunks = visible;
+
+                let msg = self
+                    .program
+                    .on_region_needed(&mut state.state, &state.chunks, &needed);
+
+                if let Some(msg) = msg {
+                    shell.publish(msg);
+                }
+            }
+        }
+
+        if self.rebase_origin {
+            state.rebase();
+        }
+
+        status
+    }
+
+    fn operate(
+        &self,
+        state: &mut tree::Tree,
+        layout: layout::Layout<'_>,
+        _renderer: &Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+        let bounds = self.active_bounds(layout.bounds(), state);
+
+        state.controller.viewport = Some(state.viewport(bounds));
+
+        operation.focusable(state, self.id.as_ref());
+        operation.custom(&mut state.controller, self.id.as_ref());
+
+        let commands = std::mem::take(&mut state.controller.commands);
+
+        if commands.is_empty() {
+            return;
+        }
+
+        // Operations cannot publish messages, so those of the hooks are dropped.
+        // The viewport change is published with the next event instead.
+        let mut messages = Vec::new();
+        let mut shell = advanced::Shell::new(&mut messages);
+        let before = state.view();
+        let cursors = get_cursors(Cursor::Unavailable, bounds, before);
+
+        for command in commands {
+            self.execute(state, &mut shell, bounds, cursors, command);
+        }
+
+        state.view_commanded |= state.view() != before;
+        state.last_view_change = Some(Instant::now());
+        state.controller.viewport = Some(state.viewport(bounds));
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &tree::Tree,
+        layout: layout::Layout<'_>,
+        cursor: advanced::mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> advanced::mouse::Interaction {
+        let bounds = layout.bounds();
+        let state = &state.state.downcast_ref::<InfiniteState<P::State>>();
+
+        if let Some(split) = self.split {
+            let (_, _, divider) = split_bounds(bounds, split, state.split.ratio);
+
+            if state.split.dragging || cursor.is_over(divider.expand(DIVIDER_WIDTH)) {
+                return match split {
+                    Split::Horizontal => mouse::Interaction::ResizingHorizontally,
+                    Split::Vertical => mouse::Interaction::ResizingVertically,
+                };
+            }
+        }
+
+        if state.pan.is_some() {
+            return mouse::Interaction::Grabbing;
+        }
+
+        let cursor = state.pointer(cursor, self.program.captures_pointer(&state.state));
+        let bounds = self.active_bounds(bounds, state);
+
+        if self.rulers {
+            let guide = state.guide_drag.or_else(|| {
+                let position = cursor.position_over(bounds)?;
+                guide_at(
+                    &state.guides,
+                    &self.pointer_view(state).viewport(bounds),
+                    position,
+                )
+            });
+
+            match guide.map(|index| state.guides[index]) {
+                Some(Guide::Horizontal(_)) => return mouse::Interaction::ResizingVertically,
+                Some(Guide::Vertical(_)) => return mouse::Interaction::ResizingHorizontally,
+                None => {}
+            }
+        }
+        let cursors = get_cursors(cursor, bounds, self.pointer_view(state));
+        let context = InputContext::new(bounds, cursors, state.keyboard_modifier);
+
+        self.program.mouse_interaction(&state.state, context)
+    }
+
+    fn layout(
+        &self,
+        _tree: &mut iced::advanced::widget::Tree,
+        _renderer: &Renderer,
+        limits: &iced::advanced::layout::Limits,
+    ) -> layout::Node {
+        layout::atomic(limits, self.width, self.height)
+    }
+
+    fn draw(
+        &self,
+        tree: &iced::advanced::widget::Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &iced::advanced::renderer::Style,
+        layout: layout::Layout<'_>,
+        cursor: iced::advanced::mouse::Cursor,
+        _viewport: &iced::Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let outer = bounds;
+        let is_mouse_over = cursor.is_over(bounds);
+        let state = tree.state.downcast_ref::<InfiniteState<P::State>>();
+
+        if self.background_tessellation.is_some() {
+            *state.geometry.settled.borrow_mut() = Some(DrawnFrame {
+                bounds,
+                views: self.drawn_views(state),
+                caches: Vec::new(),
+            });
+        }
+
+        if bounds.width < 1.0 || bounds.height < 1.0 {
+            return;
+        }
+
+        let status = if is_mouse_over {
+            Status::Hovered
+        } else {
+            Status::Active
+        };
+
+        let style = theme.style(&self.style, status);
+
+        renderer.fill_quad(
+            advanced::renderer::Quad {
+                bounds,
+                border: style.border,
+                shadow: Shadow::default(),
+            },
+            style.background,
+        );
+
+        let border_width = style.border.width;
+
+        let bounds = {
+            let width = bounds.width - (2. * border_width);
+            let height = bounds.height - (2.0 * border_width);
+
+            let position = bounds.position();
+
+            let top_left = Point::new(position.x + border_width, position.y + border_width);
+
+            Rectangle::new(top_left, Size::new(width, height))
+        };
+
+        match self.split {
+            Some(split) => {
+                let (first, second, divider) = split_bounds(bounds, split, state.split.ratio);
+                let (first_view, second_view) = self.drawn_views(state);
+
+                let panes = [
+                    (Pane::First, first_view, first),
+                    (Pane::Second, second_view, second),
+                ];
+
+                for (pane, view, bounds) in panes {
+                    self.draw_pane(renderer, theme, &style, state, pane, view, bounds, cursor);
+                }
+
+                renderer.fill_quad(
+                    advanced::renderer::Quad {
+                        bounds: divider,
+                        border: Border::default(),
+                        shadow: Shadow::default(),
+                    },
+                    style.divider,
+                );
+            }
+            None => {
+                let view = state.camera_view(self.camera_motion);
+                self.draw_pane(
+                    renderer,
+                    theme,
+                    &style,
+                    state,
+                    Pane::First,
+                    view,
+                    bounds,
+                    cursor,
+                )
+            }
+        }
+
+        if !state.geometry.pending.borrow().is_empty() {
+            *state.geometry.settled.borrow_mut() = None;
+        }
+
+        if let Some((start, end)) = state.zoom_box {
+            renderer.fill_quad(
+                advanced::renderer::Quad {
+                    bounds: rectangle_between(start, end),
+                    border: Border::default().width(1.0).color(style.zoom_box),
+                    shadow: Shadow::default(),
+                },
+                style.zoom_box.scale_alpha(0.2),
+            );
+        }
+
+        if state.focused && self.keyboard_scope == KeyboardScope::Focused {
+            renderer.fill_quad(
+                advanced::renderer::Quad {
+                    bounds: outer,
+                    border: Border {
+                        color: style.focus_ring,
+                        width: FOCUS_RING_WIDTH,
+                        radius: style.border.radius,
+                    },
+                    shadow: Shadow::default(),
+                },
+                Color::TRANSPARENT,
+            );
+        }
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        state: &'b mut tree::Tree,
+        layout: layout::Layout<'_>,
+        _renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<advanced::overlay::Element<'b, Message, Theme, Renderer>> {
+        let state = state.state.downcast_mut::<InfiniteState<P::State>>();
+        let bounds = self.active_bounds(layout.bounds(), state);
+
+        let viewport = self.pointer_view(state).viewport(bounds);
+
+        let menu = state.menu.open.and_then(|(position, point)| {
+            let entries = self.program.context_menu(&state.state, point);
+
+            (!entries.is_empty()).then(|| {
+                (
+                    bounds.position() + Vector::new(position.x, position.y),
+                    entries,
+                )
+            })
+        });
+
+        let tooltip = state
+            .screen_cursor
+            .filter(|_| state.tooltip.shown && menu.is_none())
+            .and_then(|cursor| {
+                let point = translate_cursor(bounds, self.pointer_view(state), cursor);
+                let tooltip = self.program.tooltip(&state.state, point)?;
+
+                let position = match tooltip.anchor {
+                    Some(anchor) => (viewport.to_screen(anchor), true),
+                    None => (cursor + TOOLTIP_OFFSET, false),
+                };
+
+                Some((tooltip.content, position))
+            });
+
+        let context = OverlayContext {
+            bounds,
+            infinite_cursor: state.mouse_position.unwrap_or_default(),
+            viewport,
+            translation,
+        };
+        let mut overlays = self.program.overlays(&mut state.state, context);
+
+        if let Some((content, (position, above))) = tooltip {
+            overlays.push(advanced::overlay::Element::new(Box::new(TooltipOverlay {
+                content,
+                position: position + translation,
+                above,
+                class: &self.style,
+            })));
+        }
+
+        match menu {
+            Some((position, entries)) => {
+                overlays.push(advanced::overlay::Element::new(Box::new(ContextMenu {
+                    entries,
+                    position: position + translation,
+                    state: &mut state.menu,
+                    class: &self.style,
+                })))
+            }
+            None => state.menu.open = None,
+        }
+
+        match overlays.len() {
+            0 => None,
+            1 => overlays.pop(),
+            _ => Some(advanced::overlay::Group::with_children(overlays).overlay()),
+        }
+    }
+}
+
+impl<'a, P, Message, Theme, Renderer> From<Infinite<'a, P, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: Catalog + 'a,
+    P: Program<Message, Theme, Renderer> + 'a,
+    Renderer: geometry::Renderer + TileRenderer + 'static,
+    Renderer::Frame: Send,
+    <Renderer::Geometry as Cached>::Cache: 'static,
+{
+    fn from(value: Infinite<'a, P, Message, Theme, Renderer>) -> Self {
+        Element::new(value)
+    }
+}
+
+/// The shown tooltip of an [`Infinite`], see [`Program::tooltip`].
+struct TooltipOverlay<'a, 'b, Theme: Catalog> {
+    content: String,
+    /// The point the tooltip is placed at.
+    position: Point,
+    /// Whether the tooltip is centered above its point rather than placed to
+    /// the bottom-right of it.
+    above: bool,
+    class: &'a <Theme as Catalog>::Class<'b>,
+}
+
+impl<Message, Theme, Renderer> advanced::Overlay<Message, Theme, Renderer>
+    for TooltipOverlay<'_, '_, Theme>
+where
+    Theme: Catalog,
+    Renderer: geometry::Renderer,
+{
+    fn layout(&mut self, _renderer: &Renderer, bounds: Size) -> layout::Node {
+        let text = min_text_bounds(&self.content, Size::INFINITY, TOOLTIP_TEXT_SIZE);
+        let size = Size::new(text.width + 2.0 * MENU_PADDING, text.height + MENU_PADDING);
+
+        let position = match self.above {
+            true => Point::new(
+                self.position.x - size.width / 2.0,
+                self.position.y - size.height - MENU_PADDING,
+            ),
+            false => self.position,
+        };
+
+        // Kept within the window
+        let position = Point::new(
+            position.x.min(bounds.width - size.width).max(0.0),
+            position.y.min(bounds.height - size.height).max(0.0),
+        );
+
+        layout::Node::new(size).move_to(position)
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &advanced::renderer::Style,
+        layout: layout::Layout<'_>,
+        _cursor: Cursor,
+    ) {
+        let bounds = layout.bounds();
+        let style = theme.style(self.class, Status::Active);
+
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        frame.fill(
+            &Path::rounded_rectangle(Point::ORIGIN, bounds.size(), style.details_border_radius),
+            style.details_background,
+        );
+
+        frame.fill_text(Text {
+            content: self.content.clone(),
+            position: Point::new(MENU_PADDING, MENU_PADDING / 2.0),
+            color: style.details_text,
+            size: TOOLTIP_TEXT_SIZE.into(),
+            ..Default::default()
+        });
+
+        renderer.with_translation(Vector::new(bounds.x, bounds.y), |renderer| {
+            renderer.draw_geometry(frame.into_geometry())
+        });
+    }
+
+    // Tooltips let the cursor through to the canvas beneath them
+    fn is_over(
+        &self,
+        _layout: layout::Layout<'_>,
+        _renderer: &Renderer,
+        _cursor_position: Point,
+    ) -> bool {
+        false
+    }
+}
+
+/// The open context menu of an [`Infinite`], see [`Program::context_menu`].
+struct ContextMenu<'a, 'b, Message, Theme: Catalog> {
+    entries: Vec<MenuEntry<Message>>,
+    /// The position of the top-left corner of the menu, before it is moved
+    /// to fit the window.
+    position: Point,
+    state: &'a mut MenuState,
+    class: &'a <Theme as Catalog>::Class<'b>,
+}
+
+impl<Message, Theme: Catalog> ContextMenu<'_, '_, Message, Theme> {
+    /// Returns the height of an entry.
+    fn entry_height() -> f32 {
+        min_text_bounds("", Size::INFINITY, MENU_TEXT_SIZE).height + MENU_PADDING
+    }
+
+    /// Returns the index of the entry under the `cursor`.
+    fn entry_at(&self, bounds: Rectangle, cursor: Point) -> Option<usize> {
+        if !bounds.contains(cursor) {
+            return None;
+        }
+
+        let index = ((cursor.y - bounds.y - MENU_PADDING / 2.0) / Self::entry_height()).floor();
+
+        (index >= 0.0 && (index as usize) < self.entries.len()).then_some(index as usize)
+    }
+}
+
+impl<Message, Theme, Renderer> advanced::Overlay<Message, Theme, Renderer>
+    for ContextMenu<'_, '_, Message, Theme>
+where
+    Theme: Catalog,
+    Renderer: geometry::Renderer,
+{
+    fn layout(&mut self, _renderer: &Renderer, bounds: Size) -> layout::Node {
+        let width = self
+            .entries
+            .iter()
+            .map(|entry| min_text_bounds(&entry.label, Size::INFINITY, MENU_TEXT_SIZE).width)
+            .fold(0.0, f32::max);
+
+        let size = Size::new(
+            width + 2.0 * MENU_PADDING,
+            self.entries.len() as f32 * Self::entry_height() + MENU_PADDING,
+        );
+
+        // Menus near the edges open towards the inside of the window
+        let x = if self.position.x + size.width > bounds.width {
+            self.position.x - size.width
+        } else {
+            self.position.x
+        };
+        let y = if self.position.y + size.height > bounds.height {
+            self.position.y - size.height
+        } else {
+            self.position.y
+        };
+
+        layout::Node::new(size).move_to(Point::new(x.max(0.0), y.max(0.0)))
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &advanced::renderer::Style,
+        layout: layout::Layout<'_>,
+        cursor: Cursor,
+    ) {
+        let bounds = layout.bounds();
+        let style = theme.style(self.class, Status::Active);
+        let height = Self::entry_height();
+        let hovered = cursor
+            .position()
+            .and_then(|cursor| self.entry_at(bounds, cursor));
+
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        frame.fill(
+            &Path::rounded_rectangle(Point::ORIGIN, bounds.size(), style.details_border_radius),
+            style.details_background,
+        );
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            let top = MENU_PADDING / 2.0 + index as f32 * height;
+
+            if hovered == Some(index) {
+                frame.fill_rectangle(
+                    Point::new(0.0, top),
+                    Size::new(bounds.width, height),
+                    style.details_text.scale_alpha(0.15),
+                );
+            }
+
+            frame.fill_text(Text {
+                content: entry.label.clone(),
+                position: Point::new(MENU_PADDING, top + MENU_PADDING / 2.0),
+                color: style.details_text,
+                size: MENU_TEXT_SIZE.into(),
+                ..Default::default()
+            });
+        }
+
+        renderer.with_translation(Vector::new(bounds.x, bounds.y), |renderer| {
+            renderer.draw_geometry(frame.into_geometry())
+        });
+    }
+
+    fn on_event(
+        &mut self,
+        event: iced::Event,
+        layout: layout::Layout<'_>,
+        cursor: Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn advanced::Clipboard,
+        shell: &mut advanced::Shell<'_, Message>,
+    ) -> iced_event::Status {
+        let bounds = layout.bounds();
+        let position = match event {
+            iced::Event::Touch(
+                touch::Event::FingerPressed { position, .. }
+                | touch::Event::FingerLifted { position, .. },
+            ) => Some(position),
+            _ => cursor.position(),
+        };
+        let entry = position.and_then(|position| self.entry_at(bounds, position));
+
+        match event {
+            iced::Event::Mouse(mouse::Event::ButtonPressed(_))
+            | iced::Event::Touch(touch::Event::FingerPressed { .. }) => {
+                if position.is_some_and(|position| bounds.contains(position)) {
+                    self.state.pressed = entry;
+                    return iced_event::Status::Captured;
+                }
+
+                // Pressing elsewhere closes the menu
+                self.state.open = None;
+            }
+            iced::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+            | iced::Event::Touch(touch::Event::FingerLifted { .. }) => {
+                let pressed = self.state.pressed.take();
+
+                if let Some(index) = pressed.filter(|index| entry == Some(*index)) {
+                    self.state.open = None;
+                    shell.publish(self.entries.swap_remove(index).message);
+
+                    return iced_event::Status::Captured;
+                }
+            }
+            iced::Event::Mouse(mouse::Event::WheelScrolled { .. }) if cursor.is_over(bounds) => {
+                return iced_event::Status::Captured;
+            }
+            iced::Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(keyboard::key::Named::Escape),
+                ..
+            }) => {
+                self.state.open = None;
+                return iced_event::Status::Captured;
+            }
+            _ => {}
+        }
+
+        iced_event::Status::Ignored
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: layout::Layout<'_>,
+        cursor: Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let over = cursor
+            .position()
+            .and_then(|cursor| self.entry_at(layout.bounds(), cursor));
+
+        match over {
+            Some(_) => mouse::Interaction::Pointer,
+            None => mouse::Interaction::default(),
+        }
+    }
+}
+
+/// The items with an id under the cursor, see [`Buffer::item_id`].
+#[derive(Debug, Clone, Default)]
+struct PickingState {
+    /// The areas of the items drawn on the last frame of each pane, from the
+    /// bottom-most to the top-most.
+    hits: RefCell<HashMap<Pane, Vec<Hit>>>,
+    /// The item last hovered.
+    hovered: Option<u64>,
+    /// The item the left button was pressed on, clicked once released over
+    /// it.
+    pressed: Option<u64>,
+    /// The last position of the cursor on the [`Infinite`].
+    position: Point,
+}
+
+/// The context menu of an [`Infinite`], see [`Program::context_menu`].
+#[derive(Debug, Clone, Copy, Default)]
+struct MenuState {
+    /// The point of the pane, from its top-left corner, and the point on the
+    /// [`Infinite`] the open menu was opened at.
+    open: Option<(Point, Point)>,
+    /// The entry of the open menu pressed on, chosen once released over it.
+    pressed: Option<usize>,
+    /// The finger held down which may open the menu, with where and when it
+    /// was pressed.
+    press: Option<(touch::Finger, Point, Instant)>,
+}
+
+/// The tooltip of an [`Infinite`], see [`Program::tooltip`].
+#[derive(Debug, Clone, Copy, Default)]
+struct TooltipState {
+    /// The time the cursor came to rest over a point with a tooltip.
+    since: Option<Instant>,
+    /// Whether the tooltip is shown.
+    shown: bool,
+}
+
+#[derive(Debug, Clone)]
+struct InfiniteState<State> {
+    /// The scroll, relative to the `origin`.
+    offset: Vector,
+    /// The point of the canvas the view is scrolled from, see
+    /// [`Infinite::rebase_origin`].
+    origin: Point<f64>,
+    /// The natural logarithms of the zoom factors of the X and Y axes.
+    scale_level: Vector,
+    /// The zoom factors of the X and Y axes.
+    scale: Vector,
+    /// The counter-clockwise rotation, in radians.
+    rotation: f32,
+    /// The direction of the Y axis, kept in sync with the [`Infinite`].
+    y_axis: YDirection,
+    keyboard_modifier: keyboard::Modifiers,
+    /// Whether the [`Infinite`] was the last widget pressed on.
+    focused: bool,
+    /// The last position of the cursor while drag panning.
+    pan: Option<Point>,
+    /// The start and end of the zoom box being dragged out.
+    zoom_box: Option<(Point, Point)>,
+    guides: Vec<Guide>,
+    /// The index of the guide being dragged.
+    guide_drag: Option<usize>,
+    /// The time the scroll or zoom last changed.
+    last_view_change: Option<Instant>,
+    /// Whether a [`Command`] moved the view since the last event.
+    view_commanded: bool,
+    /// The positions of at most two fingers touching the [`Infinite`].
+    fingers: Vec<(touch::Finger, Point)>,
+    state: State,
+    /// The virtual position of the cursor
+    mouse_position: Option<Point>,
+    /// The last position of the cursor on the screen.
+    screen_cursor: Option<Point>,
+    /// The position and time of the last press which could start a double
+    /// click.
+    last_press: Option<(Point, Instant)>,
+    /// The time of the last scroll of an ongoing scroll gesture.
+    scroll_gesture: Option<Instant>,
+    /// The time of the last zoom of an ongoing zoom gesture.
+    zoom_gesture: Option<Instant>,
+    split: SplitState,
+    spotlight: SpotlightState,
+    camera: CameraState,
+    /// The release of an overscroll, and the last frame it was applied on.
+    overscroll: Option<(Animated<Vector>, Instant)>,
+    /// Whether the [`Program`] captured the press of an ongoing drag.
+    program_drag: bool,
+    /// The last frame the [`Infinite`] scrolled towards a border on.
+    edge_pan_frame: Option<Instant>,
+    /// The last chunks reported as visible, see [`Infinite::chunks`].
+    chunks: Vec<Chunk>,
+    picking: PickingState,
+    menu: MenuState,
+    tooltip: TooltipState,
+    controller: Controller,
+    geometry: GeometryCaches,
+}
+
+/// What a frame of a pane drew, shown by [`Infinite::debug_overlay`].
+#[derive(Debug, Clone, Copy, Default)]
+struct FrameStats {
+    buffers: usize,
+    paths: usize,
+    texts: usize,
+    /// The items left out for their zoom range or the frame budget.
+    culled: usize,
+    /// Whether the geometry of the last frame was drawn again, see
+    /// [`Program::content_version`].
+    reused: bool,
+}
+
+impl FrameStats {
+    /// Counts the items of a drawn `buffer`.
+    fn add(&mut self, buffer: &Buffer) {
+        self.buffers += 1;
+
+        for (_, _, item) in &buffer.items {
+            match item {
+                Item::Fill(..)
+                | Item::Stroke(..)
+                | Item::MarchingAnts(..)
+                | Item::Pattern(..)
+                | Item::ScreenRectangle(..) => self.paths += 1,
+                Item::Text(..)
+                | Item::RotatedText(..)
+                | Item::TextBox(..)
+                | Item::RichText(..)
+                | Item::Lines(..) => self.texts += 1,
+                Item::Label(..) | Item::Badge(..) => {
+                    self.paths += 1;
+                    self.texts += 1;
+                }
+            }
+        }
+    }
+}
+
+/// The settings items of a [`Buffer`] are drawn with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RenderContext {
+    view: View,
+    center: Point,
+    antialiasing: Antialiasing,
+}
+
+/// The scroll and zoom an [`Infinite`] pane is drawn with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct View {
+    /// The scroll, relative to the `origin`.
+    offset: Vector,
+    origin: Point<f64>,
+    scale_level: Vector,
+    scale: Vector,
+    /// The counter-clockwise rotation, in radians.
+    rotation: f32,
+    y_axis: YDirection,
+}
+
+impl View {
+    /// Returns the [`Viewport`] of a pane drawn with the view, which has the
+    /// given `bounds`.
+    fn viewport(self, bounds: Rectangle) -> Viewport {
+        Viewport {
+            offset: self.scroll(),
+            scale: self.scale,
+            rotation: Radians(self.rotation),
+            y_axis: self.y_axis,
+            bounds,
+        }
+    }
+
+    /// Returns the scroll of the view, like that of [`Program::init_scroll`].
+    fn scroll(&self) -> Vector {
+        let origin = self.to_screen(self.origin);
+
+        Vector::new(
+            (f64::from(self.offset.x) + origin.x) as f32,
+            (f64::from(self.offset.y) + origin.y) as f32,
+        )
+    }
+
+    /// Returns the same view, with its scroll relative to `origin`.
+    fn rebased(self, origin: Point<f64>) -> Self {
+        let diff = self.to_screen(Point::new(
+            self.origin.x - origin.x,
+            self.origin.y - origin.y,
+        ));
+
+        Self {
+            offset: Vector::new(
+                (f64::from(self.offset.x) + diff.x) as f32,
+                (f64::from(self.offset.y) + diff.y) as f32,
+            ),
+            origin,
+            ..self
+        }
+    }
+
+    /// Converts a `vector` on the screen to one on the canvas.
+    fn to_canvas(self, vector: Vector) -> Vector {
+        let vector = rotate(vector, -self.rotation);
+
+        Vector::new(
+            vector.x / self.scale.x,
+            self.y_axis.sign() * vector.y / self.scale.y,
+        )
+    }
+
+    /// Converts a `point` on the canvas to its distance on the screen from
+    /// the point at the origin.
+    fn to_screen(self, point: Point<f64>) -> Vector<f64> {
+        let (sin, cos) = f64::from(self.rotation).sin_cos();
+        let x = point.x * f64::from(self.scale.x);
+        let y = f64::from(self.y_axis.sign()) * point.y * f64::from(self.scale.y);
+
+        Vector::new(x * cos + y * sin, y * cos - x * sin)
+    }
+}
+
+/// One of the two panes of a split [`Infinite`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+enum Pane {
+    #[default]
+    First,
+    Second,
+}
+
+/// A continuous interaction with an [`Infinite`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Gesture {
+    Scroll,
+    Zoom,
+}
+
+/// An action triggered by one of the [`KeyBindings`] of an [`Infinite`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum KeyAction {
+    PanUp,
+    PanDown,
+    PanLeft,
+    PanRight,
+    PageUp,
+    PageDown,
+    PageLeft,
+    PageRight,
+    ZoomIn,
+    ZoomOut,
+    ZoomInOrigin,
+    ZoomOutOrigin,
+    NextPreset,
+    PreviousPreset,
+    ResetScroll,
+    ResetZoom,
+    ResetAll,
+}
+
+/// An action requested of an [`Infinite`] by an application.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Command {
+    CycleZoom {
+        forward: bool,
+    },
+    ZoomToPreset(usize),
+    /// Pans by a fraction of the visible canvas, with positive Y upwards.
+    PanByFraction(Vector),
+    /// Scrolls to an offset, like that of [`Program::init_scroll`].
+    ScrollTo(Vector),
+    /// Zooms to a zoom factor while keeping the center of the view fixed.
+    ZoomTo(f32),
+    /// Rotates to an angle around the center of the view.
+    RotateTo(Radians),
+    /// Resets both the scroll and the zoom.
+    Reset,
+    /// Zooms, rotates and then scrolls to those of a [`Viewport`].
+    Restore(Viewport),
+    /// Fits a world region, or the [`Program::content_bounds`] if none is
+    /// given, within the view with some padding on the screen.
+    Fit {
+        region: Option<Rectangle>,
+        padding: f32,
+    },
+}
+
+/// The [`Command`]s sent to an [`Infinite`] through widget operations.
+///
+/// [`Command`]s are executed as soon as the operation reaches the
+/// [`Infinite`]. The [`Program`] hooks they trigger are called, but the
+/// messages they return are not published, as operations cannot publish
+/// messages. The viewport change is published with the next event through
+/// [`Program::on_viewport_change`].
+#[derive(Debug, Clone, Default)]
+struct Controller {
+    commands: Vec<Command>,
+    /// The [`Viewport`] of the [`Infinite`] as of the last operation.
+    viewport: Option<Viewport>,
+}
+
+/// A widget [`Operation`] sending a [`Command`] to an [`Infinite`].
+struct Control {
+    target: Id,
+    command: Command,
+}
+
+impl<T> Operation<T> for Control {
+    fn container(
+        &mut self,
+        _id: Option<&Id>,
+        _bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+    ) {
+        operate_on_children(self)
+    }
+
+    fn custom(&mut self, state: &mut dyn Any, id: Option<&Id>) {
+        if id != Some(&self.target) {
+            return;
+        }
+
+        if let Some(controller) = state.downcast_mut::<Controller>() {
+            controller.commands.push(self.command);
+        }
+    }
+
+    fn finish(&self) -> operation::Outcome<T> {
+        operation::Outcome::None
+    }
+}
+
+/// A widget [`Operation`] reading the [`Viewport`] of an [`Infinite`].
+struct Query {
+    target: Id,
+    viewport: Option<Viewport>,
+}
+
+impl Operation<Viewport> for Query {
+    fn container(
+        &mut self,
+        _id: Option<&Id>,
+        _bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<Viewport>),
+    ) {
+        operate_on_children(self)
+    }
+
+    fn custom(&mut self, state: &mut dyn Any, id: Option<&Id>) {
+        if id != Some(&self.target) {
+            return;
+        }
+
+        if let Some(controller) = state.downcast_ref::<Controller>() {
+            self.viewport = controller.viewport;
+        }
+    }
+
+    fn finish(&self) -> operation::Outcome<Viewport> {
+        match self.viewport {
+            Some(viewport) => operation::Outcome::Some(viewport),
+            None => operation::Outcome::None,
+        }
+    }
+}
+
+/// Returns the current [`Viewport`] of the [`Infinite`] with the given [`Id`].
+///
+/// The [`Task`] produces nothing if there is no such [`Infinite`]. The
+/// [`Viewport`] can be used to persist the scroll and zoom or to display them
+/// elsewhere in an application.
+pub fn viewport(id: Id) -> Task<Viewport> {
+    advanced::widget::operate(Query {
+        target: id,
+        viewport: None,
+    })
+}
+
+/// Sends a [`Command`] to the [`Infinite`] with the given [`Id`].
+fn control<T>(id: Id, command: Command) -> Task<T>
+where
+    T: Send + 'static,
+{
+    advanced::widget::operate(Control {
+        target: id,
+        command,
+    })
+}
+
+/// Zooms the [`Infinite`] with the given [`Id`] to its next zoom preset.
+///
+/// The zoom wraps around to the smallest preset after the largest.
+pub fn next_zoom_preset<T>(id: Id) -> Task<T>
+where
+    T: Send + 'static,
+{
+    control(id, Command::CycleZoom { forward: true })
+}
+
+/// Zooms the [`Infinite`] with the given [`Id`] to its previous zoom preset.
+///
+/// The zoom wraps around to the largest preset before the smallest.
+pub fn previous_zoom_preset<T>(id: Id) -> Task<T>
+where
+    T: Send + 'static,
+{
+    control(id, Command::CycleZoom { forward: false })
+}
+
+/// Zooms the [`Infinite`] with the given [`Id`] to the zoom preset at `index`.
+///
+/// Nothing happens if there is no preset at `index`. See
+/// [`Infinite::zoom_presets`].
+pub fn zoom_to_preset<T>(id: Id, index: usize) -> Task<T>
+where
+    T: Send + 'static,
+{
+    control(id, Command::ZoomToPreset(index))
+}
+
+/// Pans the [`Infinite`] with the given [`Id`] by a fraction of its visible
+/// canvas.
+///
+/// A `fraction` of `(0.5, 0.0)` pans right by half of the visible width while
+/// `(0.0, 1.0)` pans up by the whole visible height, regardless of the zoom.
+pub fn pan_by_fraction<T>(id: Id, fraction: Vector) -> Task<T>
+where
+    T: Send + 'static,
+{
+    control(id, Command::PanByFraction(fraction))
+}
+
+/// Scrolls the [`Infinite`] with the given [`Id`] to `scroll`.
+///
+/// The scroll is given like [`Program::init_scroll`] and is kept within the
+/// [`Infinite::scroll_bounds`].
+pub fn scroll_to<T>(id: Id, scroll: Vector) -> Task<T>
+where
+    T: Send + 'static,
+{
+    control(id, Command::ScrollTo(scroll))
+}
+
+/// Zooms the [`Infinite`] with the given [`Id`] to `zoom` while keeping the
+/// center of its view fixed.
+///
+/// A `zoom` of 1.0 is the unzoomed canvas, like a zoom preset. Zooms which
+/// are not positive are ignored.
+pub fn zoom_to<T>(id: Id, zoom: f32) -> Task<T>
+where
+    T: Send + 'static,
+{
+    control(id, Command::ZoomTo(zoom))
+}
+
+/// Resets both the scroll and the zoom of the [`Infinite`] with the given
+/// [`Id`], like Cmd(Ctrl) + Home.
+pub fn reset<T>(id: Id) -> Task<T>
+where
+    T: Send + 'static,
+{
+    control(id, Command::Reset)
+}
+
+/// Rotates the [`Infinite`] with the given [`Id`] counter-clockwise to
+/// `angle` around the center of its view.
+///
+/// See [`Infinite::rotate`] for rotating it by touch.
+pub fn rotate_to<T>(id: Id, angle: impl Into<Radians>) -> Task<T>
+where
+    T: Send + 'static,
+{
+    control(id, Command::RotateTo(angle.into()))
+}
+
+/// Restores the scroll, zoom and rotation of the [`Infinite`] with the given
+/// [`Id`] to those of a [`Viewport`], like one saved from [`viewport`].
+///
+/// The bounds of the [`Viewport`] are ignored. Viewports with a zoom which is
+/// not positive are ignored.
+pub fn restore<T>(id: Id, viewport: Viewport) -> Task<T>
+where
+    T: Send + 'static,
+{
+    control(id, Command::Restore(viewport))
+}
+
+/// Scrolls and zooms the [`Infinite`] with the given [`Id`] so the world
+/// `region`, given by its bottom-left corner, fills its view.
+///
+/// `padding` is the space kept on each side of the `region`, in screen
+/// pixels.
+pub fn fit_to<T>(id: Id, region: Rectangle, padding: f32) -> Task<T>
+where
+    T: Send + 'static,
+{
+    control(
+        id,
+        Command::Fit {
+            region: Some(region),
+            padding,
+        },
+    )
+}
+
+/// Scrolls and zooms the [`Infinite`] with the given [`Id`] so the
+/// [`Program::content_bounds`] fill its view.
+///
+/// `padding` is the space kept on each side of the contents, in screen
+/// pixels. Nothing happens if the [`Program`] has no content bounds.
+pub fn fit_content<T>(id: Id, padding: f32) -> Task<T>
+where
+    T: Send + 'static,
+{
+    control(
+        id,
+        Command::Fit {
+            region: None,
+            padding,
+        },
+    )
+}
+
+/// Returns the zoom preset after or before `scale`, wrapping around.
+fn cycle_preset(presets: &[f32], scale: f32, forward: bool) -> Option<f32> {
+    let level = scale.ln();
+
+    if forward {
+        presets
+            .iter()
+            .find(|preset| preset.ln() > level + PRESET_EPSILON)
+            .or(presets.first())
+            .copied()
+    } else {
+        presets
+            .iter()
+            .rev()
+            .find(|preset| preset.ln() < level - PRESET_EPSILON)
+            .or(presets.last())
+            .copied()
+    }
+}
+
+/// The view an [`Infinite`] is drawn with while it moves towards its scroll
+/// and zoom.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CameraState {
+    view: View,
+    /// The start, end and start time of an eased movement.
+    eased: Option<(View, View, Instant)>,
+}
+
+impl CameraState {
+    /// The distance under which a moved target is taken to be the same.
+    const EPSILON: f32 = 0.01;
+    /// The longest time step taken in a single frame.
+    const MAX_STEP: Duration = Duration::from_millis(100);
+
+    fn new(view: View) -> Self {
+        Self { view, eased: None }
+    }
+
+    /// Moves the camera towards `target` over a fixed `duration` with the
+    /// given [`Easing`].
+    ///
+    /// Returns true if the camera is still moving.
+    fn ease(&mut self, target: View, duration: Duration, easing: Easing, now: Instant) -> bool {
+        self.rebase(target);
+
+        if self.eased.is_none_or(|(_, to, _)| to != target) {
+            if self.view == target {
+                return false;
+            }
+
+            self.eased = Some((self.view, target, now));
+        }
+
+        let Some((from, to, started)) = self.eased else {
+            return false;
+        };
+
+        let t = if duration.is_zero() {
+            1.0
+        } else {
+            now.saturating_duration_since(started).as_secs_f32() / duration.as_secs_f32()
+        };
+
+        if t >= 1.0 {
+            *self = Self::new(to);
+            return false;
+        }
+
+        let t = easing.apply(t);
+        let level = from.scale_level + (to.scale_level - from.scale_level) * t;
+
+        self.view = View {
+            offset: from.offset + (to.offset - from.offset) * t,
+            origin: to.origin,
+            scale_level: level,
+            scale: scales(level),
+            rotation: from.rotation + (to.rotation - from.rotation) * t,
+            y_axis: to.y_axis,
+        };
+
+        true
+    }
+
+    /// Makes the camera scroll relative to the origin of `target`.
+    fn rebase(&mut self, target: View) {
+        if self.view.origin == target.origin {
+            return;
+        }
+
+        self.view = self.view.rebased(target.origin);
+
+        if let Some((from, to, _)) = &mut self.eased {
+            *from = from.rebased(target.origin);
+            *to = to.rebased(target.origin);
+
+            // Keeps easing towards a target which only moved its origin
+            let moved = to.offset - target.offset;
+            if moved.x.abs() < Self::EPSILON && moved.y.abs() < Self::EPSILON {
+                to.offset = target.offset;
+            }
+        }
+    }
+}
+
+/// Returns the zoom factors of the given scale levels.
+fn scales(level: Vector) -> Vector {
+    Vector::new(E.powf(level.x), E.powf(level.y))
+}
+
+#[derive(Debug, Clone, Default)]
+struct SpotlightState {
+    /// The most recent [`Spotlight`], kept around while fading out.
+    spotlight: Option<Spotlight>,
+    /// How far the dimming has faded in, from 0.0 to 1.0.
+    progress: f32,
+    last_frame: Option<Instant>,
+}
+
+impl SpotlightState {
+    /// Advances the fade towards the `target` [`Spotlight`].
+    ///
+    /// Returns true if the fade is still in progress.
+    fn animate(&mut self, target: Option<Spotlight>, now: Instant) -> bool {
+        let goal = if target.is_some() { 1.0 } else { 0.0 };
+
+        if target.is_some() {
+            self.spotlight = target;
+        }
+
+        let Some(spotlight) = &self.spotlight else {
+            return false;
+        };
+
+        let elapsed = self
+            .last_frame
+            .map(|last| now.saturating_duration_since(last))
+            .unwrap_or_default();
+
+        let step = if spotlight.duration.is_zero() {
+            1.0
+        } else {
+            elapsed.as_secs_f32() / spotlight.duration.as_secs_f32()
+        };
+
+        self.progress = if goal > self.progress {
+            (self.progress + step).min(goal)
+        } else {
+            (self.progress - step).max(goal)
+        };
+
+        if self.progress == goal {
+            self.last_frame = None;
+
+            if goal == 0.0 {
+                self.spotlight = None;
+            }
+
+            false
+        } else {
+            self.last_frame = Some(now);
+            true
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SplitState {
+    /// The fraction of the [`Infinite`] taken up by the first pane.
+    ratio: f32,
+    /// Whether the divider is being dragged.
+    dragging: bool,
+    /// The pane receiving events.
+    active: Pane,
+    /// The view of the pane not receiving events.
+    inactive: View,
+}
+
+impl<State> operation::Focusable for InfiniteState<State> {
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+impl<State> InfiniteState<State> {
+    fn new(state: State) -> Self {
+        let scale_level = Vector::ZERO;
+        let scale = scales(scale_level);
+        Self {
+            offset: Vector::new(0., 0.),
+            origin: Point::new(0.0, 0.0),
+            scale_level,
+            state,
+            scale,
+            rotation: 0.0,
+            y_axis: YDirection::default(),
+            keyboard_modifier: keyboard::Modifiers::default(),
+            focused: false,
+            pan: None,
+            zoom_box: None,
+            guides: Vec::new(),
+            guide_drag: None,
+            last_view_change: None,
+            view_commanded: false,
+            fingers: Vec::new(),
+            mouse_position: None,
+            screen_cursor: None,
+            last_press: None,
+            scroll_gesture: None,
+            zoom_gesture: None,
+            split: SplitState {
+                ratio: 0.5,
+                dragging: false,
+                active: Pane::default(),
+                inactive: View {
+                    offset: Vector::new(0., 0.),
+                    origin: Point::new(0.0, 0.0),
+                    scale_level,
+                    scale,
+                    rotation: 0.0,
+                    y_axis: YDirection::default(),
+                },
+            },
+            spotlight: SpotlightState::default(),
+            menu: MenuState::default(),
+            tooltip: TooltipState::default(),
+            camera: CameraState::new(View {
+                offset: Vector::new(0., 0.),
+                origin: Point::new(0.0, 0.0),
+                scale_level,
+                scale,
+                rotation: 0.0,
+                y_axis: YDirection::default(),
+            }),
+            overscroll: None,
+            program_drag: false,
+            edge_pan_frame: None,
+            chunks: Vec::new(),
+            picking: PickingState::default(),
+            controller: Controller::default(),
+            geometry: GeometryCaches::default(),
+        }
+    }
+
+    fn view(&self) -> View {
+        View {
+            offset: self.offset,
+            origin: self.origin,
+            scale_level: self.scale_level,
+            scale: self.scale,
+            rotation: self.rotation,
+            y_axis: self.y_axis,
+        }
+    }
+
+    fn set_view(&mut self, view: View) {
+        self.offset = view.offset;
+        self.origin = view.origin;
+        self.scale_level = view.scale_level;
+        self.scale = view.scale;
+        self.rotation = view.rotation;
+    }
+
+    /// Sets the direction of the Y axis of every view.
+    fn set_y_axis(&mut self, y_axis: YDirection) {
+        self.y_axis = y_axis;
+        self.split.inactive.y_axis = y_axis;
+        self.camera.view.y_axis = y_axis;
+
+        if let Some((from, to, _)) = &mut self.camera.eased {
+            from.y_axis = y_axis;
+            to.y_axis = y_axis;
+        }
+    }
+
+    /// Makes `pane` the pane receiving events, swapping the views of the
+    /// panes if necessary.
+    fn activate_pane(&mut self, pane: Pane, linked: bool) {
+        if self.split.active == pane {
+            return;
+        }
+
+        let current = self.view();
+
+        if !linked {
+            self.set_view(self.split.inactive);
+        }
+
+        self.split.inactive = current;
+        self.split.active = pane;
+        self.mouse_position = None;
+        self.camera = CameraState::new(self.view());
+    }
+
+    /// Returns the view the active pane is drawn with.
+    fn camera_view(&self, motion: CameraMotion) -> View {
+        match motion {
+            CameraMotion::Immediate => self.view(),
+            CameraMotion::Spring { .. } | CameraMotion::Eased { .. } => self.camera.view,
+        }
+    }
+
+    /// Moves the camera towards the current scroll and zoom.
+    ///
+    /// Returns true if the camera is still moving.
+    fn follow_camera(&mut self, motion: CameraMotion, now: Instant) -> bool {
+        let target = self.view();
+
+        match motion {
+            CameraMotion::Immediate => {
+                self.camera = CameraState::new(target);
+                false
+            }
+            CameraMotion::Spring { stiffness } => {
+                // The swings of an Easing::Spring shrink by e^-6 over the duration
+                let duration = Duration::try_from_secs_f32(6.0 / stiffness).unwrap_or_default();
+                self.camera.ease(target, duration, Easing::Spring, now)
+            }
+            CameraMotion::Eased { duration, easing } => {
+                self.camera.ease(target, duration, easing, now)
+            }
+        }
+    }
+
+    /// Returns the views of the first and second panes respectively.
+    fn pane_views(&self, linked: bool, motion: CameraMotion) -> (View, View) {
+        let active = self.camera_view(motion);
+        let inactive = if linked { active } else { self.split.inactive };
+
+        match self.split.active {
+            Pane::First => (active, inactive),
+            Pane::Second => (inactive, active),
+        }
+    }
+
+    /// Returns the `cursor`, falling back to its last known position while
+    /// the pointer is `captured`.
+    fn pointer(&self, cursor: Cursor, captured: bool) -> Cursor {
+        match cursor {
+            Cursor::Unavailable if captured => self.screen_cursor.map_or(cursor, Cursor::Available),
+            _ => cursor,
+        }
+    }
+
+    /// Records a press at `position`.
+    ///
+    /// Returns true if the press completes a double click.
+    fn is_double_press(&mut self, position: Point, now: Instant) -> bool {
+        match self.last_press.take() {
+            Some((last, at))
+                if now - at <= DOUBLE_CLICK_TIME
+                    && last.distance(position) <= DOUBLE_CLICK_DISTANCE =>
+            {
+                true
+            }
+            _ => {
+                self.last_press = Some((position, now));
+                false
+            }
+        }
+    }
+
+    /// Returns the [`Viewport`] of the pane receiving events, which has the
+    /// given `bounds`.
+    fn viewport(&self, bounds: Rectangle) -> Viewport {
+        self.view().viewport(bounds)
+    }
+
+    /// Returns the scroll of the view, like that of [`Program::init_scroll`].
+    fn scroll(&self) -> Vector {
+        self.view().scroll()
+    }
+
+    /// Scrolls the view of a pane with the given `bounds` so the canvas
+    /// `point` is at the `screen` position.
+    ///
+    /// Returns the change in offset.
+    fn align(&mut self, bounds: Rectangle, point: Point, screen: Point) -> Vector {
+        let view = self.view();
+        let target = view.to_screen(Point::new(
+            f64::from(point.x) - view.origin.x,
+            f64::from(point.y) - view.origin.y,
+        ));
+        let screen = screen - bounds.center();
+        let offset = Vector::new(
+            (target.x - f64::from(screen.x)) as f32,
+            (target.y - f64::from(screen.y)) as f32,
+        );
+
+        let diff = offset - self.offset;
+        self.offset = offset;
+
+        diff
+    }
+
+    /// Moves the origin to the center of the view once it is scrolled far
+    /// enough from it.
+    fn rebase(&mut self) {
+        if self.offset.x.abs().max(self.offset.y.abs()) < REBASE_DISTANCE {
+            return;
+        }
+
+        let center = self.view().to_canvas(self.offset);
+
+        self.origin = Point::new(
+            self.origin.x + f64::from(center.x),
+            self.origin.y + f64::from(center.y),
+        );
+        self.offset = Vector::ZERO;
+    }
+
+    fn set_mouse_position(&mut self, position: Option<Point>) {
+        self.mouse_position = position;
+    }
+
+    /// Adds to the scale level of the zoomed `axes`
+    fn add_level(&mut self, diff: f32, axes: ZoomAxes, focal_origin: bool) -> Vector {
+        let prev_scale = self.scale;
+        self.set_scale_level(axes.add(self.scale_level, diff));
+
+        let delta = if focal_origin {
+            let ratio = |prev: f32, scale: f32| {
+                if diff < 0.0 {
+                    1.0 - prev / scale
+                } else {
+                    1.0 - scale / prev
+                }
+            };
+
+            Vector::new(
+                ratio(prev_scale.x, self.scale.x) * self.offset.x,
+                -ratio(prev_scale.y, self.scale.y) * self.offset.y,
+            )
+        } else {
+            let diff = self.scale - prev_scale;
+            let cursor = self.relative(self.mouse_position.unwrap_or(Point::ORIGIN));
+
+            let sign = self.y_axis.sign();
+
+            rotate(
+                Vector::new(diff.x * cursor.x, sign * diff.y * cursor.y),
+                self.rotation,
+            )
+        };
+
+        self.offset = self.offset + delta;
+
+        delta
+    }
+
+    /// Scrolls and zooms the zoomed `axes` so the `world` rectangle, given by
+    /// its bottom-left corner, fills a view of the given size.
+    ///
+    /// Zooming both axes keeps their ratio, while a single axis is fit on its
+    /// own. Returns the change in scale level and in offset respectively.
+    fn fit(&mut self, size: Size, world: Rectangle, axes: ZoomAxes) -> (f32, Vector) {
+        let factor = match axes {
+            ZoomAxes::Both => (size.width / self.scale.x / world.width)
+                .min(size.height / self.scale.y / world.height),
+            ZoomAxes::X => size.width / self.scale.x / world.width,
+            ZoomAxes::Y => size.height / self.scale.y / world.height,
+        };
+
+        if !factor.is_finite() || factor <= 0.0 {
+            return (0.0, Vector::ZERO);
+        }
+
+        let prev_level = self.scale_level;
+        let prev_offset = self.offset;
+
+        self.set_scale_level(axes.add(self.scale_level, factor.ln()));
+
+        let center = self.relative(world.center());
+        self.offset = rotate(
+            Vector::new(
+                center.x * self.scale.x,
+                self.y_axis.sign() * center.y * self.scale.y,
+            ),
+            self.rotation,
+        );
+
+        (
+            axes.get(self.scale_level - prev_level),
+            self.offset - prev_offset,
+        )
+    }
+
+    /// Zooms to the scale `level`s while keeping the center of the view fixed.
+    ///
+    /// Returns the change in offset.
+    fn zoom_centered(&mut self, level: Vector) -> Vector {
+        let prev_scale = self.scale;
+        self.set_scale_level(level);
+
+        // The axes are scaled before the canvas is rotated
+        let unrotated = rotate(self.offset, -self.rotation);
+        let offset = rotate(
+            Vector::new(
+                unrotated.x * (self.scale.x / prev_scale.x),
+                unrotated.y * (self.scale.y / prev_scale.y),
+            ),
+            self.rotation,
+        );
+        let delta = offset - self.offset;
+        self.offset = offset;
+
+        delta
+    }
+
+    /// Rotates counter-clockwise by `diff` radians around `pivot`, a point on
+    /// the screen relative to the center of the view.
+    ///
+    /// Returns the change in offset.
+    fn rotate_around(&mut self, diff: f32, pivot: Vector) -> Vector {
+        let offset = rotate(pivot + self.offset, diff) - pivot;
+        let delta = offset - self.offset;
+
+        self.rotation += diff;
+        self.offset = offset;
+
+        delta
+    }
+
+    /// Returns the position of a `point` on the canvas relative to the origin.
+    fn relative(&self, point: Point) -> Vector {
+        Vector::new(
+            (f64::from(point.x) - self.origin.x) as f32,
+            (f64::from(point.y) - self.origin.y) as f32,
+        )
+    }
+
+    fn set_scale_level(&mut self, level: Vector) {
+        self.scale_level = level;
+        self.scale = scales(self.scale_level);
+    }
+
+    fn reset_all(&mut self, offset: Vector, scale: f32) {
+        self.reset_scale(scale);
+        self.reset_offset(offset);
+        self.rotation = 0.0;
+    }
+
+    fn reset_offset(&mut self, init: Vector) {
+        self.offset = init;
+        self.origin = Point::new(0.0, 0.0);
+    }
+
+    fn reset_scale(&mut self, init: f32) {
+        let prev_scale = self.scale;
+        self.set_scale_level(Vector::new(init, init));
+
+        let delta = {
+            let diff = self.scale - prev_scale;
+            let mouse = self.relative(self.mouse_position.unwrap_or_default());
+            rotate(
+                Vector::new(diff.x * mouse.x, self.y_axis.sign() * diff.y * mouse.y),
+                self.rotation,
+            )
+        };
+
+        self.offset = self.offset + delta;
+    }
+}
+
+/// Style an [`Infinite`] canvas.
+pub mod style {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    /// The appearance of the [`Infinite`].
+    pub struct Style {
+        /// The [`Border`] of the [`Infinite`].
+        pub border: Border,
+        /// The [`Background`] of the [`Infinite`].
+        pub background: Background,
+        /// The border radius of the [`Infinite`]'s details.
+        pub details_border_radius: Radius,
+        /// The [`Background`] of the [`Infinite`]'s details.
+        pub details_background: Color,
+        /// The text [`Color`] of the [`Infinite`]'s details.
+        pub details_text: Color,
+        /// The [`Color`] of the divider between the panes of a split [`Infinite`].
+        pub divider: Color,
+        /// The [`Color`] of the zoom box of the [`Infinite`].
+        pub zoom_box: Color,
+        /// The [`Color`] of the ring drawn around a focused [`Infinite`].
+        ///
+        /// The ring is only drawn with [`KeyboardScope::Focused`].
+        ///
+        /// [`KeyboardScope::Focused`]: super::KeyboardScope::Focused
+        pub focus_ring: Color,
+        /// The [`Color`] of the lines of the [`Infinite`]'s grid.
+        pub grid: Color,
+        /// The [`Color`] of the major lines of the [`Infinite`]'s grid.
+        pub grid_major: Color,
+        /// The [`Color`] of the lines of the [`Infinite`]'s pixel grid.
+        pub pixel_grid: Color,
+        /// The [`Color`] of the [`Infinite`]'s axes and their ticks.
+        pub axes: Color,
+        /// The [`Color`] of the labels of the [`Infinite`]'s axes.
+        pub axes_label: Color,
+        /// The background [`Color`] of the [`Infinite`]'s rulers.
+        pub ruler_background: Color,
+        /// The [`Color`] of the ticks and labels of the [`Infinite`]'s rulers.
+        pub ruler_text: Color,
+        /// The [`Color`] of the guides dragged out of the [`Infinite`]'s
+        /// rulers.
+        pub guide: Color,
+        /// The [`Color`] of the [`Infinite`]'s scale bar and its label.
+        pub scale_bar: Color,
+    }
+
+    #[derive(Debug, Clone, Copy, Default, PartialEq)]
+    /// The possible status of an [`Infinite`].
+    pub enum Status {
+        #[default]
+        /// The [`Infinite`] is not being hovered on.
+        Active,
+        /// The [`Infinite`] is being hovered on.
+        Hovered,
+    }
+
+    /// The theme of an [`Infinite`].
+    pub trait Catalog {
+        /// The item class of the [`Catalog`].
+        type Class<'a>;
+
+        /// The default class produced by the [`Catalog`].
+        fn default<'a>() -> Self::Class<'a>;
+
+        /// The [`Style`] of a class with the given status.
+        fn style(&self, class: &Self::Class<'_>, status: Status) -> Style;
+    }
+
+    /// A styling function for an [`Infinite`].
+    pub type StyleFn<'a, Theme> = Box<dyn Fn(&Theme, Status) -> Style + 'a>;
+
+    impl Catalog for Theme {
+        type Class<'a> = StyleFn<'a, Self>;
+
+        fn default<'a>() -> Self::Class<'a> {
+            Box::new(default)
+        }
+
+        fn style(&self, class: &Self::Class<'_>, status: Status) -> Style {
+            class(self, status)
+        }
+    }
+
+    /// The default [`Theme`] styling of an [`Infinite`].
+    pub fn default(theme: &Theme, status: Status) -> Style {
+        let palette = theme.extended_palette();
+        let border_width = 2.5;
+
+        let background = palette.background.base;
+        let details_background = Color {
+            a: 0.9,
+            ..background.color
+        };
+        let details_text = background.text;
+
+        let border = match status {
+            Status::Active => Border::default()
+                .width(border_width)
+                .color(palette.background.base.color),
+            Status::Hovered => Border::default()
+                .width(border_width)
+                .color(palette.primary.strong.color),
+        };
+
+        Style {
+            border,
+            background: DEFAULT_BACKGROUND,
+            details_background,
+            details_border_radius: 5.into(),
+            details_text,
+            divider: palette.background.strong.color,
+            zoom_box: palette.primary.strong.color,
+            focus_ring: palette.primary.base.color,
+            grid: palette.background.strong.color.scale_alpha(0.3),
+            grid_major: palette.background.strong.color.scale_alpha(0.6),
+            pixel_grid: palette.background.base.text.scale_alpha(0.15),
+            axes: palette.background.strong.text,
+            axes_label: palette.background.strong.text,
+            ruler_background: palette.background.weak.color,
+            ruler_text: palette.background.weak.text,
+            guide: palette.primary.base.color,
+            scale_bar: details_text,
+        }
+    }
+}
+
+/// Returns the smallest of 1, 2 and 5 times a power of ten which is at least
+/// `min`.
+fn nice_step(min: f32) -> f32 {
+    let power = 10_f32.powf(min.log10().floor());
+
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|factor| factor * power)
+        .find(|step| *step >= min)
+        .unwrap_or(10.0 * power)
+}
+
+/// Returns the largest of 1, 2 and 5 times a power of ten which is at most
+/// `max`.
+fn nice_floor(max: f32) -> f32 {
+    let power = 10_f32.powf(max.log10().floor());
+
+    [5.0, 2.0, 1.0]
+        .into_iter()
+        .map(|factor| factor * power)
+        .find(|length| *length <= max)
+        .unwrap_or(power)
+}
+
+/// Formats the `value` of a tick with as many decimals as its `step` needs.
+fn tick_label(value: f32, step: f32) -> String {
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+    // Avoids labelling the origin as -0
+    let value = if value.abs() < step * 1e-3 {
+        0.0
+    } else {
+        value
+    };
+
+    format!("{value:.decimals$}")
+}
+
+/// Returns the index of the guide within grabbing distance of `position`.
+fn guide_at(guides: &[Guide], viewport: &Viewport, position: Point) -> Option<usize> {
+    let world = viewport.to_world(position);
+
+    guides.iter().position(|guide| match *guide {
+        Guide::Horizontal(y) => (world.y - y).abs() * viewport.scale.y < GUIDE_GRAB,
+        Guide::Vertical(x) => (world.x - x).abs() * viewport.scale.x < GUIDE_GRAB,
+    })
+}
+
+/// Draws the grid across a pane of the given `size`.
+fn draw_grid<Renderer: geometry::Renderer>(
+    frame: &mut Frame<Renderer>,
+    style: &Style,
+    config: &GridConfig,
+    view: View,
+    size: Size,
+) {
+    let viewport = view.viewport(Rectangle::with_size(size));
+    let visible = viewport.visible();
+    let stroke = Stroke::default().with_width(1.0);
+
+    match config.pattern {
+        GridPattern::Square => {}
+        GridPattern::Isometric => {
+            draw_isometric_grid(frame, style, config, view, viewport);
+            return;
+        }
+        GridPattern::Hex(layout) => {
+            let across = layout.size * view.scale.x.min(view.scale.y) * 3.0_f32.sqrt();
+
+            if !(layout.size > 0.0 && across >= config.min_spacing) {
+                return;
+            }
+
+            let (min, max) = hex_range(layout, visible);
+            let mut cells = path::Builder::new();
+            let mut count = 0;
+
+            'rows: for r in min.1..=max.1 {
+                // Rows are slanted, so the range of `q` shifts with `r`
+                let shift = (r - min.1).div_euclid(2);
+
+                for q in (min.0 - shift)..=(max.0 - shift) {
+                    let corners = layout.corners(q, r);
+
+                    cells.move_to(viewport.to_screen(corners[0]));
+
+                    for corner in &corners[1..] {
+                        cells.line_to(viewport.to_screen(*corner));
+                    }
+
+                    cells.close();
+
+                    count += 1;
+
+                    if count >= MAX_HEX_CELLS {
+                        break 'rows;
+                    }
+                }
+            }
+
+            frame.stroke(&cells.build(), stroke.with_color(style.grid));
+            return;
+        }
+    }
+
+    // The positions and whether they are major of the lines along an axis
+    let lines = |from: f32, length: f32, step: f32| {
+        let first = (from / step).floor() as i64;
+        let last = ((from + length) / step).ceil() as i64;
+        let last = last.min(first + MAX_GRID_LINES);
+
+        (first..=last).map(move |line| (line as f32 * step, line % GRID_MAJOR == 0))
+    };
+
+    let step_x = config.step(view.scale.x);
+    let step_y = config.step(view.scale.y);
+
+    if !(step_x.is_finite() && step_x > 0.0 && step_y.is_finite() && step_y > 0.0) {
+        return;
+    }
+
+    let mut minor = path::Builder::new();
+    let mut major = path::Builder::new();
+
+    for (x, is_major) in lines(visible.x, visible.width, step_x) {
+        let builder = if is_major { &mut major } else { &mut minor };
+
+        builder.move_to(viewport.to_screen(Point::new(x, visible.y)));
+        builder.line_to(viewport.to_screen(Point::new(x, visible.y + visible.height)));
+    }
+
+    for (y, is_major) in lines(visible.y, visible.height, step_y) {
+        let builder = if is_major { &mut major } else { &mut minor };
+
+        builder.move_to(viewport.to_screen(Point::new(visible.x, y)));
+        builder.line_to(viewport.to_screen(Point::new(visible.x + visible.width, y)));
+    }
+
+    frame.stroke(&minor.build(), stroke.with_color(style.grid));
+    frame.stroke(&major.build(), stroke.with_color(style.grid_major));
+}
+
+/// Draws a line between every unit across a pane of the given `size`.
+fn draw_pixel_grid<Renderer: geometry::Renderer>(
+    frame: &mut Frame<Renderer>,
+    style: &Style,
+    view: View,
+    size: Size,
+) {
+    let viewport = view.viewport(Rectangle::with_size(size));
+    let visible = viewport.visible();
+
+    let (left, right) = (visible.x.floor(), (visible.x + visible.width).ceil());
+    let (top, bottom) = (visible.y.floor(), (visible.y + visible.height).ceil());
+
+    if right - left > MAX_GRID_LINES as f32 || bottom - top > MAX_GRID_LINES as f32 {
+        return;
+    }
+
+    let lines = Path::new(|builder| {
+        for x in left as i64..=right as i64 {
+            builder.move_to(viewport.to_screen(Point::new(x as f32, top)));
+            builder.line_to(viewport.to_screen(Point::new(x as f32, bottom)));
+        }
+
+        for y in top as i64..=bottom as i64 {
+            builder.move_to(viewport.to_screen(Point::new(left, y as f32)));
+            builder.line_to(viewport.to_screen(Point::new(right, y as f32)));
+        }
+    });
+
+    frame.stroke(
+        &lines,
+        Stroke::default()
+            .with_width(1.0)
+            .with_color(style.pixel_grid),
+    );
+}
+
+/// Draws an isometric grid across the `viewport`.
+///
+/// The lines have slopes of one half and minus one half, and cross the X axis
+/// a step apart.
+fn draw_isometric_grid<Renderer: geometry::Renderer>(
+    frame: &mut Frame<Renderer>,
+    style: &Style,
+    config: &GridConfig,
+    view: View,
+    viewport: Viewport,
+) {
+    let visible = viewport.visible();
+    let step = config.step(view.scale.x.min(view.scale.y));
+
+    if !(step.is_finite() && step > 0.0) {
+        return;
+    }
+
+    let corners = [
+        Point::new(visible.x, visible.y),
+        Point::new(visible.x + visible.width, visible.y),
+        Point::new(visible.x, visible.y + visible.height),
+        Point::new(visible.x + visible.width, visible.y + visible.height),
+    ];
+    let (left, right) = (visible.x, visible.x + visible.width);
+
+    let mut minor = path::Builder::new();
+    let mut major = path::Builder::new();
+
+    for slope in [0.5, -0.5] {
+        // Each line is `y = slope * x + offset`, with offsets half a step apart
+        let offsets = corners.map(|corner| corner.y - slope * corner.x);
+        let lowest = offsets.into_iter().fold(f32::INFINITY, f32::min);
+        let highest = offsets.into_iter().fold(f32::NEG_INFINITY, f32::max);
+
+        let spacing = step / 2.0;
+        let first = (lowest / spacing).floor() as i64;
+        let last = ((highest / spacing).ceil() as i64).min(first + MAX_GRID_LINES);
+
+        for line in first..=last {
+            let offset = line as f32 * spacing;
+            let builder = if line % GRID_MAJOR == 0 {
+                &mut major
+            } else {
+                &mut minor
+            };
+
+            builder.move_to(viewport.to_screen(Point::new(left, slope * left + offset)));
+            builder.line_to(viewport.to_screen(Point::new(right, slope * right + offset)));
+        }
+    }
+
+    let stroke = Stroke::default().with_width(1.0);
+
+    frame.stroke(&minor.build(), stroke.with_color(style.grid));
+    frame.stroke(&major.build(), stroke.with_color(style.grid_major));
+}
+
+/// Returns the smallest and largest axial co-ordinates of the cells of a
+/// hexagonal grid overlapping `visible`.
+///
+/// `q` is given for the first row, and shifts by one every other row.
+fn hex_range(layout: HexLayout, visible: Rectangle) -> ((i32, i32), (i32, i32)) {
+    let row = layout.size * 1.5;
+    let width = layout.size * 3.0_f32.sqrt();
+
+    let first = (visible.y / row).floor() as i32 - 1;
+    let last = ((visible.y + visible.height) / row).ceil() as i32 + 1;
+
+    // The column of `x` in the first row, which shifts by half a cell each row
+    let column = |x: f32| x / width - first as f32 / 2.0;
+    let left = column(visible.x).floor() as i32 - 1;
+    let right = column(visible.x + visible.width).ceil() as i32 + 1;
+
+    ((left, first), (right, last))
+}
+
+/// Draws the axes through the origin across a pane of the given `size`.
+fn draw_axes<Renderer: geometry::Renderer>(
+    frame: &mut Frame<Renderer>,
+    style: &Style,
+    config: &AxesConfig,
+    view: View,
+    size: Size,
+) {
+    let viewport = view.viewport(Rectangle::with_size(size));
+    let visible = viewport.visible();
+    let origin = viewport.to_screen(Point::ORIGIN);
+
+    // The direction on the screen of a unit along each axis
+    let direction = |unit: Point| {
+        let vector = viewport.to_screen(unit) - origin;
+        let length = vector.x.hypot(vector.y);
+
+        Vector::new(vector.x / length, vector.y / length)
+    };
+    let (along_x, along_y) = (
+        direction(Point::new(1.0, 0.0)),
+        direction(Point::new(0.0, 1.0)),
+    );
+
+    let mut labels = Vec::new();
+
+    let path = Path::new(|builder| {
+        builder.move_to(viewport.to_screen(Point::new(visible.x, 0.0)));
+        builder.line_to(viewport.to_screen(Point::new(visible.x + visible.width, 0.0)));
+        builder.move_to(viewport.to_screen(Point::new(0.0, visible.y)));
+        builder.line_to(viewport.to_screen(Point::new(0.0, visible.y + visible.height)));
+
+        let axes = [
+            (visible.x, visible.width, view.scale.x, along_y, true),
+            (visible.y, visible.height, view.scale.y, along_x, false),
+        ];
+
+        for (from, length, scale, across, is_x) in axes {
+            let step = nice_step(config.tick_spacing / scale);
+
+            if !step.is_finite() || step <= 0.0 {
+                continue;
+            }
+
+            let first = (from / step).floor() as i64;
+            let last = ((from + length) / step).ceil() as i64;
+            let last = last.min(first + MAX_GRID_LINES);
+
+            // The origin is labelled once, by the X axis
+            for tick in (first..=last).filter(|tick| is_x || *tick != 0) {
+                let value = tick as f32 * step;
+                let point = if is_x {
+                    Point::new(value, 0.0)
+                } else {
+                    Point::new(0.0, value)
+                };
+                let position = viewport.to_screen(point);
+
+                builder.move_to(position - across * (AXIS_TICK / 2.0));
+                builder.line_to(position + across * (AXIS_TICK / 2.0));
+
+                // Labels go below the X axis and right of the Y axis
+                if config.labels {
+                    let side = if is_x { -AXIS_TICK } else { AXIS_TICK };
+                    let position = position + across * side + Vector::new(2.0, 0.0);
+                    labels.push((tick_label(value, step), position));
+                }
+            }
+        }
+    });
+
+    frame.stroke(
+        &path,
+        Stroke::default()
+            .with_width(config.width)
+            .with_color(style.axes),
+    );
+
+    for (content, position) in labels {
+        frame.fill_text(Text {
+            content,
+            position,
+            color: style.axes_label,
+            size: AXIS_TEXT_SIZE.into(),
+            ..Default::default()
+        });
+    }
+}
+
+/// Draws the `guides` across a pane of the given `size`.
+fn draw_guides<Renderer: geometry::Renderer>(
+    frame: &mut Frame<Renderer>,
+    style: &Style,
+    guides: &[Guide],
+    view: View,
+    size: Size,
+) {
+    if guides.is_empty() {
+        return;
+    }
+
+    let viewport = view.viewport(Rectangle::with_size(size));
+    let visible = viewport.visible();
+
+    let path = Path::new(|builder| {
+        for guide in guides {
+            let (start, end) = match *guide {
+                Guide::Horizontal(y) => (
+                    Point::new(visible.x, y),
+                    Point::new(visible.x + visible.width, y),
+                ),
+                Guide::Vertical(x) => (
+                    Point::new(x, visible.y),
+                    Point::new(x, visible.y + visible.height),
+                ),
+            };
+
+            builder.move_to(viewport.to_screen(start));
+            builder.line_to(viewport.to_screen(end));
+        }
+    });
+
+    frame.stroke(
+        &path,
+        Stroke::default().with_width(1.0).with_color(style.guide),
+    );
+}
+
+/// Draws the rulers along the top and left edges of a pane of the given
+/// `size`.
+fn draw_rulers<Renderer: geometry::Renderer>(
+    frame: &mut Frame<Renderer>,
+    style: &Style,
+    view: View,
+    size: Size,
+) {
+    // The rulers measure the canvas as if it were not rotated
+    let viewport = Viewport {
+        rotation: Radians(0.0),
+        ..view.viewport(Rectangle::with_size(size))
+    };
+
+    frame.fill_rectangle(
+        Point::ORIGIN,
+        Size::new(size.width, RULER_SIZE),
+        style.ruler_background,
+    );
+    frame.fill_rectangle(
+        Point::ORIGIN,
+        Size::new(RULER_SIZE, size.height),
+        style.ruler_background,
+    );
+
+    // The values and whether they are labelled of the ticks between `from`
+    // and `to`, at the given zoom
+    let ticks = |from: f32, to: f32, scale: f32| {
+        let step = nice_step(MIN_TICK_SPACING / scale);
+        let minor = step / MINOR_TICKS as f32;
+        let (from, to) = (from.min(to), from.max(to));
+
+        let first = (from / minor).floor() as i64;
+        let last = (to / minor).ceil() as i64;
+        // Guards against degenerate zooms
+        let last = last.min(first + 10_000);
+
+        (first..=last).map(move |tick| (tick as f32 * minor, tick % MINOR_TICKS == 0, step))
+    };
+
+    let start = viewport.to_world(Point::new(RULER_SIZE, RULER_SIZE));
+    let end = viewport.to_world(Point::new(size.width, size.height));
+
+    let mut labels = Vec::new();
+
+    let path = Path::new(|builder| {
+        for (x, major, step) in ticks(start.x, end.x, view.scale.x) {
+            let screen = viewport.to_screen(Point::new(x, 0.0)).x;
+
+            if screen < RULER_SIZE {
+                continue;
+            }
+
+            let length = if major { RULER_SIZE } else { RULER_SIZE / 4.0 };
+            builder.move_to(Point::new(screen, RULER_SIZE - length));
+            builder.line_to(Point::new(screen, RULER_SIZE));
+
+            if major {
+                labels.push((tick_label(x, step), Point::new(screen + 2.0, 1.0)));
+            }
+        }
+
+        for (y, major, step) in ticks(start.y, end.y, view.scale.y) {
+            let screen = viewport.to_screen(Point::new(0.0, y)).y;
+
+            if screen < RULER_SIZE {
+                continue;
+            }
+
+            let length = if major { RULER_SIZE } else { RULER_SIZE / 4.0 };
+            builder.move_to(Point::new(RULER_SIZE - length, screen));
+            builder.line_to(Point::new(RULER_SIZE, screen));
+
+            if major {
+                labels.push((tick_label(y, step), Point::new(1.0, screen + 1.0)));
+            }
+        }
+    });
+
+    frame.stroke(
+        &path,
+        Stroke::default()
+            .with_width(1.0)
+            .with_color(style.ruler_text),
+    );
+
+    for (content, position) in labels {
+        frame.fill_text(Text {
+            content,
+            position,
+            color: style.ruler_text,
+            size: RULER_TEXT_SIZE.into(),
+            ..Default::default()
+        });
+    }
+}
+
+/// Draws the `details` of a pane of the given `size` in their corners with
+/// their opacity, keeping `inset` pixels clear along the top and left edges.
+fn draw_details<Renderer: geometry::Renderer>(
+    frame: &mut Frame<Renderer>,
+    style: &Style,
+    details: Vec<(Corner, String, f32)>,
+    size: Size,
+    inset: f32,
+) {
+    let top = 2.5;
+    let left = 8.0;
+    let padding = {
+        let bottom = 2.5;
+        let right = 8.0;
+        Size::new(left + right, top + bottom)
+    };
+    let text_size = 16.0;
+
+    let radius = style.details_border_radius;
+
+    // The height taken up by the details already drawn in each corner
+    let mut stacked = [0.0; 4];
+
+    for (corner, content, opacity) in details {
+        let background = style.details_background.scale_alpha(opacity);
+        let color = style.details_text.scale_alpha(opacity);
+
+        let bounds = min_text_bounds(&content, Size::INFINITY, text_size).expand(padding);
+
+        let x = match corner {
+            Corner::TopLeft | Corner::BottomLeft => (size.width * 0.01).max(inset),
+            Corner::TopRight | Corner::BottomRight => {
+                (size.width * 0.9).min(size.width - bounds.width - left)
+            }
+        };
+
+        let stack = &mut stacked[corner as usize];
+        let y = match corner {
+            Corner::TopLeft | Corner::TopRight => (size.height * 0.01).max(inset) + *stack,
+            Corner::BottomLeft | Corner::BottomRight => size.height * 0.95 - *stack,
+        };
+        *stack += bounds.height + top;
+
+        let position = Point::new(x, y);
+        let rect = Path::rounded_rectangle(position, bounds, radius);
+
+        frame.fill(&rect, background);
+
+        frame.fill_text(Text {
+            content,
+            position: (position.x + left, position.y + top).into(),
+            color,
+            ..Default::default()
+        });
+    }
+}
+
+/// Draws the scale bar in the top-right corner of a pane of the given `size`,
+/// below `top`.
+fn draw_scale_bar<Renderer: geometry::Renderer>(
+    frame: &mut Frame<Renderer>,
+    style: &Style,
+    scale: f32,
+    size: Size,
+    top: f32,
+) {
+    let units = nice_floor(MAX_SCALE_BAR / scale);
+
+    if !units.is_finite() || units <= 0.0 {
+        return;
+    }
+
+    let length = units * scale;
+    let label = format!("{} units", tick_label(units, units));
+
+    let padding = 8.0;
+    let text = min_text_bounds(&label, Size::INFINITY, SCALE_BAR_TEXT_SIZE);
+    let width = length.max(text.width) + 2.0 * padding;
+    let height = text.height + 3.0 * padding;
+
+    let position = Point::new(size.width - width - padding, top + padding);
+
+    frame.fill(
+        &Path::rounded_rectangle(
+            position,
+            Size::new(width, height),
+            style.details_border_radius,
+        ),
+        style.details_background,
+    );
+
+    frame.fill_text(Text {
+        content: label,
+        position: Point::new(position.x + padding, position.y + padding / 2.0),
+        color: style.scale_bar,
+        size: SCALE_BAR_TEXT_SIZE.into(),
+        ..Default::default()
+    });
+
+    let y = position.y + height - padding;
+    let (start, end) = (position.x + padding, position.x + padding + length);
+    let cap = padding / 2.0;
+
+    let bar = Path::new(|builder| {
+        builder.move_to(Point::new(start, y - cap));
+        builder.line_to(Point::new(start, y));
+        builder.line_to(Point::new(end, y));
+        builder.line_to(Point::new(end, y - cap));
+    });
+
+    frame.stroke(
+        &bar,
+        Stroke::default()
+            .with_width(2.0)
+            .with_color(style.scale_bar),
+    );
+}
+
+/// Returns a pair of [`Cursor`]s with the second [`Cursor`]'s point translated
+/// to fit within the [`Infinite`]'s coordinate system.
+fn get_cursors(cursor: Cursor, bounds: Rectangle, view: View) -> (Cursor, Cursor) {
+    match cursor {
+        Cursor::Available(point) => {
+            let point = translate_cursor(bounds, view, point);
+
+            (cursor, Cursor::Available(point))
+        }
+        Cursor::Unavailable => (cursor, cursor),
+    }
+}
+
+/// Returns the smallest and largest offsets which keep a view of the given
+/// size within the world `region`, given by its bottom-left corner.
+///
+/// A region smaller than the view along an axis is centered along it.
+fn offset_range(
+    scale: Vector,
+    y_axis: YDirection,
+    size: Size,
+    region: Rectangle,
+) -> (Vector, Vector) {
+    let range = |start: f32, length: f32, view: f32, scale: f32| {
+        let (low, high) = (
+            start * scale + view / 2.0,
+            (start + length) * scale - view / 2.0,
+        );
+
+        if low > high {
+            let middle = (start + length / 2.0) * scale;
+            (middle, middle)
+        } else {
+            (low, high)
+        }
+    };
+
+    let (min_x, max_x) = range(region.x, region.width, size.width, scale.x);
+    let (min_y, max_y) = range(region.y, region.height, size.height, scale.y);
+
+    // The y axis of the offset points down
+    match y_axis {
+        YDirection::Up => (Vector::new(min_x, -max_y), Vector::new(max_x, -min_y)),
+        YDirection::Down => (Vector::new(min_x, min_y), Vector::new(max_x, max_y)),
+    }
+}
+
+/// Returns the direction to scroll in with the cursor at `position`, scaled by
+/// how far within `margin` of the border of `bounds` it is.
+fn edge_direction(bounds: Rectangle, position: Point, margin: f32) -> Vector {
+    let margin = margin.max(f32::EPSILON);
+
+    let pull = |near: f32, far: f32| {
+        let towards_start = ((margin - near) / margin).clamp(0.0, 1.0);
+        let towards_end = ((margin - far) / margin).clamp(0.0, 1.0);
+
+        towards_end - towards_start
+    };
+
+    Vector::new(
+        pull(position.x - bounds.x, bounds.x + bounds.width - position.x),
+        pull(position.y - bounds.y, bounds.y + bounds.height - position.y),
+    )
+}
+
+/// Returns `step` in the direction of `delta`, or zero without a `delta`.
+fn axis_step(delta: f32, step: f32) -> f32 {
+    if delta == 0.0 {
+        0.0
+    } else {
+        step.copysign(delta)
+    }
+}
+
+/// Returns the offset along a single axis after scrolling from `previous` to
+/// `target`, resisting the part of the scroll past `min` and `max`.
+fn overscroll(previous: f32, target: f32, min: f32, max: f32) -> f32 {
+    let clamped = target.clamp(min, max);
+    let excess = target - clamped;
+
+    let prior = previous - previous.clamp(min, max);
+    let prior = if prior.signum() == excess.signum() {
+        prior
+    } else {
+        0.0
+    };
+
+    // Scrolling back towards the bounds is never resisted
+    if excess.abs() <= prior.abs() {
+        return target;
+    }
+
+    let excess = prior + (excess - prior) * OVERSCROLL_RESISTANCE;
+
+    clamped + excess.clamp(-MAX_OVERSCROLL, MAX_OVERSCROLL)
+}
+
+/// Returns the bounds of the first pane, the second pane and the divider of a
+/// split [`Infinite`] respectively.
+fn split_bounds(bounds: Rectangle, split: Split, ratio: f32) -> (Rectangle, Rectangle, Rectangle) {
+    match split {
+        Split::Horizontal => {
+            let first_width = ((bounds.width - DIVIDER_WIDTH) * ratio).max(0.0);
+            let second_width = (bounds.width - DIVIDER_WIDTH - first_width).max(0.0);
+
+            let first = Rectangle::new(bounds.position(), Size::new(first_width, bounds.height));
+            let divider = Rectangle::new(
+                Point::new(bounds.x + first_width, bounds.y),
+                Size::new(DIVIDER_WIDTH, bounds.height),
+            );
+            let second = Rectangle::new(
+                Point::new(divider.x + DIVIDER_WIDTH, bounds.y),
+                Size::new(second_width, bounds.height),
+            );
+
+            (first, second, divider)
+        }
+        Split::Vertical => {
+            let first_height = ((bounds.height - DIVIDER_WIDTH) * ratio).max(0.0);
+            let second_height = (bounds.height - DIVIDER_WIDTH - first_height).max(0.0);
+
+            let first = Rectangle::new(bounds.position(), Size::new(bounds.width, first_height));
+            let divider = Rectangle::new(
+                Point::new(bounds.x, bounds.y + first_height),
+                Size::new(bounds.width, DIVIDER_WIDTH),
+            );
+            let second = Rectangle::new(
+                Point::new(bounds.x, divider.y + DIVIDER_WIDTH),
+                Size::new(bounds.width, second_height),
+            );
+
+            (first, second, divider)
+        }
+    }
+}
+
+/// Handles dragging the divider of a split [`Infinite`] and activates the
+/// pane under the cursor.
+///
+/// Returns a status if the event was consumed by the divider.
+fn handle_split<State>(
+    state: &mut InfiniteState<State>,
+    split: Split,
+    linked: bool,
+    event: &iced::Event,
+    cursor: Cursor,
+    bounds: Rectangle,
+) -> Option<iced_event::Status> {
+    let (first, second, divider) = split_bounds(bounds, split, state.split.ratio);
+
+    match event {
+        iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+            if cursor.is_over(divider.expand(DIVIDER_WIDTH)) =>
+        {
+            state.split.dragging = true;
+            return Some(iced_event::Status::Captured);
+        }
+        iced::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+            if state.split.dragging =>
+        {
+            state.split.dragging = false;
+            return Some(iced_event::Status::Captured);
+        }
+        iced::Event::Mouse(mouse::Event::CursorMoved { position }) if state.split.dragging => {
+            let ratio = match split {
+                Split::Horizontal => (position.x - bounds.x) / bounds.width,
+                Split::Vertical => (position.y - bounds.y) / bounds.height,
+            };
+            state.split.ratio = ratio.clamp(MIN_SPLIT_RATIO, MAX_SPLIT_RATIO);
+
+            return Some(iced_event::Status::Captured);
+        }
+        _ => {}
+    }
+
+    if state.pan.is_some() {
+        return None;
+    }
+
+    if cursor.is_over(first) {
+        state.activate_pane(Pane::First, linked);
+    } else if cursor.is_over(second) {
+        state.activate_pane(Pane::Second, linked);
+    }
+
+    None
+}
+
+/// Returns the corners of a regular polygon with `sides` sides whose corners
+/// lie `radius` away from its `center`.
+///
+/// The first corner lies along the X axis from the `center`, turned by
+/// `rotation`. A `rotation` of a quarter turn gives a pointy-topped polygon.
+pub fn regular_polygon(
+    center: impl Into<Point>,
+    radius: f32,
+    sides: usize,
+    rotation: impl Into<Radians>,
+) -> Vec<Point> {
+    let center = center.into();
+    let rotation = rotation.into().0;
+
+    (0..sides)
+        .map(|side| {
+            let angle = rotation + 2.0 * PI * side as f32 / sides as f32;
+
+            Point::new(
+                center.x + radius * angle.cos(),
+                center.y + radius * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+/// Returns the [`Path`] of an ellipse with the given `radii`.
+fn ellipse(center: Point, radii: Vector) -> Path {
+    Path::new(|builder| {
+        builder.ellipse(path::arc::Elliptical {
+            center,
+            radii,
+            rotation: Radians(0.0),
+            start_angle: Radians(0.0),
+            end_angle: Radians(2.0 * PI),
+        });
+        builder.close();
+    })
+}
+
+/// Returns the [`Path`] of a circular arc, closed through its `center` if it is
+/// a `pie`.
+fn arc(center: Point, radius: f32, start_angle: Radians, end_angle: Radians, pie: bool) -> Path {
+    use iced::widget::canvas::path::lyon_path::{geom, math};
+
+    let arc = geom::Arc {
+        center: math::point(center.x, center.y),
+        radii: math::vector(radius, radius),
+        x_rotation: math::Angle::zero(),
+        start_angle: math::Angle::radians(start_angle.0),
+        sweep_angle: math::Angle::radians(end_angle.0 - start_angle.0),
+    };
+    let start = arc.sample(0.0);
+
+    Path::new(|builder| {
+        if pie {
+            builder.move_to(center);
+            builder.line_to(Point::new(start.x, start.y));
+        } else {
+            builder.move_to(Point::new(start.x, start.y));
+        }
+
+        arc.for_each_quadratic_bezier(&mut |curve| {
+            builder.quadratic_curve_to(
+                Point::new(curve.ctrl.x, curve.ctrl.y),
+                Point::new(curve.to.x, curve.to.y),
+            );
+        });
+
+        if pie {
+            builder.close();
+        }
+    })
+}
+
+/// Returns the closed [`Path`] through the given `points`.
+fn polygon(points: &[Point]) -> Path {
+    Path::new(|builder| {
+        if let Some((first, rest)) = points.split_first() {
+            builder.move_to(*first);
+
+            for point in rest {
+                builder.line_to(*point);
+            }
+
+            builder.close();
+        }
+    })
+}
+
+/// Lays out `spans` as a single text, returning each piece of a span on a line
+/// with its top-left corner relative to the top-left corner of the text.
+fn layout_spans(spans: &[text::Span<'_>], style: &TextStyle) -> Vec<(Vector, Text)> {
+    use iced::advanced::{self, text::Paragraph};
+
+    let paragraph = iced_graphics::text::Paragraph::with_spans(advanced::Text {
+        content: spans,
+        bounds: Size::INFINITY,
+        size: style.size,
+        line_height: style.line_height,
+        font: style.font,
+        horizontal_alignment: alignment::Horizontal::Left,
+        vertical_alignment: alignment::Vertical::Top,
+        shaping: style.shaping,
+        wrapping: text::Wrapping::None,
+    });
+
+    let mut pieces = Vec::new();
+
+    for run in paragraph.buffer().layout_runs() {
+        for glyphs in run.glyphs.chunk_by(|a, b| a.metadata == b.metadata) {
+            let Some(span) = spans.get(glyphs[0].metadata) else {
+                continue;
+            };
+
+            let start = glyphs.iter().map(|glyph| glyph.start).min().unwrap_or(0);
+            let end = glyphs.iter().map(|glyph| glyph.end).max().unwrap_or(0);
+
+            let text = Text {
+                content: run.text[start..end].to_string(),
+                position: Point::ORIGIN,
+                color: span.color.unwrap_or(style.color),
+                size: span.size.unwrap_or(style.size),
+                line_height: span.line_height.unwrap_or(style.line_height),
+                font: span.font.unwrap_or(style.font),
+                horizontal_alignment: alignment::Horizontal::Left,
+                vertical_alignment: alignment::Vertical::Top,
+                shaping: style.shaping,
+            };
+
+            // Pieces of different sizes share the baseline of their line, so
+            // each is moved by where its baseline falls when drawn on its own
+            let alone = shaped(advanced::Text {
+                content: text.content.as_str(),
+                bounds: Size::INFINITY,
+                size: text.size,
+                line_height: text.line_height,
+                font: text.font,
+                horizontal_alignment: alignment::Horizontal::Left,
+                vertical_alignment: alignment::Vertical::Top,
+                shaping: text.shaping,
+                wrapping: text::Wrapping::None,
+            });
+            let baseline = alone
+                .buffer()
+                .layout_runs()
+                .next()
+                .map_or(0.0, |run| run.line_y);
+
+            pieces.push((Vector::new(glyphs[0].x, run.line_y - baseline), text));
+        }
+    }
+
+    pieces
+}
+
+/// Wraps `content` to the width of `region`, returning a [`Text`] for each
+/// line which fits in it.
+fn wrap_text(content: &str, region: Rectangle, style: &TextStyle) -> Vec<Text> {
+    use iced::advanced;
+
+    let paragraph = shaped(advanced::Text {
+        content,
+        bounds: Size::new(region.width, f32::INFINITY),
+        size: style.size,
+        line_height: style.line_height,
+        font: style.font,
+        horizontal_alignment: alignment::Horizontal::Left,
+        vertical_alignment: alignment::Vertical::Top,
+        shaping: style.shaping,
+        wrapping: text::Wrapping::WordOrGlyph,
+    });
+
+    let x = match style.horizontal_alignment {
+        alignment::Horizontal::Left => region.x,
+        alignment::Horizontal::Center => region.center_x(),
+        alignment::Horizontal::Right => region.x + region.width,
+    };
+
+    paragraph
+        .buffer()
+        .layout_runs()
+        .take_while(|run| run.line_top + run.line_height <= region.height)
+        .filter_map(|run| {
+            let start = run.glyphs.iter().map(|glyph| glyph.start).min()?;
+            let end = run.glyphs.iter().map(|glyph| glyph.end).max()?;
+
+            Some(Text {
+                content: run.text[start..end].trim_end().to_string(),
+                position: Point::new(x, region.y + run.line_top),
+                color: style.color,
+                size: style.size,
+                line_height: style.line_height,
+                font: style.font,
+                horizontal_alignment: style.horizontal_alignment,
+                vertical_alignment: alignment::Vertical::Top,
+                shaping: style.shaping,
+            })
+        })
+        .collect()
+}
+
+/// Returns the width of `content` on a single line, in the font and size of
+/// the `text`.
+pub(crate) fn line_width(content: &str, text: &Text) -> f32 {
+    let text = advanced::Text {
+        content,
+        bounds: Size::INFINITY,
+        font: text.font,
+        size: text.size,
+        line_height: text.line_height,
+        horizontal_alignment: alignment::Horizontal::Left,
+        vertical_alignment: alignment::Vertical::Top,
+        wrapping: text::Wrapping::None,
+        shaping: text.shaping,
+    };
+
+    advanced::text::Paragraph::min_bounds(&shaped(text)).width
+}
+
+/// Returns the minimum bounds that can fit `text`.
+///
+/// The text is only shaped the first time it is measured, so measuring the
+/// same text on every frame is cheap.
+pub fn min_text_bounds(text: &str, bounds: Size, size: impl Into<Pixels>) -> Size {
+    use iced::{
+        advanced::{
+            self,
+            text::{self, Paragraph},
+        },
+        alignment, Font,
+    };
+
+    let text = advanced::Text {
+        content: text,
+        bounds,
+        font: Font::default(),
+        size: size.into(),
+        line_height: text::LineHeight::default(),
+        horizontal_alignment: alignment::Horizontal::Left,
+        vertical_alignment: alignment::Vertical::Center,
+        wrapping: text::Wrapping::default(),
+        shaping: text::Shaping::default(),
+    };
+
+    shaped(text).min_bounds()
+}
+
+/// The most paragraphs kept by [`shaped`] between frames.
+const MAX_PARAGRAPHS: usize = 1024;
+
+/// What a paragraph is shaped from, see [`shaped`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ParagraphKey {
+    content: String,
+    font: Font,
+    /// The bits of the size, line height and bounds, as floats are not
+    /// hashable.
+    size: u32,
+    line_height: u32,
+    bounds: [u32; 2],
+    horizontal_alignment: alignment::Horizontal,
+    shaping: text::Shaping,
+    wrapping: text::Wrapping,
+}
+
+/// The paragraphs shaped lately, see [`shaped`].
+#[derive(Default)]
+struct Paragraphs {
+    /// The version of the font system the paragraphs were shaped with.
+    version: Option<iced_graphics::text::Version>,
+    /// Each paragraph and whether it was used since the cache last filled.
+    entries: HashMap<ParagraphKey, (iced_graphics::text::Paragraph, bool)>,
+}
+
+thread_local! {
+    static PARAGRAPHS: RefCell<Paragraphs> = RefCell::new(Paragraphs::default());
+}
+
+/// Returns the shaped paragraph of `text`, reusing the paragraph of the same
+/// text shaped on an earlier frame if there is one.
+///
+/// Loading a font drops all the paragraphs shaped before it.
+fn shaped(text: advanced::Text<&str>) -> iced_graphics::text::Paragraph {
+    use iced::advanced::text::Paragraph;
+
+    let version = iced_graphics::text::font_system()
+        .read()
+        .ok()
+        .map(|system| system.version());
+
+    let key = ParagraphKey {
+        content: text.content.to_string(),
+        font: text.font,
+        size: text.size.0.to_bits(),
+        line_height: text.line_height.to_absolute(text.size).0.to_bits(),
+        bounds: [text.bounds.width.to_bits(), text.bounds.height.to_bits()],
+        horizontal_alignment: text.horizontal_alignment,
+        shaping: text.shaping,
+        wrapping: text.wrapping,
+    };
+
+    PARAGRAPHS.with_borrow_mut(|paragraphs| {
+        if paragraphs.version != version {
+            paragraphs.version = version;
+            paragraphs.entries.clear();
+        }
+
+        if let Some((paragraph, used)) = paragraphs.entries.get_mut(&key) {
+            *used = true;
+            return paragraph.clone();
+        }
+
+        // Once full, keep only the paragraphs used since it last filled
+        if paragraphs.entries.len() >= MAX_PARAGRAPHS {
+            paragraphs
+                .entries
+                .retain(|_, (_, used)| std::mem::take(used));
+
+            if paragraphs.entries.len() >= MAX_PARAGRAPHS {
+                paragraphs.entries.clear();
+            }
+        }
+
+        let paragraph = iced_graphics::text::Paragraph::with_text(text);
+        paragraphs.entries.insert(key, (paragraph.clone(), true));
+
+        paragraph
+    })
+}
+
+fn wrap_event(event: iced::Event, bounds: Rectangle, view: View) -> Option<event::Event> {
+    let translate = |position| translate_cursor(bounds, view, position);
+
+    match event.clone() {
+        iced::Event::Mouse(mouse::Event::CursorMoved { position }) => {
+            let position = translate(position);
+            Some(Event::Mouse(mouse::Event::CursorMoved { position }))
+        }
+        iced::Event::Mouse(event) => Some(Event::Mouse(event)),
+        iced::Event::Keyboard(event) => Some(Event::Keyboard(event)),
+        iced::Event::Touch(event) => {
+            let event = match event {
+                touch::Event::FingerLost { id, position } => {
+                    let position = translate(position);
+                    Event::Touch(touch::Event::FingerLost { id, position })
+                }
+                touch::Event::FingerMoved { id, position } => {
+                    let position = translate(position);
+                    Event::Touch(touch::Event::FingerMoved { id, position })
+                }
+                touch::Event::FingerLifted { id, position } => {
+                    let position = translate(position);
+                    Event::Touch(touch::Event::FingerLifted { id, position })
+                }
+                touch::Event::FingerPressed { id, position } => {
+                    let position = translate(position);
+                    Event::Touch(touch::Event::FingerPressed { id, position })
+                }
+            };
+
+            Some(event)
+        }
+        iced::Event::Window(event) => {
+            let event = match event {
+                window::Event::Resized(size) => WindowEvent::Resized(size),
+                window::Event::Focused => WindowEvent::Focused,
+                window::Event::Unfocused => WindowEvent::Unfocused,
+                window::Event::RedrawRequested(now) => WindowEvent::RedrawRequested(now),
+                _ => return None,
+            };
+
+            Some(Event::Window(event))
+        }
+    }
+}
+
+/// Translates a `point` on the screen to fit within the [`Infinite`]'s
+/// coordinate system.
+fn translate_cursor(bounds: Rectangle, view: View, point: Point) -> Point {
+    let point = view.to_canvas(point - bounds.center() + view.offset);
+
+    Point::new(
+        (view.origin.x + f64::from(point.x)) as f32,
+        (view.origin.y + f64::from(point.y)) as f32,
+    )
+}
+
+/// Rotates a `vector` on the screen counter-clockwise by `angle` radians.
+fn rotate(vector: Vector, angle: f32) -> Vector {
+    let (sin, cos) = angle.sin_cos();
+
+    Vector::new(
+        vector.x * cos + vector.y * sin,
+        vector.y * cos - vector.x * sin,
+    )
+}
+
+/// Converts a point given as (radius, angle) to cartesian co-ordinates.
+fn polar_point(point: Point) -> Point {
+    let (sin, cos) = point.y.sin_cos();
+
+    Point::new(point.x * cos, point.x * sin)
+}
+
+/// Converts a `path` with points given as (radius, angle) to cartesian
+/// co-ordinates, drawn at the given `scale`.
+///
+/// Lines are split into enough straight segments to follow their curve on
+/// the screen.
+fn polar_path(path: &Path, scale: f32) -> Path {
+    use iced::widget::canvas::path::lyon_path::{self, iterator::PathIterator, Event as PathEvent};
+
+    let to_point = |point: lyon_path::math::Point| Point::new(point.x, point.y);
+
+    Path::new(|builder| {
+        let mut start = Point::ORIGIN;
+
+        for event in path.raw().iter().flattened(POLAR_TOLERANCE / scale) {
+            match event {
+                PathEvent::Begin { at } => {
+                    start = to_point(at);
+                    builder.move_to(polar_point(start));
+                }
+                PathEvent::Line { from, to } => {
+                    sweep_polar(builder, to_point(from), to_point(to), scale);
+                }
+                PathEvent::End { last, close, .. } => {
+                    if close {
+                        sweep_polar(builder, to_point(last), start, scale);
+                        builder.close()
+                    }
+                }
+                PathEvent::Quadratic { .. } | PathEvent::Cubic { .. } => {}
+            }
+        }
+    })
+}
+
+/// Returns the moment the dashes of all marching ants started moving from, so
+/// they move in step.
+fn marching_ants_epoch() -> Instant {
+    static EPOCH: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
+
+    *EPOCH.get_or_init(Instant::now)
+}
+
+/// Splits a `path` into dashes of the given `segments`, alternating between
+/// dashes and gaps, with the pattern moved back by `phase`.
+fn dash(path: &Path, segments: &[f32], phase: f32) -> Path {
+    use iced::widget::canvas::path::lyon_path::{iterator::PathIterator, Event as PathEvent};
+
+    // An odd number of segments alternates between dashes and gaps on repeat
+    let segments = if segments.len() % 2 == 1 {
+        [segments, segments].concat()
+    } else {
+        segments.to_vec()
+    };
+    let period: f32 = segments.iter().sum();
+
+    if !(period.is_finite() && period > 0.0) || segments.iter().any(|segment| *segment < 0.0) {
+        return path.clone();
+    }
+
+    // Find where in the pattern the path starts
+    let mut index = 0;
+    let mut remaining = segments[0];
+    let mut offset = phase.rem_euclid(period);
+
+    while offset >= remaining {
+        offset -= remaining;
+        index = (index + 1) % segments.len();
+        remaining = segments[index];
+    }
+
+    remaining -= offset;
+
+    Path::new(|builder| {
+        let mut drawing = false;
+        let mut start = Point::ORIGIN;
+
+        let mut line = |builder: &mut path::Builder, from: Point, to: Point, drawing: &mut bool| {
+            let length = from.distance(to);
+            let mut travelled = 0.0;
+
+            while travelled < length {
+                let step = remaining.min(length - travelled);
+                let at = |distance: f32| from + (to - from) * (distance / length);
+
+                if index % 2 == 0 {
+                    if !*drawing {
+                        builder.move_to(at(travelled));
+                        *drawing = true;
+                    }
+
+                    builder.line_to(at(travelled + step));
+                }
 
-            if let Some(message) = message {
-                shell.publish(message);
+                travelled += step;
+                remaining -= step;
+
+                if remaining <= 0.0 {
+                    index = (index + 1) % segments.len();
+                    remaining = segments[index];
+                    *drawing = false;
+                }
             }
+        };
 
-            if status == event::Status::Captured {
-                return status.into();
+        for event in path.raw().iter().flattened(DASH_TOLERANCE) {
+            match event {
+                PathEvent::Begin { at } => {
+                    start = Point::new(at.x, at.y);
+                    drawing = false;
+                }
+                PathEvent::Line { from, to } => line(
+                    builder,
+                    Point::new(from.x, from.y),
+                    Point::new(to.x, to.y),
+                    &mut drawing,
+                ),
+                PathEvent::End { last, close, .. } => {
+                    if close {
+                        line(builder, Point::new(last.x, last.y), start, &mut drawing);
+                    }
+                }
+                PathEvent::Quadratic { .. } | PathEvent::Cubic { .. } => {}
             }
         }
+    })
+}
 
-        if !cursor.is_over(bounds) {
-            return iced_event::Status::Ignored;
-        }
+/// Draws a line between two points given as (radius, angle), sweeping both
+/// its radius and its angle.
+fn sweep_polar(builder: &mut path::Builder, from: Point, to: Point, scale: f32) {
+    // The sagitta of an arc with a short enough angle is within the tolerance
+    let radius = from.x.abs().max(to.x.abs()) * scale;
+    let step = (8.0 * POLAR_TOLERANCE / radius).sqrt();
+    let segments = ((to.y - from.y).abs() / step)
+        .ceil()
+        .clamp(1.0, MAX_POLAR_SEGMENTS);
+
+    for segment in 1..=segments as usize {
+        let t = segment as f32 / segments;
+        let point = Point::new(from.x + (to.x - from.x) * t, from.y + (to.y - from.y) * t);
+
+        builder.line_to(polar_point(point));
+    }
+}
 
-        match event {
-            iced::Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
-                let state = state.state.downcast_mut::<InfiniteState<P::State>>();
-                let (cursor, infinite) = get_cursors(cursor, bounds, state.offset, state.scale);
-                let modifiers = state.keyboard_modifier;
-                let scale_step = self.scale_step.unwrap_or(SCALE_STEP);
+/// Scales the alpha of the colors of a `style` by `opacity`.
+fn fade_style(style: fill::Style, opacity: f32) -> fill::Style {
+    match style {
+        fill::Style::Solid(color) => fill::Style::Solid(color.scale_alpha(opacity)),
+        fill::Style::Gradient(Gradient::Linear(mut linear)) => {
+            for stop in linear.stops.iter_mut().flatten() {
+                stop.color = stop.color.scale_alpha(opacity);
+            }
 
-                match delta {
-                    // Zoom
-                    mouse::ScrollDelta::Lines { y, .. }
-                        if modifiers.shift() && modifiers.command() =>
-                    {
-                        if !self.allow_scale {
-                            return iced_event::Status::Ignored;
-                        };
-                        let step = if y < 0. { -scale_step } else { scale_step };
-                        handle_scale(self, state, shell, bounds, (cursor, infinite), step, true)
-                    }
-                    mouse::ScrollDelta::Pixels { y, .. }
-                        if modifiers.shift() && modifiers.command() =>
-                    {
-                        if !self.allow_scale {
-                            return iced_event::Status::Ignored;
-                        };
-                        let step = if y < 0. { -scale_step } else { scale_step };
-                        handle_scale(self, state, shell, bounds, (cursor, infinite), step, true)
-                    }
-                    mouse::ScrollDelta::Lines { y, .. } if modifiers.shift() => {
-                        if !self.allow_scale {
-                            return iced_event::Status::Ignored;
-                        };
-                        let step = if y < 0. { -scale_step } else { scale_step };
-                        handle_scale(self, state, shell, bounds, (cursor, infinite), step, false)
-                    }
-                    mouse::ScrollDelta::Pixels { y, .. } if modifiers.shift() => {
-                        if !self.allow_scale {
-                            return iced_event::Status::Ignored;
-                        };
-                        let step = if y < 0. { -scale_step } else { scale_step };
-                        handle_scale(self, state, shell, bounds, (cursor, infinite), step, false)
-                    }
+            fill::Style::Gradient(Gradient::Linear(linear))
+        }
+    }
+}
 
-                    // Translation
-                    mouse::ScrollDelta::Pixels { x, y } => {
-                        let (x, y) = match self.offset_step {
-                            Some(offset) => (offset.x, offset.y),
-                            None => (x, y),
-                        };
-                        let offset = match self.direction {
-                            ScrollDirection::X => Vector::new(x, 0.),
-                            ScrollDirection::Y => Vector::new(0., y),
-                            ScrollDirection::Both => Vector::new(x, y),
-                            ScrollDirection::None => return iced_event::Status::Ignored,
-                        };
+/// Returns a copy of the `path` placed in the `view` with the `anchor`.
+///
+/// The view is applied to the points of the path rather than through the
+/// transforms of the [`Frame`], which copy every path they transform all the
+/// same. Neither can it be applied to tessellated geometry, as a
+/// [`Transformation`] cannot flip the Y axis, rotate, or scale a single axis.
+/// Only the geometry of cached [`Buffer`]s is kept between frames and moved
+/// by a [`Transformation`] as the canvas is scrolled, see [`Buffer::cache`].
+fn transform_path(view: &View, center: Point, path: &Path, anchor: Anchor, scale: bool) -> Path {
+    use path::lyon_path::math;
+
+    let (coarse, transform) = view_transform(view, center, anchor, scale);
+
+    if coarse == Vector::ZERO {
+        return path.transform(&transform);
+    }
 
-                        state.offset = state.offset - offset;
-                        let msg = self.program.on_scroll(
-                            &mut state.state,
-                            bounds,
-                            cursor,
-                            infinite,
-                            state.offset,
-                            -offset,
-                        );
+    // Points near the origin are moved to it on their own, as the precision
+    // of their coordinates would otherwise be lost in the larger transform.
+    // Both are applied in a single pass, copying the path only once.
+    map_path(path, |point| {
+        let point = transform.transform_point(math::point(point.x - coarse.x, point.y - coarse.y));
 
-                        if let Some(msg) = msg {
-                            shell.publish(msg);
-                        }
+        Point::new(point.x, point.y)
+    })
+}
 
-                        iced_event::Status::Captured
-                    }
-                    mouse::ScrollDelta::Lines { x, y } => {
-                        let (x, y) = match self.offset_step {
-                            Some(offset) => (offset.x, offset.y),
-                            None => (x, y),
-                        };
-                        let mult = 100.0;
-                        let offset = match self.direction {
-                            ScrollDirection::X => Vector::new(x, 0.),
-                            ScrollDirection::Y => Vector::new(0., y),
-                            ScrollDirection::Both => Vector::new(x, y),
-                            ScrollDirection::None => return iced_event::Status::Ignored,
-                        } * mult;
+/// Returns a copy of the `path` with each of its points moved by `place`.
+fn map_path(path: &Path, place: impl Fn(Point) -> Point) -> Path {
+    Path::new(|builder| append_path(builder, path, place))
+}
 
-                        state.offset = state.offset - offset;
-                        let msg = self.program.on_scroll(
-                            &mut state.state,
-                            bounds,
-                            cursor,
-                            infinite,
-                            state.offset,
-                            -offset,
-                        );
+/// Adds the segments of the `path` to the `builder`, with each of its points
+/// moved by `place`.
+fn append_path(builder: &mut path::Builder, path: &Path, place: impl Fn(Point) -> Point) {
+    use path::lyon_path::Event as PathEvent;
 
-                        if let Some(msg) = msg {
-                            shell.publish(msg);
-                        }
+    let place = |point: path::lyon_path::math::Point| place(Point::new(point.x, point.y));
 
-                        iced_event::Status::Captured
-                    }
+    for event in path.raw().iter() {
+        match event {
+            PathEvent::Begin { at } => builder.move_to(place(at)),
+            PathEvent::Line { to, .. } => builder.line_to(place(to)),
+            PathEvent::Quadratic { ctrl, to, .. } => {
+                builder.quadratic_curve_to(place(ctrl), place(to))
+            }
+            PathEvent::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => builder.bezier_curve_to(place(ctrl1), place(ctrl2), place(to)),
+            PathEvent::End { close, .. } => {
+                if close {
+                    builder.close()
                 }
             }
+        }
+    }
+}
 
-            iced::Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
-                let state = state.state.downcast_mut::<InfiniteState<P::State>>();
-                let (cursor, infinite) = get_cursors(cursor, bounds, state.offset, state.scale);
-                let (offset_x, offset_y) = match self.offset_step {
-                    Some(offset) => (offset.x, offset.y),
-                    None => (OFFSET_STEP, OFFSET_STEP),
-                };
-                let scale_step = self.scale_step.unwrap_or(SCALE_STEP);
-
-                match key {
-                    // Zoom
-                    keyboard::Key::Named(keyboard::key::Named::ArrowUp)
-                        if modifiers.shift() && modifiers.command() =>
-                    {
-                        if !self.allow_scale {
-                            return iced_event::Status::Ignored;
-                        };
-                        handle_scale(
-                            self,
-                            state,
-                            shell,
-                            bounds,
-                            (cursor, infinite),
-                            scale_step,
-                            true,
-                        )
-                    }
+/// Returns the transform which places items with the `anchor` in the `view`,
+/// and the translation to apply on its own before it.
+fn view_transform(view: &View, center: Point, anchor: Anchor, scale: bool) -> (Vector, Transform) {
+    let offset = match anchor {
+        Anchor::None => view.offset,
+        Anchor::X => Vector::new(0., view.offset.y),
+        Anchor::Y => Vector::new(view.offset.x, 0.),
+        Anchor::Both => Vector::new(0., 0.),
+        Anchor::Parallax(factor) => Vector::new(view.offset.x * factor.x, view.offset.y * factor.y),
+        Anchor::Screen(horizontal, vertical) => {
+            let origin = screen_origin(center, horizontal, vertical);
+            let sign = view.y_axis.sign();
+
+            return (
+                Vector::ZERO,
+                Transform2D::new(1.0, 0.0, 0.0, sign, origin.x, origin.y),
+            );
+        }
+    };
+    let (coarse, fine) = split_origin(view.origin, anchor);
+    let scale = if scale {
+        view.scale
+    } else {
+        Vector::new(1.0, 1.0)
+    };
 
-                    keyboard::Key::Named(keyboard::key::Named::ArrowDown)
-                        if modifiers.shift() && modifiers.command() =>
-                    {
-                        if !self.allow_scale {
-                            return iced_event::Status::Ignored;
-                        };
-                        handle_scale(
-                            self,
-                            state,
-                            shell,
-                            bounds,
-                            (cursor, infinite),
-                            -scale_step,
-                            true,
-                        )
-                    }
+    let (sin, cos) = view.rotation.sin_cos();
+    let sign = view.y_axis.sign();
 
-                    keyboard::Key::Named(keyboard::key::Named::ArrowUp) if modifiers.shift() => {
-                        if !self.allow_scale {
-                            return iced_event::Status::Ignored;
-                        };
-                        handle_scale(
-                            self,
-                            state,
-                            shell,
-                            bounds,
-                            (cursor, infinite),
-                            scale_step,
-                            false,
-                        )
-                    }
+    let center = center
+        - offset
+        - rotate(
+            Vector::new(fine.x * scale.x, sign * fine.y * scale.y),
+            view.rotation,
+        );
 
-                    keyboard::Key::Named(keyboard::key::Named::ArrowDown) if modifiers.shift() => {
-                        if !self.allow_scale {
-                            return iced_event::Status::Ignored;
-                        };
-                        handle_scale(
-                            self,
-                            state,
-                            shell,
-                            bounds,
-                            (cursor, infinite),
-                            -scale_step,
-                            false,
-                        )
-                    }
+    let transform = Transform2D::new(
+        scale.x * cos,
+        -scale.x * sin,
+        sign * scale.y * sin,
+        sign * scale.y * cos,
+        center.x,
+        center.y,
+    );
 
-                    // Translations
-                    keyboard::Key::Named(keyboard::key::Named::ArrowUp) if modifiers.command() => {
-                        let offset = match self.direction {
-                            ScrollDirection::X => Vector::new(0., 0.),
-                            ScrollDirection::Y => Vector::new(0., offset_y),
-                            ScrollDirection::Both => Vector::new(0., offset_y),
-                            ScrollDirection::None => return iced_event::Status::Ignored,
-                        } * (1.0 / state.scale);
+    (coarse, transform)
+}
 
-                        state.offset = state.offset - offset;
-                        let msg = self.program.on_scroll(
-                            &mut state.state,
-                            bounds,
-                            cursor,
-                            infinite,
-                            state.offset,
-                            -offset,
-                        );
+/// Splits the `origin` of a view into the nearest point and the remaining
+/// distance from it, dropping the axes fixed by the `anchor` and scaling
+/// those of a parallax.
+fn split_origin(origin: Point<f64>, anchor: Anchor) -> (Vector, Vector) {
+    let (x, y) = match anchor {
+        Anchor::None => (origin.x, origin.y),
+        Anchor::X => (0.0, origin.y),
+        Anchor::Y => (origin.x, 0.0),
+        Anchor::Both | Anchor::Screen(..) => (0.0, 0.0),
+        Anchor::Parallax(factor) => (
+            origin.x * f64::from(factor.x),
+            origin.y * f64::from(factor.y),
+        ),
+    };
 
-                        if let Some(msg) = msg {
-                            shell.publish(msg);
-                        }
+    let coarse = Vector::new(x as f32, y as f32);
+    let fine = Vector::new(
+        (x - f64::from(coarse.x)) as f32,
+        (y - f64::from(coarse.y)) as f32,
+    );
 
-                        iced_event::Status::Captured
-                    }
+    (coarse, fine)
+}
 
-                    keyboard::Key::Named(keyboard::key::Named::ArrowDown)
-                        if modifiers.command() =>
-                    {
-                        let offset = match self.direction {
-                            ScrollDirection::X => Vector::new(0., 0.),
-                            ScrollDirection::Y => Vector::new(0., offset_y),
-                            ScrollDirection::Both => Vector::new(0., offset_y),
-                            ScrollDirection::None => return iced_event::Status::Ignored,
-                        } * (1.0 / state.scale);
-                        state.offset = state.offset + offset;
+fn translate_point(view: &View, center: Point, point: impl Into<Point>, anchor: Anchor) -> Point {
+    let offset = match anchor {
+        Anchor::Both => Vector::new(0., 0.),
+        Anchor::X => Vector::new(0., view.offset.y),
+        Anchor::Y => Vector::new(view.offset.x, 0.),
+        Anchor::None => view.offset,
+        Anchor::Parallax(factor) => Vector::new(view.offset.x * factor.x, view.offset.y * factor.y),
+        Anchor::Screen(horizontal, vertical) => {
+            let point = point.into();
+
+            return screen_origin(center, horizontal, vertical)
+                + Vector::new(point.x, view.y_axis.sign() * point.y);
+        }
+    };
+    let center = center - offset;
+    let point = {
+        let (coarse, fine) = split_origin(view.origin, anchor);
+        let point = point.into() - coarse - fine;
+        Vector::new(
+            point.x * view.scale.x,
+            view.y_axis.sign() * point.y * view.scale.y,
+        )
+    };
 
-                        let msg = self.program.on_scroll(
-                            &mut state.state,
-                            bounds,
-                            cursor,
-                            infinite,
-                            state.offset,
-                            offset,
-                        );
+    center + rotate(point, view.rotation)
+}
 
-                        if let Some(msg) = msg {
-                            shell.publish(msg);
-                        }
+/// Returns copies of the `motif` every `spacing` units along each axis which
+/// cover the `region`, at most [`MAX_PATTERN_COPIES`] of them.
+fn repeat_pattern(motif: &Path, spacing: Size, region: Rectangle) -> Path {
+    let Some(bounds) = path_bounds(motif) else {
+        return Path::new(|_| {});
+    };
 
-                        iced_event::Status::Captured
-                    }
+    if !(spacing.width > 0.0 && spacing.height > 0.0) {
+        return Path::new(|_| {});
+    }
 
-                    keyboard::Key::Named(keyboard::key::Named::ArrowLeft)
-                        if modifiers.command() =>
-                    {
-                        let offset = match self.direction {
-                            ScrollDirection::X => Vector::new(offset_x, 0.),
-                            ScrollDirection::Y => Vector::new(0., 0.),
-                            ScrollDirection::Both => Vector::new(offset_x, 0.),
-                            ScrollDirection::None => return iced_event::Status::Ignored,
-                        } * (1.0 / state.scale);
-                        state.offset = state.offset - offset;
+    // The copies whose motif overlaps the region along an axis
+    let copies = |from: f32, length: f32, start: f32, extent: f32, step: f32| {
+        let first = ((from - start - extent) / step).floor() as i64;
+        let last = ((from + length - start) / step).ceil() as i64;
 
-                        let msg = self.program.on_scroll(
-                            &mut state.state,
-                            bounds,
-                            cursor,
-                            infinite,
-                            state.offset,
-                            -offset,
-                        );
+        first..=last
+    };
 
-                        if let Some(msg) = msg {
-                            shell.publish(msg);
-                        }
+    let columns = copies(
+        region.x,
+        region.width,
+        bounds.x,
+        bounds.width,
+        spacing.width,
+    );
+    let rows = copies(
+        region.y,
+        region.height,
+        bounds.y,
+        bounds.height,
+        spacing.height,
+    );
 
-                        iced_event::Status::Captured
-                    }
-                    keyboard::Key::Named(keyboard::key::Named::ArrowRight)
-                        if modifiers.command() =>
-                    {
-                        let offset = match self.direction {
-                            ScrollDirection::X => Vector::new(offset_x, 0.),
-                            ScrollDirection::Y => Vector::new(0., 0.),
-                            ScrollDirection::Both => Vector::new(offset_x, 0.),
-                            ScrollDirection::None => return iced_event::Status::Ignored,
-                        } * (1.0 / state.scale);
-                        state.offset = state.offset + offset;
+    Path::new(|builder| {
+        let mut count = 0;
 
-                        let msg = self.program.on_scroll(
-                            &mut state.state,
-                            bounds,
-                            cursor,
-                            infinite,
-                            state.offset,
-                            offset,
-                        );
+        'rows: for row in rows {
+            for column in columns.clone() {
+                let shift = Vector::new(column as f32 * spacing.width, row as f32 * spacing.height);
 
-                        if let Some(msg) = msg {
-                            shell.publish(msg);
-                        }
-                        iced_event::Status::Captured
-                    }
+                append_path(builder, motif, |point| point + shift);
 
-                    // Resets
-                    keyboard::Key::Named(keyboard::key::Named::Home) if modifiers.command() => {
-                        let init_offset = self.program.init_scroll();
-                        let init_scale = self.program.init_zoom();
+                count += 1;
 
-                        state.reset_all(init_offset, init_scale);
+                if count >= MAX_PATTERN_COPIES {
+                    break 'rows;
+                }
+            }
+        }
+    })
+}
 
-                        if let Some(msg) = self.program.on_scroll_reset(
-                            &mut state.state,
-                            bounds,
-                            cursor,
-                            infinite,
-                            init_offset,
-                        ) {
-                            shell.publish(msg);
-                        }
+/// Returns the point of a pane around `center`, whose top-left corner is at
+/// the origin, which the points of items with an [`Anchor::Screen`] are
+/// relative to.
+fn screen_origin(
+    center: Point,
+    horizontal: alignment::Horizontal,
+    vertical: alignment::Vertical,
+) -> Point {
+    let x = match horizontal {
+        alignment::Horizontal::Left => 0.0,
+        alignment::Horizontal::Center => center.x,
+        alignment::Horizontal::Right => 2.0 * center.x,
+    };
+    let y = match vertical {
+        alignment::Vertical::Top => 0.0,
+        alignment::Vertical::Center => center.y,
+        alignment::Vertical::Bottom => 2.0 * center.y,
+    };
 
-                        if let Some(msg) = self.program.on_zoom_reset(
-                            &mut state.state,
-                            bounds,
-                            cursor,
-                            infinite,
-                            init_scale,
-                        ) {
-                            shell.publish(msg);
-                        }
+    Point::new(x, y)
+}
 
-                        iced_event::Status::Captured
-                    }
+fn transform_text(view: &View, center: Point, text: &Text, anchor: Anchor) -> Text {
+    //dbg!(&text.content);
+    //dbg!(text.position);
+    let position = translate_point(view, center, text.position, anchor);
+    //dbg!(position);
 
-                    keyboard::Key::Named(keyboard::key::Named::Home) if modifiers.shift() => {
-                        let init = self.program.init_zoom();
-                        state.reset_scale(init);
+    Text {
+        content: text.content.clone(),
+        position,
+        size: text.size,
+        color: text.color,
+        font: text.font,
+        horizontal_alignment: text.horizontal_alignment,
+        vertical_alignment: text.vertical_alignment,
+        line_height: text.line_height,
+        shaping: text.shaping,
+    }
+}
 
-                        let msg = self.program.on_zoom_reset(
-                            &mut state.state,
-                            bounds,
-                            cursor,
-                            infinite,
-                            state.scale,
-                        );
+/// Zooms and scrolls the [`Infinite`] as one of two fingers moves from
+/// `moved.0` to `moved.1` while the other finger rests at `other`.
+///
+/// The change in distance between the fingers zooms around the midpoint of
+/// the fingers, while the movement of the midpoint scrolls. Twisting the
+/// fingers rotates around the midpoint if [`Infinite::rotate`] is enabled.
+fn handle_pinch<P, Message, Theme, Renderer>(
+    canvas: &Infinite<P, Message, Theme, Renderer>,
+    state: &mut InfiniteState<P::State>,
+    shell: &mut advanced::Shell<'_, Message>,
+    bounds: Rectangle,
+    moved: (Point, Point),
+    other: Point,
+) -> iced_event::Status
+where
+    Theme: Catalog,
+    P: Program<Message, Theme, Renderer>,
+    Renderer: geometry::Renderer,
+{
+    let (previous, current) = moved;
 
-                        if let Some(msg) = msg {
-                            shell.publish(msg);
-                        }
-                        iced_event::Status::Captured
-                    }
+    let previous_mid = Point::new((previous.x + other.x) / 2.0, (previous.y + other.y) / 2.0);
+    let current_mid = Point::new((current.x + other.x) / 2.0, (current.y + other.y) / 2.0);
 
-                    keyboard::Key::Named(keyboard::key::Named::Home) => {
-                        let init = self.program.init_scroll();
-                        state.reset_offset(init);
+    let diff = current_mid - previous_mid;
+    let scroll = match canvas.direction {
+        ScrollDirection::X => Vector::new(diff.x, 0.),
+        ScrollDirection::Y => Vector::new(0., diff.y),
+        ScrollDirection::Both => diff,
+        ScrollDirection::None => Vector::ZERO,
+    };
 
-                        let msg = self.program.on_scroll_reset(
-                            &mut state.state,
-                            bounds,
-                            cursor,
-                            infinite,
-                            init,
-                        );
+    if scroll != Vector::ZERO {
+        canvas.scroll_by(
+            state,
+            shell,
+            bounds,
+            Cursor::Available(current_mid),
+            -scroll,
+        );
+    }
 
-                        if let Some(msg) = msg {
-                            shell.publish(msg);
-                        }
+    if canvas.allow_rotation {
+        let angle = |finger: Point| (finger.y - other.y).atan2(finger.x - other.x);
 
-                        iced_event::Status::Captured
-                    }
+        // The screen's y axis points down, so a counter-clockwise twist
+        // decreases the angle
+        let twist = angle(previous) - angle(current);
+        let twist = (twist + PI).rem_euclid(2.0 * PI) - PI;
 
-                    _ => iced_event::Status::Ignored,
-                }
+        if twist != 0.0 {
+            let pivot = current_mid - bounds.center();
+            let diff = state.rotate_around(twist, pivot);
+            let cursors = get_cursors(
+                Cursor::Available(current_mid),
+                bounds,
+                canvas.pointer_view(state),
+            );
+            let context = InputContext::new(bounds, cursors, state.keyboard_modifier);
+
+            let scroll = state.scroll();
+            if let Some(msg) = canvas
+                .program
+                .on_scroll(&mut state.state, context, scroll, diff)
+            {
+                shell.publish(msg);
             }
+        }
+    }
 
-            iced::Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
-                let state = state.state.downcast_mut::<InfiniteState<P::State>>();
-                state.keyboard_modifier = modifiers;
+    let previous_distance = previous.distance(other);
+    let current_distance = current.distance(other);
 
-                iced_event::Status::Captured
-            }
+    if !canvas.allow_scale || previous_distance <= 0.0 || current_distance <= 0.0 {
+        return iced_event::Status::Captured;
+    }
 
-            iced::Event::Mouse(mouse::Event::CursorMoved { .. }) => {
-                let state = state.state.downcast_mut::<InfiniteState<P::State>>();
-                let (_, cursor) = get_cursors(cursor, bounds, state.offset, state.scale);
+    let cursors = get_cursors(
+        Cursor::Available(current_mid),
+        bounds,
+        canvas.pointer_view(state),
+    );
+    state.set_mouse_position(cursors.1.position());
 
-                state.set_mouse_position(cursor.position());
+    let zoom = (current_distance / previous_distance).ln();
 
-                iced_event::Status::Captured
-            }
+    handle_scale(canvas, state, shell, bounds, cursors, zoom, false)
+}
 
-            iced::Event::Mouse(mouse::Event::CursorLeft) => {
-                let state = state.state.downcast_mut::<InfiniteState<P::State>>();
-                state.set_mouse_position(None);
+/// Scrolls and zooms so the `world` rectangle, given by its bottom-left
+/// corner, fills the [`Infinite`] but for `padding` on each side.
+fn handle_fit<P, Message, Theme, Renderer>(
+    canvas: &Infinite<P, Message, Theme, Renderer>,
+    state: &mut InfiniteState<P::State>,
+    shell: &mut advanced::Shell<'_, Message>,
+    bounds: Rectangle,
+    cursors: (Cursor, Cursor),
+    world: Rectangle,
+    padding: f32,
+) -> iced_event::Status
+where
+    Theme: Catalog,
+    P: Program<Message, Theme, Renderer>,
+    Renderer: geometry::Renderer,
+{
+    canvas.begin_gesture(state, shell, bounds, Gesture::Zoom);
+    canvas.begin_gesture(state, shell, bounds, Gesture::Scroll);
 
-                iced_event::Status::Captured
-            }
+    let padding = padding.max(0.0);
+    let size = Size::new(
+        (bounds.width - 2.0 * padding).max(1.0),
+        (bounds.height - 2.0 * padding).max(1.0),
+    );
 
-            _ => iced_event::Status::Ignored,
-        }
-    }
+    let (zoom, offset_diff) = state.fit(size, world, canvas.zoom_axes);
 
-    fn mouse_interaction(
-        &self,
-        state: &tree::Tree,
-        layout: layout::Layout<'_>,
-        cursor: advanced::mouse::Cursor,
-        _viewport: &Rectangle,
-        _renderer: &Renderer,
-    ) -> advanced::mouse::Interaction {
-        let bounds = layout.bounds();
-        let state = &state.state.downcast_ref::<InfiniteState<P::State>>();
-        let (cursor, infinite) = get_cursors(cursor, bounds, state.offset, state.scale);
+    let context = InputContext::new(bounds, cursors, state.keyboard_modifier);
 
-        self.program
-            .mouse_interaction(&state.state, bounds, cursor, infinite)
+    if zoom != 0.0 {
+        if let Some(msg) = canvas.program.on_zoom(
+            &mut state.state,
+            context,
+            world.center(),
+            canvas.zoom_axes.get(state.scale),
+            zoom,
+        ) {
+            shell.publish(msg);
+        }
     }
 
-    fn layout(
-        &self,
-        _tree: &mut iced::advanced::widget::Tree,
-        _renderer: &Renderer,
-        limits: &iced::advanced::layout::Limits,
-    ) -> layout::Node {
-        layout::atomic(limits, self.width, self.height)
+    if offset_diff != Vector::ZERO {
+        let scroll = state.scroll();
+        if let Some(msg) = canvas
+            .program
+            .on_scroll(&mut state.state, context, scroll, offset_diff)
+        {
+            shell.publish(msg);
+        }
     }
 
-    fn draw(
-        &self,
-        tree: &iced::advanced::widget::Tree,
-        renderer: &mut Renderer,
-        theme: &Theme,
-        _style: &iced::advanced::renderer::Style,
-        layout: layout::Layout<'_>,
-        cursor: iced::advanced::mouse::Cursor,
-        _viewport: &iced::Rectangle,
-    ) {
-        let bounds = layout.bounds();
-        let is_mouse_over = cursor.is_over(bounds);
+    iced_event::Status::Captured
+}
 
-        if bounds.width < 1.0 || bounds.height < 1.0 {
-            return;
-        }
+/// Returns the rectangle with the opposite corners `a` and `b`.
+fn rectangle_between(a: Point, b: Point) -> Rectangle {
+    let top_left = Point::new(a.x.min(b.x), a.y.min(b.y));
+    let size = Size::new((a.x - b.x).abs(), (a.y - b.y).abs());
 
-        let status = if is_mouse_over {
-            Status::Hovered
-        } else {
-            Status::Active
-        };
+    Rectangle::new(top_left, size)
+}
 
-        let style = theme.style(&self.style, status);
+/// Zooms to the scale `level`s around the center of the view.
+fn handle_zoom_to<P, Message, Theme, Renderer>(
+    canvas: &Infinite<P, Message, Theme, Renderer>,
+    state: &mut InfiniteState<P::State>,
+    shell: &mut advanced::Shell<'_, Message>,
+    bounds: Rectangle,
+    cursors: (Cursor, Cursor),
+    level: Vector,
+) -> iced_event::Status
+where
+    Theme: Catalog,
+    P: Program<Message, Theme, Renderer>,
+    Renderer: geometry::Renderer,
+{
+    canvas.begin_gesture(state, shell, bounds, Gesture::Zoom);
+    canvas.begin_gesture(state, shell, bounds, Gesture::Scroll);
 
-        let state = tree.state.downcast_ref::<InfiniteState<P::State>>();
+    let zoom = canvas.zoom_axes.get(level - state.scale_level);
+    let offset_diff = state.zoom_centered(level);
+    let focal_point = translate_cursor(bounds, state.view(), bounds.center());
 
-        renderer.fill_quad(
-            advanced::renderer::Quad {
-                bounds,
-                border: style.border,
-                shadow: Shadow::default(),
-            },
-            style.background,
-        );
+    let context = InputContext::new(bounds, cursors, state.keyboard_modifier);
+    let msg = canvas.program.on_zoom(
+        &mut state.state,
+        context,
+        focal_point,
+        canvas.zoom_axes.get(state.scale),
+        zoom,
+    );
 
-        let border_width = style.border.width;
+    if let Some(msg) = msg {
+        shell.publish(msg);
+    }
 
-        let bounds = {
-            let width = bounds.width - (2. * border_width);
-            let height = bounds.height - (2.0 * border_width);
+    let scroll = state.scroll();
+    if let Some(msg) = canvas
+        .program
+        .on_scroll(&mut state.state, context, scroll, offset_diff)
+    {
+        shell.publish(msg);
+    }
 
-            let position = bounds.position();
+    iced_event::Status::Captured
+}
 
-            let top_left = Point::new(position.x + border_width, position.y + border_width);
+fn handle_scale<P, Message, Theme, Renderer>(
+    canvas: &Infinite<P, Message, Theme, Renderer>,
+    state: &mut InfiniteState<P::State>,
+    shell: &mut advanced::Shell<'_, Message>,
+    bounds: Rectangle,
+    cursors: (Cursor, Cursor),
+    zoom: f32,
+    focal_origin: bool,
+) -> iced::event::Status
+where
+    Theme: Catalog,
+    P: Program<Message, Theme, Renderer>,
+    Renderer: geometry::Renderer,
+{
+    canvas.begin_gesture(state, shell, bounds, Gesture::Zoom);
+    canvas.begin_gesture(state, shell, bounds, Gesture::Scroll);
+
+    // The focal point is where the cursor is on the drawn view, which lags
+    // behind while the camera moves
+    let aligned = match (focal_origin, cursors.0.position(), state.mouse_position) {
+        (false, Some(cursor), Some(point)) if canvas.pointer_view(state) != state.view() => {
+            state.align(bounds, point, cursor)
+        }
+        _ => Vector::ZERO,
+    };
 
-            Rectangle::new(top_left, Size::new(width, height))
-        };
+    let offset_diff = aligned + state.add_level(zoom, canvas.zoom_axes, focal_origin);
+    let focal_point = if focal_origin {
+        Point::ORIGIN
+    } else {
+        state.mouse_position.unwrap_or(Point::ORIGIN)
+    };
 
-        let position = bounds.position();
+    let context = InputContext::new(bounds, cursors, state.keyboard_modifier);
+    let msg = canvas.program.on_zoom(
+        &mut state.state,
+        context,
+        focal_point,
+        canvas.zoom_axes.get(state.scale),
+        zoom,
+    );
 
-        renderer.with_translation(Vector::new(position.x, position.y), |renderer| {
-            let mut frame = Frame::new(renderer, bounds.size());
-            let center = frame.center();
+    if let Some(msg) = msg {
+        shell.publish(msg);
+    }
 
-            let (cursor, infinite) = get_cursors(cursor, bounds, state.offset, state.scale);
+    let scroll = state.scroll();
+    if let Some(msg) = canvas
+        .program
+        .on_scroll(&mut state.state, context, scroll, offset_diff)
+    {
+        shell.publish(msg);
+    }
 
-            let buffers = self.program.draw(
-                &state.state,
-                theme,
-                bounds,
-                cursor,
-                infinite,
-                Point::ORIGIN - state.offset,
-            );
+    iced_event::Status::Captured
+}
 
-            for buffer in buffers {
-                buffer.draw(&mut frame, state, center);
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            let top = 2.5;
-            let left = 8.0;
-            let details_padding = {
-                let bottom = 2.5;
-                let right = 8.0;
-                Size::new(left + right, top + bottom)
-            };
-            let details_bounds = Size::INFINITY;
-            let details_size = 16.0;
+    use iced::futures::{executor::block_on, StreamExt};
+    use iced_runtime::{task, Action};
 
-            if state.scale_level != 0.0 {
-                let pos = (bounds.width * 0.9, bounds.height * 0.95).into();
-                let background = style.details_background;
-                let radius = style.details_border_radius;
-                let color = style.details_text;
+    struct Empty;
 
-                let scale = (state.scale_level) * 100.;
+    impl Program<(), Theme, ()> for Empty {
+        type State = ();
 
-                let scale_string = format!("{:.0}%", scale);
-                let min_bounds = min_text_bounds(&scale_string, details_bounds, details_size);
-                let bounds = min_bounds.expand(details_padding);
+        fn init_state(&self) -> Self::State {}
 
-                let rect = Path::rounded_rectangle(pos, bounds, radius);
+        fn draw<'a>(&self, _state: &(), _theme: &Theme, _context: DrawContext) -> Vec<Buffer<'a>> {
+            Vec::new()
+        }
+    }
 
-                frame.fill(&rect, background);
+    /// Runs the widget operation of `task` on `element` as the runtime would,
+    /// returning its output.
+    fn run<T>(
+        element: &Element<'_, (), Theme, ()>,
+        tree: &mut tree::Tree,
+        task: Task<T>,
+    ) -> Option<T> {
+        let mut stream = task::into_stream(task)?;
+        let Some(Action::Widget(mut operation)) = block_on(stream.next()) else {
+            return None;
+        };
 
-                let text = Text {
-                    content: scale_string,
-                    position: (pos.x + left, pos.y + top).into(),
-                    color,
-                    ..Default::default()
-                };
+        let node = layout::Node::new(Size::new(400.0, 300.0));
+        element
+            .as_widget()
+            .operate(tree, layout::Layout::new(&node), &(), operation.as_mut());
 
-                frame.fill_text(text);
-            }
+        let _ = operation.finish();
+        // Closes the channel of the output
+        drop(operation);
 
-            if state.offset != Vector::ZERO {
-                let pos = (bounds.width * 0.01, bounds.height * 0.95).into();
-                let background = style.details_background;
-                let radius = style.details_border_radius;
-                let color = style.details_text;
+        match block_on(stream.next()) {
+            Some(Action::Output(output)) => Some(output),
+            _ => None,
+        }
+    }
 
-                let x = state.offset.x;
-                let y = state.offset.y * -1.;
+    /// A [`Program`] drawing a single cached square.
+    struct Cached {
+        cache: BufferCache,
+    }
 
-                let offset_string = format!("x: {x:.1}, y: {y:.1}");
-                let min_bounds = min_text_bounds(&offset_string, details_bounds, details_size);
-                let bounds = min_bounds.expand(details_padding);
+    impl Program<(), Theme, ()> for Cached {
+        type State = ();
 
-                let rect = Path::rounded_rectangle(pos, bounds, radius);
+        fn init_state(&self) -> Self::State {}
 
-                frame.fill(&rect, background);
+        fn draw<'a>(&self, _state: &(), _theme: &Theme, _context: DrawContext) -> Vec<Buffer<'a>> {
+            let mut buffer = Buffer::new().cache(&self.cache);
+            buffer.fill_rectangle((0.0, 0.0), (50.0, 50.0), Color::BLACK);
 
-                let text = Text {
-                    content: offset_string,
-                    position: (pos.x + left, pos.y + top).into(),
-                    color,
-                    ..Default::default()
-                };
+            vec![buffer]
+        }
+    }
 
-                frame.fill_text(text);
-            }
+    const SIZE: Size = Size::new(400.0, 300.0);
 
-            let geoms = frame.into_geometry();
+    /// Draws `element` as the runtime would.
+    fn draw(element: &Element<'_, (), Theme, ()>, tree: &tree::Tree) {
+        let node = layout::Node::new(SIZE);
 
-            renderer.draw_geometry(geoms);
-        });
+        element.as_widget().draw(
+            tree,
+            &mut (),
+            &Theme::Light,
+            &advanced::renderer::Style::default(),
+            layout::Layout::new(&node),
+            Cursor::Unavailable,
+            &Rectangle::with_size(SIZE),
+        );
     }
 
-    fn overlay<'b>(
-        &'b mut self,
-        state: &'b mut tree::Tree,
-        layout: layout::Layout<'_>,
-        _renderer: &Renderer,
-        translation: Vector,
-    ) -> Option<advanced::overlay::Element<'b, Message, Theme, Renderer>> {
-        let bounds = layout.bounds();
-        let state = state.state.downcast_mut::<InfiniteState<P::State>>();
-
-        self.program.overlay(
-            &mut state.state,
-            bounds,
-            state.mouse_position.unwrap_or_default(),
-            translation,
-        )
-    }
-}
+    /// Starts a frame of `element`, returning the redraw it asks for.
+    fn start_frame(
+        element: &mut Element<'_, (), Theme, ()>,
+        tree: &mut tree::Tree,
+    ) -> Option<window::RedrawRequest> {
+        let node = layout::Node::new(SIZE);
+        let mut messages = Vec::new();
+        let mut shell = advanced::Shell::new(&mut messages);
+
+        element.as_widget_mut().on_event(
+            tree,
+            iced::Event::Window(window::Event::RedrawRequested(Instant::now())),
+            layout::Layout::new(&node),
+            Cursor::Unavailable,
+            &(),
+            &mut advanced::clipboard::Null,
+            &mut shell,
+            &Rectangle::with_size(SIZE),
+        );
 
-impl<'a, P, Message, Theme, Renderer> From<Infinite<'a, P, Message, Theme, Renderer>>
-    for Element<'a, Message, Theme, Renderer>
-where
-    Message: 'a,
-    Theme: Catalog + 'a,
-    P: Program<Message, Theme, Renderer> + 'a,
-    Renderer: geometry::Renderer + 'a,
-{
-    fn from(value: Infinite<'a, P, Message, Theme, Renderer>) -> Self {
-        Element::new(value)
+        shell.redraw_request()
     }
-}
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-struct InfiniteState<State> {
-    offset: Vector,
-    scale_level: f32,
-    scale: f32,
-    keyboard_modifier: keyboard::Modifiers,
-    state: State,
-    /// The virtual position of the cursor
-    mouse_position: Option<Point>,
-}
+    /// Waits for the workers tessellating the buffers of `tree`.
+    fn wait_for_workers(tree: &tree::Tree) {
+        let geometry = &tree.state.downcast_ref::<InfiniteState<()>>().geometry;
 
-impl<State> InfiniteState<State> {
-    fn new(state: State) -> Self {
-        let scale_level = 0.0;
-        let scale = E.powf(scale_level);
-        Self {
-            offset: Vector::new(0., 0.),
-            scale_level,
-            state,
-            scale,
-            keyboard_modifier: keyboard::Modifiers::default(),
-            mouse_position: None,
+        while geometry.pending.borrow().values().any(|job| {
+            let job = job.downcast_ref::<Tessellation<Frame<()>>>();
+            !job.expect("a job").worker.is_finished()
+        }) {
+            std::thread::sleep(Duration::from_millis(1));
         }
     }
 
-    fn set_mouse_position(&mut self, position: Option<Point>) {
-        self.mouse_position = position;
+    /// Returns the generation of the geometry drawn for the cached buffer of
+    /// `tree`, and the number of buffers being tessellated.
+    fn drawn_generation(tree: &tree::Tree) -> (Option<u64>, usize) {
+        let geometry = &tree.state.downcast_ref::<InfiniteState<()>>().geometry;
+        let generation = geometry.buffers.borrow().values().next().map(|entry| {
+            let entry = entry.downcast_ref::<CachedGeometry<()>>();
+            entry.expect("cached geometry").generation
+        });
+
+        (generation, geometry.pending.borrow().len())
     }
 
-    /// Adds to scale level
-    fn add_level(&mut self, diff: f32, focal_origin: bool) -> Vector {
-        self.scale_level += diff;
-        let prev_scale = self.scale;
-        self.scale = E.powf(self.scale_level);
+    #[test]
+    fn background_tessellation_swaps_in_ready_geometry() {
+        let cache = BufferCache::new();
+        let program = Cached {
+            cache: cache.clone(),
+        };
+        let mut element: Element<'_, (), Theme, ()> =
+            Infinite::new(program).background_tessellation(1).into();
+        let mut tree = tree::Tree::new(&element);
+
+        // Nothing is drawn until the first geometry is ready
+        start_frame(&mut element, &mut tree);
+        draw(&element, &tree);
+        assert_eq!(drawn_generation(&tree), (None, 1));
+        assert_eq!(
+            start_frame(&mut element, &mut tree),
+            Some(window::RedrawRequest::NextFrame)
+        );
 
-        let delta = if focal_origin {
-            let ratio = if diff < 0.0 {
-                prev_scale / self.scale
-            } else {
-                self.scale / prev_scale
-            };
+        wait_for_workers(&tree);
+        draw(&element, &tree);
+        assert_eq!(drawn_generation(&tree), (Some(0), 0));
+        assert_eq!(start_frame(&mut element, &mut tree), None);
 
-            let diff = 1.0 - ratio;
+        // The last geometry is drawn while the new one is tessellated
+        cache.clear();
+        assert_eq!(
+            start_frame(&mut element, &mut tree),
+            Some(window::RedrawRequest::NextFrame)
+        );
+        draw(&element, &tree);
+        assert_eq!(drawn_generation(&tree), (Some(0), 1));
 
-            Vector::new(diff * self.offset.x, -diff * self.offset.y)
-        } else {
-            let diff = self.scale - prev_scale;
-            let cursor = self.mouse_position.unwrap_or(Point::ORIGIN);
+        wait_for_workers(&tree);
+        draw(&element, &tree);
+        assert_eq!(drawn_generation(&tree), (Some(1), 0));
+        assert_eq!(start_frame(&mut element, &mut tree), None);
+    }
 
-            Vector::new(diff * cursor.x, -diff * cursor.y)
+    #[test]
+    fn cached_geometry_is_scaled_to_a_new_zoom() {
+        let mut state = InfiniteState::new(());
+        state.offset = Vector::new(30.0, -12.0);
+        let before = state.view();
+        let center = Point::new(SIZE.width / 2.0, SIZE.height / 2.0);
+
+        let entry = CachedGeometry {
+            generation: 0,
+            size: SIZE,
+            antialiasing: Antialiasing::default(),
+            view: before,
+            group: cache::Group::unique(),
+            geometry: (),
         };
 
-        self.offset = self.offset + delta;
+        state.set_scale_level(Vector::new(0.7, 0.7));
+        state.offset = Vector::new(-80.0, 45.0);
+        let context = RenderContext {
+            view: state.view(),
+            center,
+            antialiasing: Antialiasing::default(),
+        };
 
-        delta
-    }
+        let transformation = entry.transformation(&context).expect("a transformation");
 
-    fn set_scale_level(&mut self, level: f32) {
-        self.scale_level = level;
-        self.scale = E.powf(self.scale_level);
+        for point in [Point::new(0.0, 0.0), Point::new(120.0, -35.0)] {
+            // Where the point was drawn in the geometry, spanning a pane in
+            // every direction
+            let drawn = translate_point(&before, center, point, Anchor::None)
+                + Vector::new(SIZE.width, SIZE.height);
+            let placed = drawn * transformation;
+            let expected = translate_point(&context.view, center, point, Anchor::None);
+
+            assert!(
+                placed.distance(expected) < 1e-3,
+                "{placed:?} is not {expected:?}"
+            );
+        }
+
+        // Geometry cannot be rotated into place
+        state.rotation = 0.5;
+        let context = RenderContext {
+            view: state.view(),
+            ..context
+        };
+        assert!(entry.transformation(&context).is_none());
     }
 
-    fn reset_all(&mut self, offset: Vector, scale: f32) {
-        self.reset_scale(scale);
-        self.reset_offset(offset);
+    /// Returns viewports rotated by each quarter turn and more, with each
+    /// direction of the Y axis.
+    fn turned_viewports() -> Vec<Viewport> {
+        let angles = [0.0, 0.3, PI / 2.0, PI, -2.5];
+
+        angles
+            .into_iter()
+            .flat_map(|angle| {
+                [YDirection::Up, YDirection::Down].map(|y_axis| Viewport {
+                    offset: Vector::new(35.0, -120.0),
+                    scale: Vector::new(2.0, 0.5),
+                    rotation: Radians(angle),
+                    y_axis,
+                    bounds: Rectangle::new(Point::new(10.0, 20.0), SIZE),
+                })
+            })
+            .collect()
     }
 
-    fn reset_offset(&mut self, init: Vector) {
-        self.offset = init;
+    fn assert_near(a: Point, b: Point) {
+        assert!(a.distance(b) < 1e-3, "{a:?} != {b:?}");
     }
 
-    fn reset_scale(&mut self, init: f32) {
-        self.scale_level = init;
-        let prev_scale = self.scale;
-        self.scale = E.powf(self.scale_level);
+    #[test]
+    fn to_screen_reverses_to_world() {
+        let points = [
+            Point::ORIGIN,
+            Point::new(10.0, 20.0),
+            Point::new(-250.0, 75.5),
+            Point::new(410.0, 320.0),
+        ];
+
+        for viewport in turned_viewports() {
+            for point in points {
+                assert_near(viewport.to_screen(viewport.to_world(point)), point);
+                assert_near(viewport.to_world(viewport.to_screen(point)), point);
+            }
+        }
+    }
 
-        let delta = {
-            let diff = self.scale - prev_scale;
-            let mouse = self.mouse_position.unwrap_or_default();
-            Vector::new(diff * mouse.x, -diff * mouse.y)
-        };
+    #[test]
+    fn flipping_the_y_axis_mirrors_the_screen() {
+        for up in turned_viewports()
+            .into_iter()
+            .filter(|viewport| viewport.y_axis == YDirection::Up)
+        {
+            let down = Viewport {
+                y_axis: YDirection::Down,
+                ..up
+            };
+            let point = Point::new(30.0, 40.0);
+            let mirrored = Point::new(point.x, -point.y);
 
-        self.offset = self.offset + delta;
+            assert_near(up.to_screen(point), down.to_screen(mirrored));
+            assert_near(down.to_world(up.to_screen(point)), mirrored);
+        }
     }
-}
 
-/// Style an [`Infinite`] canvas.
-pub mod style {
-    use super::*;
+    #[test]
+    fn rotation_turns_the_canvas_counter_clockwise() {
+        let viewport = Viewport {
+            offset: Vector::ZERO,
+            scale: Vector::new(1.0, 1.0),
+            rotation: Radians(PI / 2.0),
+            y_axis: YDirection::Up,
+            bounds: Rectangle::with_size(SIZE),
+        };
+        let center = viewport.bounds.center();
 
-    #[derive(Debug, Clone, Copy, PartialEq)]
-    /// The appearance of the [`Infinite`].
-    pub struct Style {
-        /// The [`Border`] of the [`Infinite`].
-        pub border: Border,
-        /// The [`Background`] of the [`Infinite`].
-        pub background: Background,
-        /// The border radius of the [`Infinite`]'s details.
-        pub details_border_radius: Radius,
-        /// The [`Background`] of the [`Infinite`]'s details.
-        pub details_background: Color,
-        /// The text [`Color`] of the [`Infinite`]'s details.
-        pub details_text: Color,
+        // The positive X axis points up the screen once turned a quarter
+        assert_near(
+            viewport.to_screen(Point::new(10.0, 0.0)),
+            center - Vector::new(0.0, 10.0),
+        );
     }
 
-    #[derive(Debug, Clone, Copy, Default, PartialEq)]
-    /// The possible status of an [`Infinite`].
-    pub enum Status {
-        #[default]
-        /// The [`Infinite`] is not being hovered on.
-        Active,
-        /// The [`Infinite`] is being hovered on.
-        Hovered,
+    #[cfg(feature = "serde")]
+    #[test]
+    fn viewport_survives_serialization() {
+        let id = Id::unique();
+        let element: Element<'_, (), Theme, ()> = Infinite::new(Empty)
+            .id(id.clone())
+            .y_axis(YDirection::Down)
+            .into();
+        let mut tree = tree::Tree::new(&element);
+
+        let state = tree.state.downcast_mut::<InfiniteState<()>>();
+        state.set_y_axis(YDirection::Down);
+        state.set_scale_level(Vector::new(0.5, 0.5));
+        state.rotate_around(0.7, Vector::ZERO);
+        state.offset = Vector::new(3.0 * REBASE_DISTANCE, -2.0 * REBASE_DISTANCE);
+        state.rebase();
+        assert_ne!(state.origin, Point::new(0.0, 0.0));
+
+        let saved = run(&element, &mut tree, viewport(id.clone())).expect("a viewport");
+        let json = serde_json::to_string(&saved).expect("a serialized viewport");
+        let loaded: Viewport = serde_json::from_str(&json).expect("a deserialized viewport");
+
+        assert_eq!(loaded, saved);
+
+        // Restored onto a canvas which was never rebased
+        let mut tree = tree::Tree::new(&element);
+        assert!(run(&element, &mut tree, restore::<()>(id.clone(), loaded)).is_none());
+        let restored = run(&element, &mut tree, viewport(id)).expect("a viewport");
+
+        assert_eq!(restored.y_axis, YDirection::Down);
+        assert!((restored.rotation.0 - saved.rotation.0).abs() < 1e-4);
+
+        for point in [Point::ORIGIN, Point::new(120.0, 45.0)] {
+            let (a, b) = (restored.to_world(point), saved.to_world(point));
+            assert!(a.distance(b) < 0.1, "{a:?} != {b:?}");
+        }
     }
 
-    /// The theme of an [`Infinite`].
-    pub trait Catalog {
-        /// The item class of the [`Catalog`].
-        type Class<'a>;
+    #[test]
+    fn scroll_to_moves_the_view() {
+        let id = Id::unique();
+        let element: Element<'_, (), Theme, ()> = Infinite::new(Empty).id(id.clone()).into();
+        let mut tree = tree::Tree::new(&element);
+        let scroll = Vector::new(120.0, -40.0);
 
-        /// The default class produced by the [`Catalog`].
-        fn default<'a>() -> Self::Class<'a>;
+        assert!(run(&element, &mut tree, scroll_to::<()>(id.clone(), scroll)).is_none());
 
-        /// The [`Style`] of a class with the given status.
-        fn style(&self, class: &Self::Class<'_>, status: Status) -> Style;
+        let state = tree.state.downcast_ref::<InfiniteState<()>>();
+        assert_eq!(state.scroll(), scroll);
+
+        let viewport = run(&element, &mut tree, viewport(id)).expect("a viewport");
+        assert_eq!(viewport.offset, scroll);
     }
 
-    /// A styling function for an [`Infinite`].
-    pub type StyleFn<'a, Theme> = Box<dyn Fn(&Theme, Status) -> Style + 'a>;
+    #[test]
+    fn released_overscroll_eases_back_within_the_scroll_bounds() {
+        let region = Rectangle::new(Point::new(-500.0, -500.0), Size::new(1000.0, 1000.0));
+        let mut element: Element<'_, (), Theme, ()> = Infinite::new(Empty)
+            .scroll_bounds(region)
+            .elastic_overscroll(true)
+            .into();
+        let mut tree = tree::Tree::new(&element);
+        let node = layout::Node::new(SIZE);
+
+        let state = tree.state.downcast_mut::<InfiniteState<()>>();
+        let (_, max) = offset_range(state.scale, state.y_axis, SIZE, region);
+        state.offset = Vector::new(max.x + 100.0, state.offset.y);
+
+        let mut frame_at = |now: Instant| {
+            let mut messages = Vec::new();
+            let mut shell = advanced::Shell::new(&mut messages);
+
+            element.as_widget_mut().on_event(
+                &mut tree,
+                iced::Event::Window(window::Event::RedrawRequested(now)),
+                layout::Layout::new(&node),
+                Cursor::Unavailable,
+                &(),
+                &mut advanced::clipboard::Null,
+                &mut shell,
+                &Rectangle::with_size(SIZE),
+            );
 
-    impl Catalog for Theme {
-        type Class<'a> = StyleFn<'a, Self>;
+            let state = tree.state.downcast_ref::<InfiniteState<()>>();
+            (state.scroll(), state.overscroll.is_some())
+        };
 
-        fn default<'a>() -> Self::Class<'a> {
-            Box::new(default)
-        }
+        let released = Instant::now();
+        let (scroll, releasing) = frame_at(released);
+        assert!(releasing);
+        assert_eq!(scroll.x, max.x + 100.0);
 
-        fn style(&self, class: &Self::Class<'_>, status: Status) -> Style {
-            class(self, status)
-        }
+        let (scroll, releasing) = frame_at(released + OVERSCROLL_RELEASE / 2);
+        assert!(releasing);
+        let expected = max.x + 100.0 * (1.0 - Easing::CubicOut.apply(0.5));
+        assert!((scroll.x - expected).abs() < 0.01, "{scroll:?}");
+
+        let (scroll, releasing) = frame_at(released + OVERSCROLL_RELEASE);
+        assert!(!releasing);
+        assert_eq!(scroll.x, max.x);
     }
 
-    /// The default [`Theme`] styling of an [`Infinite`].
-    pub fn default(theme: &Theme, status: Status) -> Style {
-        let palette = theme.extended_palette();
-        let border_width = 2.5;
+    /// A [`Program`] which handles the Escape key.
+    struct Escaping;
 
-        let background = palette.background.base;
-        let details_background = Color {
-            a: 0.9,
-            ..background.color
-        };
-        let details_text = background.text;
+    impl Program<(), Theme, ()> for Escaping {
+        type State = ();
 
-        let border = match status {
-            Status::Active => Border::default()
-                .width(border_width)
-                .color(palette.background.base.color),
-            Status::Hovered => Border::default()
-                .width(border_width)
-                .color(palette.primary.strong.color),
-        };
+        fn init_state(&self) -> Self::State {}
 
-        Style {
-            border,
-            background: DEFAULT_BACKGROUND,
-            details_background,
-            details_border_radius: 5.into(),
-            details_text,
+        fn draw<'a>(&self, _state: &(), _theme: &Theme, _context: DrawContext) -> Vec<Buffer<'a>> {
+            Vec::new()
         }
-    }
-}
-
-/// Returns a pair of [`Cursor`]s with the second [`Cursor`]'s point translated
-/// to fit within the [`Infinite`]'s coordinate system.
-fn get_cursors(cursor: Cursor, bounds: Rectangle, offset: Vector, scale: f32) -> (Cursor, Cursor) {
-    match cursor {
-        Cursor::Available(point) => {
-            let point = bounds.center() - point;
-            let point = (point - offset) * (1. / scale);
-            let point = Point::new(-point.x, point.y);
 
-            (cursor, Cursor::Available(point))
+        fn update(
+            &self,
+            _state: &mut (),
+            event: Event,
+            _context: InputContext,
+            _clipboard: &mut dyn advanced::Clipboard,
+        ) -> (event::Status, Option<()>) {
+            match event {
+                Event::Keyboard(keyboard::Event::KeyPressed {
+                    key: keyboard::Key::Named(keyboard::key::Named::Escape),
+                    ..
+                }) => (event::Status::Captured, None),
+                _ => (event::Status::Ignored, None),
+            }
         }
-        Cursor::Unavailable => (cursor, cursor),
     }
-}
 
-/// Returns the minimum bounds that can fit `text`.
-pub fn min_text_bounds(text: &str, bounds: Size, size: impl Into<Pixels>) -> Size {
-    use iced::{
-        advanced::{
-            self,
-            text::{self, Paragraph},
-        },
-        alignment, Font,
-    };
+    /// Focuses `element` and presses the `key` on it, returning whether it is
+    /// still focused.
+    fn press_while_focused(
+        mut element: Element<'_, (), Theme, ()>,
+        key: keyboard::key::Named,
+    ) -> bool {
+        let mut tree = tree::Tree::new(&element);
+        let node = layout::Node::new(SIZE);
+        let mut messages = Vec::new();
+        let mut shell = advanced::Shell::new(&mut messages);
+
+        tree.state.downcast_mut::<InfiniteState<()>>().focused = true;
+
+        element.as_widget_mut().on_event(
+            &mut tree,
+            iced::Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(key),
+                modified_key: keyboard::Key::Named(key),
+                physical_key: keyboard::key::Physical::Unidentified(
+                    keyboard::key::NativeCode::Unidentified,
+                ),
+                location: keyboard::Location::Standard,
+                modifiers: keyboard::Modifiers::default(),
+                text: None,
+            }),
+            layout::Layout::new(&node),
+            Cursor::Unavailable,
+            &(),
+            &mut advanced::clipboard::Null,
+            &mut shell,
+            &Rectangle::with_size(SIZE),
+        );
 
-    let text = advanced::Text {
-        content: text,
-        bounds,
-        font: Font::default(),
-        size: size.into(),
-        line_height: text::LineHeight::default(),
-        horizontal_alignment: alignment::Horizontal::Left,
-        vertical_alignment: alignment::Vertical::Center,
-        wrapping: text::Wrapping::default(),
-        shaping: text::Shaping::default(),
-    };
+        tree.state.downcast_ref::<InfiniteState<()>>().focused
+    }
 
-    let text = iced_graphics::text::Paragraph::with_text(text);
+    #[test]
+    fn escape_drops_focus_unless_the_program_handles_it() {
+        use keyboard::key::Named;
 
-    text.min_bounds()
-}
+        let ignoring = |key| press_while_focused(Infinite::new(Empty).into(), key);
+        let handling = |key| press_while_focused(Infinite::new(Escaping).into(), key);
 
-fn wrap_event(
-    event: iced::Event,
-    bounds: Rectangle,
-    offset: Vector,
-    scale: f32,
-) -> Option<event::Event> {
-    match event.clone() {
-        iced::Event::Mouse(mouse::Event::CursorMoved { position }) => {
-            let point = bounds.center() - position;
-            let point = (point - offset) * (1. / scale);
-            let position = Point::new(-point.x, point.y);
-            Some(Event::Mouse(mouse::Event::CursorMoved { position }))
-        }
-        iced::Event::Mouse(event) => Some(Event::Mouse(event)),
-        iced::Event::Keyboard(event) => Some(Event::Keyboard(event)),
-        iced::Event::Touch(event) => {
-            let event = match event {
-                touch::Event::FingerLost { id, position } => {
-                    let position = bounds.center() - position;
-                    let position = (position - offset) * (1. / scale);
-                    let position = Point::new(-position.x, position.y);
-                    Event::Touch(touch::Event::FingerLost { id, position })
-                }
-                touch::Event::FingerMoved { id, position } => {
-                    let position = bounds.center() - position;
-                    let position = (position - offset) * (1. / scale);
-                    let position = Point::new(-position.x, position.y);
-                    Event::Touch(touch::Event::FingerMoved { id, position })
-                }
-                touch::Event::FingerLifted { id, position } => {
-                    let position = bounds.center() - position;
-                    let position = (position - offset) * (1. / scale);
-                    let position = Point::new(-position.x, position.y);
-                    Event::Touch(touch::Event::FingerLifted { id, position })
-                }
-                touch::Event::FingerPressed { id, position } => {
-                    let position = bounds.center() - position;
-                    let position = (position - offset) * (1. / scale);
-                    let position = Point::new(-position.x, position.y);
-                    Event::Touch(touch::Event::FingerPressed { id, position })
-                }
-            };
+        assert!(!ignoring(Named::Escape));
+        assert!(!ignoring(Named::Tab));
+        assert!(handling(Named::Escape));
+        assert!(!handling(Named::Tab));
+    }
 
-            Some(event)
-        }
+    /// A [`Program`] which publishes a message when its viewport changes.
+    struct Watched;
 
-        _ => None,
-    }
-}
+    impl Program<(), Theme, ()> for Watched {
+        type State = ();
 
-fn transform_path<State>(
-    state: &InfiniteState<State>,
-    center: Point,
-    path: &Path,
-    anchor: Anchor,
-    scale: bool,
-) -> Path {
-    let offset = match anchor {
-        Anchor::None => state.offset,
-        Anchor::X => Vector::new(0., state.offset.y),
-        Anchor::Y => Vector::new(state.offset.x, 0.),
-        Anchor::Both => Vector::new(0., 0.),
-    };
-    let center = center - offset;
-    let trans_x = center.x;
-    let trans_y = center.y;
-    let scale = if scale { state.scale } else { 1.0 };
+        fn init_state(&self) -> Self::State {}
 
-    let transform = Transform2D::new(scale, 0.0, 0.0, -scale, trans_x, trans_y);
+        fn draw<'a>(&self, _state: &(), _theme: &Theme, _context: DrawContext) -> Vec<Buffer<'a>> {
+            Vec::new()
+        }
 
-    path.transform(&transform)
-}
+        fn on_viewport_change(&self, _state: &mut (), _viewport: Viewport) -> Option<()> {
+            Some(())
+        }
+    }
 
-fn translate_point<State>(
-    state: &InfiniteState<State>,
-    center: Point,
-    point: impl Into<Point>,
-    anchor: Anchor,
-) -> Point {
-    let offset = match anchor {
-        Anchor::Both => Vector::new(0., 0.),
-        Anchor::X => Vector::new(0., state.offset.y),
-        Anchor::Y => Vector::new(state.offset.x, 0.),
-        Anchor::None => state.offset,
-    };
-    let center = center - offset;
-    let point = {
-        let point: Point = point.into();
-        Point::new(point.x * state.scale, point.y * state.scale)
-    };
-    let x = center.x + point.x;
-    let y = center.y - point.y;
+    #[test]
+    fn zoom_to_publishes_the_viewport_change_with_the_next_event() {
+        let id = Id::unique();
+        let mut element: Element<'_, (), Theme, ()> = Infinite::new(Watched).id(id.clone()).into();
+        let mut tree = tree::Tree::new(&element);
+        let node = layout::Node::new(SIZE);
+
+        let next_event = |element: &mut Element<'_, (), Theme, ()>, tree: &mut tree::Tree| {
+            let mut messages = Vec::new();
+            let mut shell = advanced::Shell::new(&mut messages);
+
+            element.as_widget_mut().on_event(
+                tree,
+                iced::Event::Window(window::Event::RedrawRequested(Instant::now())),
+                layout::Layout::new(&node),
+                Cursor::Unavailable,
+                &(),
+                &mut advanced::clipboard::Null,
+                &mut shell,
+                &Rectangle::with_size(SIZE),
+            );
 
-    Point::new(x, y)
-}
+            messages.len()
+        };
 
-fn transform_text<State>(
-    state: &InfiniteState<State>,
-    center: Point,
-    text: &Text,
-    anchor: Anchor,
-) -> Text {
-    //dbg!(&text.content);
-    //dbg!(text.position);
-    let position = translate_point(state, center, text.position, anchor);
-    //dbg!(position);
+        assert!(run(&element, &mut tree, zoom_to::<()>(id, 2.0)).is_none());
 
-    Text {
-        content: text.content.clone(),
-        position,
-        size: text.size,
-        color: text.color,
-        font: text.font,
-        horizontal_alignment: text.horizontal_alignment,
-        vertical_alignment: text.vertical_alignment,
-        line_height: text.line_height,
-        shaping: text.shaping,
+        assert_eq!(next_event(&mut element, &mut tree), 1);
+        assert_eq!(next_event(&mut element, &mut tree), 0);
     }
-}
 
-fn handle_scale<P, Message, Theme, Renderer>(
-    canvas: &Infinite<P, Message, Theme, Renderer>,
-    state: &mut InfiniteState<P::State>,
-    shell: &mut advanced::Shell<'_, Message>,
-    bounds: Rectangle,
-    cursors: (Cursor, Cursor),
-    zoom: f32,
-    focal_origin: bool,
-) -> iced::event::Status
-where
-    Theme: Catalog,
-    P: Program<Message, Theme, Renderer>,
-    Renderer: geometry::Renderer,
-{
-    let offset_diff = state.add_level(zoom, focal_origin);
-    let focal_point = if focal_origin {
-        Point::ORIGIN
-    } else {
-        state.mouse_position.unwrap_or(Point::ORIGIN)
-    };
+    #[test]
+    fn map_path_moves_every_point() {
+        let path = Path::new(|builder| {
+            builder.move_to(Point::new(0.0, 0.0));
+            builder.line_to(Point::new(2.0, 0.0));
+            builder.quadratic_curve_to(Point::new(3.0, 1.0), Point::new(2.0, 2.0));
+            builder.close();
+        });
 
-    let msg = canvas.program.on_zoom(
-        &mut state.state,
-        bounds,
-        cursors.0,
-        cursors.1,
-        focal_point,
-        state.scale,
-        zoom,
-    );
+        let moved = map_path(&path, |point| Point::new(point.x * 2.0, point.y + 1.0));
 
-    if let Some(msg) = msg {
-        shell.publish(msg);
-    }
+        let expected = Path::new(|builder| {
+            builder.move_to(Point::new(0.0, 1.0));
+            builder.line_to(Point::new(4.0, 1.0));
+            builder.quadratic_curve_to(Point::new(6.0, 2.0), Point::new(4.0, 3.0));
+            builder.close();
+        });
 
-    if let Some(msg) = canvas.program.on_scroll(
-        &mut state.state,
-        bounds,
-        cursors.0,
-        cursors.1,
-        state.offset,
-        offset_diff,
-    ) {
-        shell.publish(msg);
+        let events = |path: &Path| path.raw().iter().collect::<Vec<_>>();
+        assert_eq!(events(&moved), events(&expected));
     }
 
-    iced_event::Status::Captured
+    #[test]
+    fn align_moves_a_point_under_the_screen_position() {
+        let bounds = Rectangle::new(Point::new(10.0, 20.0), Size::new(400.0, 300.0));
+        let mut state = InfiniteState::new(());
+        state.set_scale_level(Vector::new(0.5, 0.5));
+        state.rotation = 0.3;
+        state.origin = Point::new(100.0, -50.0);
+        state.offset = Vector::new(30.0, 12.0);
+
+        let point = Point::new(140.0, -90.0);
+        let screen = Point::new(150.0, 80.0);
+        state.align(bounds, point, screen);
+
+        let aligned = translate_cursor(bounds, state.view(), screen);
+        assert!(
+            aligned.distance(point) < 1e-3,
+            "{aligned:?} is not {point:?}"
+        );
+    }
 }
@@ -0,0 +1,169 @@
+//! Easing functions for consistent motion on an [`Infinite`].
+//!
+//! An [`Easing`] maps the linear progress of an animation, from 0.0 to 1.0,
+//! to the eased progress the animated value should be at.
+//!
+//! [`Infinite`]: crate::Infinite
+
+use std::f32::consts::PI;
+
+/// An easing function.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Easing {
+    /// Constant speed.
+    #[default]
+    Linear,
+    /// Starts slow and accelerates quadratically.
+    QuadIn,
+    /// Starts fast and decelerates quadratically.
+    QuadOut,
+    /// Accelerates then decelerates quadratically.
+    QuadInOut,
+    /// Starts slow and accelerates cubically.
+    CubicIn,
+    /// Starts fast and decelerates cubically.
+    CubicOut,
+    /// Accelerates then decelerates cubically.
+    CubicInOut,
+    /// Overshoots the end and oscillates into place.
+    ElasticOut,
+    /// Settles into place like a damped spring.
+    Spring,
+}
+
+impl Easing {
+    /// Returns the eased progress for the linear progress `t`.
+    ///
+    /// `t` is clamped between 0.0 and 1.0.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QuadInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::ElasticOut => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else {
+                    let c4 = (2.0 * PI) / 3.0;
+                    2_f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * c4).sin() + 1.0
+                }
+            }
+            Easing::Spring => {
+                if t == 1.0 {
+                    t
+                } else {
+                    1.0 - (-6.0 * t).exp() * (12.0 * t).cos()
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Easing; 9] = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::ElasticOut,
+        Easing::Spring,
+    ];
+
+    /// Returns the eased progress at evenly spaced steps from 0.0 to 1.0.
+    fn samples(easing: Easing) -> Vec<f32> {
+        (0..=100)
+            .map(|step| easing.apply(step as f32 / 100.0))
+            .collect()
+    }
+
+    #[test]
+    fn every_easing_starts_and_ends_in_place() {
+        for easing in ALL {
+            assert_eq!(easing.apply(0.0), 0.0, "{easing:?}");
+            assert_eq!(easing.apply(1.0), 1.0, "{easing:?}");
+        }
+    }
+
+    #[test]
+    fn progress_is_clamped() {
+        for easing in ALL {
+            assert_eq!(easing.apply(-1.0), easing.apply(0.0), "{easing:?}");
+            assert_eq!(easing.apply(2.0), easing.apply(1.0), "{easing:?}");
+        }
+    }
+
+    #[test]
+    fn polynomial_easings_never_go_back() {
+        let polynomial = [
+            Easing::Linear,
+            Easing::QuadIn,
+            Easing::QuadOut,
+            Easing::QuadInOut,
+            Easing::CubicIn,
+            Easing::CubicOut,
+            Easing::CubicInOut,
+        ];
+
+        for easing in polynomial {
+            let samples = samples(easing);
+
+            assert!(
+                samples.windows(2).all(|pair| pair[0] <= pair[1]),
+                "{easing:?}"
+            );
+            assert!(samples.iter().all(|value| (0.0..=1.0).contains(value)));
+        }
+    }
+
+    #[test]
+    fn ins_start_slow_and_outs_start_fast() {
+        let t = 0.25;
+
+        assert!(Easing::QuadIn.apply(t) < t);
+        assert!(Easing::CubicIn.apply(t) < Easing::QuadIn.apply(t));
+        assert!(Easing::QuadOut.apply(t) > t);
+        assert!(Easing::CubicOut.apply(t) > Easing::QuadOut.apply(t));
+
+        // In and out mirror each other
+        assert!((Easing::QuadIn.apply(t) + Easing::QuadOut.apply(1.0 - t) - 1.0).abs() < 1e-6);
+
+        for easing in [Easing::QuadInOut, Easing::CubicInOut] {
+            assert!((easing.apply(0.5) - 0.5).abs() < 1e-6);
+            assert!((easing.apply(t) + easing.apply(1.0 - t) - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn elastic_and_spring_overshoot_then_settle() {
+        for easing in [Easing::ElasticOut, Easing::Spring] {
+            let samples = samples(easing);
+
+            assert!(samples.iter().any(|value| *value > 1.0), "{easing:?}");
+            assert!((samples[90] - 1.0).abs() < 0.01, "{easing:?}");
+        }
+    }
+}
@@ -0,0 +1,788 @@
+//! Geometry of the [`Path`]s drawn on an [`Infinite`], for hit testing the
+//! actual shapes of items rather than their bounds, as when erasing the
+//! strokes under the cursor.
+//!
+//! Curves are flattened into lines at most [`TOLERANCE`] units away from
+//! them, so results near the outline of a curve are approximate.
+//!
+//! Sequences of points, as drawn by hand, can be thinned out with
+//! [`simplify`] and drawn as a curve through them with [`smooth`].
+//!
+//! The length of a path and the points along it, as when placing arrowheads
+//! or moving markers along edges, are found with [`Measure`].
+//!
+//! The regions taken up by points, paths and rectangles, as when selecting
+//! or fitting the view to items, are found with [`bounds`], [`path_bounds`],
+//! [`union`], [`convex_hull`] and [`min_area_rectangle`].
+//!
+//! [`Infinite`]: crate::Infinite
+
+use iced::widget::canvas::{
+    fill,
+    path::lyon_path::{
+        geom::{Box2D, CubicBezierSegment, LineSegment, QuadraticBezierSegment},
+        iterator::PathIterator,
+        math, Event as PathEvent, FillRule,
+    },
+    Path,
+};
+use iced::{Point, Radians, Rectangle, Size, Vector};
+use lyon_algorithms::{
+    hit_test::hit_test_path,
+    measure::{PathMeasurements, SampleType},
+};
+
+/// The largest distance, in units, between a curve and the lines it is
+/// flattened into.
+pub const TOLERANCE: f32 = 0.01;
+
+/// Returns whether `point` lies within the area `path` fills with the given
+/// `rule`.
+pub fn fill_contains(path: &Path, point: Point, rule: fill::Rule) -> bool {
+    let rule = match rule {
+        fill::Rule::NonZero => FillRule::NonZero,
+        fill::Rule::EvenOdd => FillRule::EvenOdd,
+    };
+
+    hit_test_path(
+        &math::point(point.x, point.y),
+        path.raw().iter(),
+        rule,
+        TOLERANCE,
+    )
+}
+
+/// Returns whether `point` lies within `tolerance` units of the outline of
+/// `path`, as when hit testing a stroke of twice that width.
+pub fn stroke_contains(path: &Path, point: Point, tolerance: f32) -> bool {
+    let point = math::point(point.x, point.y);
+
+    segments(path, false).any(|segment| segment.distance_to_point(point) <= tolerance)
+}
+
+/// Returns the distance, in units, from `point` to the nearest point of the
+/// outline of `path`, or `None` if the path has no outline.
+pub fn distance(path: &Path, point: Point) -> Option<f32> {
+    let point = math::point(point.x, point.y);
+
+    segments(path, false)
+        .map(|segment| segment.distance_to_point(point))
+        .min_by(f32::total_cmp)
+}
+
+/// Returns whether the outline of `path` passes through `region`, given by
+/// its bottom-left corner.
+pub fn stroke_intersects(path: &Path, region: Rectangle) -> bool {
+    let region = to_box(region);
+
+    segments(path, false).any(|segment| segment.clipped(&region).is_some())
+}
+
+/// Returns whether the area `path` fills with the given `rule` overlaps
+/// `region`, given by its bottom-left corner.
+pub fn fill_intersects(path: &Path, region: Rectangle, rule: fill::Rule) -> bool {
+    let clip = to_box(region);
+
+    // Either the outline crosses the region, or one lies wholly in the other
+    segments(path, true).any(|segment| segment.clipped(&clip).is_some())
+        || fill_contains(path, region.center(), rule)
+}
+
+/// Returns as few of the `points` of a polyline as keep it within
+/// `tolerance` units of the original, by the Douglas-Peucker algorithm.
+///
+/// The first and last points are always kept.
+pub fn simplify(points: &[Point], tolerance: f32) -> Vec<Point> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let mut kept = vec![false; points.len()];
+    kept[0] = true;
+    kept[points.len() - 1] = true;
+
+    let mut ranges = vec![(0, points.len() - 1)];
+
+    while let Some((first, last)) = ranges.pop() {
+        let chord = LineSegment {
+            from: math::point(points[first].x, points[first].y),
+            to: math::point(points[last].x, points[last].y),
+        };
+
+        let farthest = (first + 1..last)
+            .map(|index| {
+                let point = math::point(points[index].x, points[index].y);
+
+                (index, chord.distance_to_point(point))
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        if let Some((index, distance)) = farthest {
+            if distance > tolerance {
+                kept[index] = true;
+                ranges.push((first, index));
+                ranges.push((index, last));
+            }
+        }
+    }
+
+    points
+        .iter()
+        .zip(kept)
+        .filter_map(|(point, kept)| kept.then_some(*point))
+        .collect()
+}
+
+/// Returns a smooth curve through all the `points`, following the centripetal
+/// Catmull-Rom spline through them, which neither loops nor overshoots
+/// between points far apart.
+pub fn smooth(points: &[Point]) -> Path {
+    Path::new(|builder| {
+        let Some(first) = points.first() else {
+            return;
+        };
+
+        builder.move_to(*first);
+
+        if points.len() < 3 {
+            points[1..].iter().for_each(|point| builder.line_to(*point));
+            return;
+        }
+
+        let at = |index: usize| points[index.min(points.len() - 1)];
+        // The square root of the distance between two points
+        let span = |from: Point, to: Point| from.distance(to).sqrt();
+
+        for index in 0..points.len() - 1 {
+            let (p0, p1, p2, p3) = (
+                at(index.saturating_sub(1)),
+                at(index),
+                at(index + 1),
+                at(index + 2),
+            );
+            let (d1, d2, d3) = (span(p0, p1), span(p1, p2), span(p2, p3));
+
+            if d2 == 0.0 {
+                continue;
+            }
+
+            // The control point next to `near` of the segment to `far`, with
+            // `outer` the point beyond `near` at a span of `outer_span`
+            let control = |near: Point, far: Point, outer: Point, outer_span: f32| {
+                if outer_span == 0.0 {
+                    return near;
+                }
+
+                let (a, b) = (outer_span * outer_span, d2 * d2);
+                let weight = 2.0 * a + 3.0 * outer_span * d2 + b;
+                let scale = 3.0 * outer_span * (outer_span + d2);
+
+                Point::new(
+                    (a * far.x - b * outer.x + weight * near.x) / scale,
+                    (a * far.y - b * outer.y + weight * near.y) / scale,
+                )
+            };
+
+            builder.bezier_curve_to(control(p1, p2, p0, d1), control(p2, p1, p3, d3), p2);
+        }
+    })
+}
+
+/// Returns the length, in units, of `path`.
+pub fn length(path: &Path) -> f32 {
+    Measure::new(path).length()
+}
+
+/// A point along a [`Path`] and the direction of the path there, see
+/// [`Measure`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    /// The point along the path.
+    pub position: Point,
+    /// The direction of the path at the point, of unit length.
+    pub tangent: Vector,
+}
+
+impl Sample {
+    /// Returns the angle of the direction of the path, counterclockwise from
+    /// the positive X axis where the Y axis points up.
+    pub fn angle(&self) -> Radians {
+        Radians(self.tangent.y.atan2(self.tangent.x))
+    }
+}
+
+/// The lengths along a [`Path`], measured once to find the points at any
+/// distance along it.
+///
+/// Subpaths follow one another, without the gaps between them counting
+/// towards the length.
+pub struct Measure {
+    path: Path,
+    measurements: PathMeasurements,
+}
+
+impl Measure {
+    /// Measures the `path`.
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.clone(),
+            measurements: PathMeasurements::from_path(path.raw(), TOLERANCE),
+        }
+    }
+
+    /// Returns the length, in units, of the [`Path`].
+    pub fn length(&self) -> f32 {
+        self.measurements.length()
+    }
+
+    /// Returns the point `distance` units along the [`Path`] from its start,
+    /// clamped to its ends, or `None` if the path has no length.
+    pub fn at_length(&self, distance: f32) -> Option<Sample> {
+        self.sample(distance, SampleType::Distance)
+    }
+
+    /// Returns the point along the [`Path`] at the fraction `t` of its
+    /// length, from 0.0 at its start to 1.0 at its end, or `None` if the path
+    /// has no length.
+    pub fn at(&self, t: f32) -> Option<Sample> {
+        self.sample(t, SampleType::Normalized)
+    }
+
+    fn sample(&self, distance: f32, kind: SampleType) -> Option<Sample> {
+        if self.length() <= 0.0 {
+            return None;
+        }
+
+        let mut sampler = self.measurements.create_sampler(self.path.raw(), kind);
+        let sample = sampler.sample(distance);
+
+        let (position, tangent) = (sample.position(), sample.tangent());
+
+        Some(Sample {
+            position: Point::new(position.x, position.y),
+            tangent: Vector::new(tangent.x, tangent.y),
+        })
+    }
+}
+
+impl std::fmt::Debug for Measure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Measure")
+            .field("path", &self.path)
+            .field("length", &self.length())
+            .finish()
+    }
+}
+
+/// Returns the smallest rectangle, given by its bottom-left corner, which
+/// contains all `points`, or `None` if there are none.
+pub fn bounds(points: &[Point]) -> Option<Rectangle> {
+    let first = *points.first()?;
+
+    let (min, max) = points.iter().fold((first, first), |(min, max), point| {
+        (
+            Point::new(min.x.min(point.x), min.y.min(point.y)),
+            Point::new(max.x.max(point.x), max.y.max(point.y)),
+        )
+    });
+
+    Some(Rectangle::new(min, Size::new(max.x - min.x, max.y - min.y)))
+}
+
+/// Returns the smallest rectangle, given by its bottom-left corner, which
+/// contains the `path`.
+pub fn path_bounds(path: &Path) -> Option<Rectangle> {
+    path.raw()
+        .iter()
+        .filter_map(|event| match event {
+            PathEvent::Begin { at } => Some(LineSegment { from: at, to: at }.bounding_box()),
+            PathEvent::Line { from, to } => Some(LineSegment { from, to }.bounding_box()),
+            PathEvent::Quadratic { from, ctrl, to } => {
+                Some(QuadraticBezierSegment { from, ctrl, to }.bounding_box())
+            }
+            PathEvent::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => Some(
+                CubicBezierSegment {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                }
+                .bounding_box(),
+            ),
+            PathEvent::End { .. } => None,
+        })
+        .reduce(|a, b| a.union(&b))
+        .map(|bounds| {
+            Rectangle::new(
+                Point::new(bounds.min.x, bounds.min.y),
+                Size::new(bounds.width(), bounds.height()),
+            )
+        })
+}
+
+/// Returns the smallest rectangle, given by its bottom-left corner, which
+/// contains all `rectangles`, or `None` if there are none.
+pub fn union(rectangles: impl IntoIterator<Item = Rectangle>) -> Option<Rectangle> {
+    rectangles.into_iter().reduce(|a, b| a.union(&b))
+}
+
+/// Returns the `rectangle` grown by `amount.x` units on its left and right
+/// and by `amount.y` units on its top and bottom, or shrunk towards its
+/// center for negative amounts.
+///
+/// Margins of a number of pixels are `pixels / scale` units on each axis,
+/// see [`Viewport::scale`](crate::Viewport::scale).
+pub fn inflate(rectangle: Rectangle, amount: Vector) -> Rectangle {
+    let center = rectangle.center();
+    let size = Size::new(
+        (rectangle.width + 2.0 * amount.x).max(0.0),
+        (rectangle.height + 2.0 * amount.y).max(0.0),
+    );
+
+    Rectangle::new(
+        Point::new(center.x - size.width / 2.0, center.y - size.height / 2.0),
+        size,
+    )
+}
+
+/// Returns the corners of the smallest convex polygon which contains all
+/// `points`, counterclockwise where the Y axis points up.
+pub fn convex_hull(points: &[Point]) -> Vec<Point> {
+    let mut points = points.to_vec();
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup();
+
+    if points.len() < 3 {
+        return points;
+    }
+
+    let turns_left = |chain: &[Point], point: Point| match chain {
+        [.., a, b] => (b.x - a.x) * (point.y - a.y) - (b.y - a.y) * (point.x - a.x) > 0.0,
+        _ => true,
+    };
+
+    // The lower and then the upper half, by Andrew's monotone chain
+    let mut hull: Vec<Point> = Vec::with_capacity(points.len() + 1);
+
+    for point in &points {
+        while hull.len() >= 2 && !turns_left(&hull, *point) {
+            let _ = hull.pop();
+        }
+
+        hull.push(*point);
+    }
+
+    let lower = hull.len() + 1;
+
+    for point in points.iter().rev().skip(1) {
+        while hull.len() >= lower && !turns_left(&hull, *point) {
+            let _ = hull.pop();
+        }
+
+        hull.push(*point);
+    }
+
+    // The first point ends the upper half too
+    let _ = hull.pop();
+
+    hull
+}
+
+/// Returns the corners of the smallest rectangle, at any rotation, which
+/// contains all `points`, counterclockwise where the Y axis points up, or
+/// `None` if there are no points.
+pub fn min_area_rectangle(points: &[Point]) -> Option<[Point; 4]> {
+    let hull = convex_hull(points);
+
+    if let [point] = hull[..] {
+        return Some([point; 4]);
+    }
+
+    let mut smallest: Option<(f32, [Point; 4])> = None;
+
+    // One side of the smallest rectangle lies along an edge of the hull
+    for (index, from) in hull.iter().enumerate() {
+        let edge = hull[(index + 1) % hull.len()] - *from;
+        let length = edge.x.hypot(edge.y);
+
+        if length == 0.0 {
+            continue;
+        }
+
+        let along = Vector::new(edge.x / length, edge.y / length);
+        let across = Vector::new(-along.y, along.x);
+
+        let project = |axis: Vector| {
+            hull.iter()
+                .map(|point| point.x * axis.x + point.y * axis.y)
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), value| {
+                    (min.min(value), max.max(value))
+                })
+        };
+
+        let (u, v) = (project(along), project(across));
+        let area = (u.1 - u.0) * (v.1 - v.0);
+
+        if smallest.is_none_or(|(smallest, _)| area < smallest) {
+            let corner =
+                |u: f32, v: f32| Point::new(along.x * u + across.x * v, along.y * u + across.y * v);
+
+            smallest = Some((
+                area,
+                [
+                    corner(u.0, v.0),
+                    corner(u.1, v.0),
+                    corner(u.1, v.1),
+                    corner(u.0, v.1),
+                ],
+            ));
+        }
+    }
+
+    smallest.map(|(_, corners)| corners)
+}
+
+/// Returns the lines of the flattened outline of `path`, closing every
+/// subpath if `close` is true, as fills do.
+fn segments(path: &Path, close: bool) -> impl Iterator<Item = LineSegment<f32>> + '_ {
+    path.raw()
+        .iter()
+        .flattened(TOLERANCE)
+        .filter_map(move |event| match event {
+            PathEvent::Line { from, to } => Some(LineSegment { from, to }),
+            PathEvent::End {
+                last,
+                first,
+                close: closed,
+            } if close || closed => Some(LineSegment {
+                from: last,
+                to: first,
+            }),
+            _ => None,
+        })
+}
+
+/// Returns `rectangle` as a box with a non-negative width and height.
+fn to_box(rectangle: Rectangle) -> Box2D<f32> {
+    let (x0, x1) = (rectangle.x, rectangle.x + rectangle.width);
+    let (y0, y1) = (rectangle.y, rectangle.y + rectangle.height);
+
+    Box2D::new(
+        math::point(x0.min(x1), y0.min(y1)),
+        math::point(x0.max(x1), y0.max(y1)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the distance from `point` to the nearest segment of the
+    /// polyline through `points`.
+    fn polyline_distance(points: &[Point], point: Point) -> f32 {
+        points
+            .windows(2)
+            .map(|pair| {
+                let segment = LineSegment {
+                    from: math::point(pair[0].x, pair[0].y),
+                    to: math::point(pair[1].x, pair[1].y),
+                };
+
+                segment.distance_to_point(math::point(point.x, point.y))
+            })
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    #[test]
+    fn simplify_keeps_short_polylines() {
+        let points = [Point::new(0.0, 0.0), Point::new(5.0, 5.0)];
+
+        assert_eq!(simplify(&points, 1.0), points.to_vec());
+        assert_eq!(simplify(&[], 1.0), Vec::new());
+    }
+
+    #[test]
+    fn simplify_drops_points_along_a_line() {
+        let points: Vec<Point> = (0..=10)
+            .map(|x| Point::new(x as f32, x as f32 * 0.5))
+            .collect();
+
+        assert_eq!(
+            simplify(&points, 0.01),
+            vec![Point::new(0.0, 0.0), Point::new(10.0, 5.0)]
+        );
+    }
+
+    #[test]
+    fn simplify_keeps_corners_beyond_the_tolerance() {
+        // A zigzag with small wiggles along its long strokes
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(5.0, 0.2),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+            Point::new(15.0, 9.8),
+            Point::new(20.0, 10.0),
+        ];
+
+        let simplified = simplify(&points, 0.5);
+
+        assert_eq!(
+            simplified,
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(10.0, 0.0),
+                Point::new(10.0, 10.0),
+                Point::new(20.0, 10.0),
+            ]
+        );
+
+        for point in points {
+            assert!(polyline_distance(&simplified, point) <= 0.5);
+        }
+
+        assert_eq!(simplify(&points, 0.1), points.to_vec());
+    }
+
+    #[test]
+    fn simplify_stays_within_the_tolerance() {
+        let points: Vec<Point> = (0..200)
+            .map(|index| {
+                let t = index as f32 / 10.0;
+                Point::new(t * 5.0, t.sin() * 20.0)
+            })
+            .collect();
+
+        for tolerance in [0.1, 1.0, 5.0] {
+            let simplified = simplify(&points, tolerance);
+
+            assert!(simplified.len() < points.len());
+            assert_eq!(simplified.first(), points.first());
+            assert_eq!(simplified.last(), points.last());
+
+            for point in &points {
+                assert!(polyline_distance(&simplified, *point) <= tolerance + 1e-4);
+            }
+        }
+    }
+
+    fn assert_near(a: Point, b: Point) {
+        assert!(a.distance(b) < 1e-3, "{a:?} is not {b:?}");
+    }
+
+    #[test]
+    fn measure_a_polyline() {
+        let path = Path::new(|builder| {
+            builder.move_to(Point::new(0.0, 0.0));
+            builder.line_to(Point::new(3.0, 0.0));
+            builder.line_to(Point::new(3.0, 4.0));
+        });
+        let measure = Measure::new(&path);
+
+        assert!((measure.length() - 7.0).abs() < 1e-4);
+        assert!((length(&path) - 7.0).abs() < 1e-4);
+
+        let sample = measure.at_length(3.5).unwrap();
+        assert_near(sample.position, Point::new(3.0, 0.5));
+        assert_near(Point::ORIGIN + sample.tangent, Point::new(0.0, 1.0));
+        assert!((sample.angle().0 - std::f32::consts::FRAC_PI_2).abs() < 1e-4);
+
+        assert_near(measure.at(0.0).unwrap().position, Point::new(0.0, 0.0));
+        assert_near(measure.at(0.5).unwrap().position, Point::new(3.0, 0.5));
+        assert_near(measure.at(1.0).unwrap().position, Point::new(3.0, 4.0));
+    }
+
+    #[test]
+    fn measure_clamps_to_the_ends() {
+        let path = Path::line(Point::new(1.0, 1.0), Point::new(5.0, 1.0));
+        let measure = Measure::new(&path);
+
+        assert_near(
+            measure.at_length(-2.0).unwrap().position,
+            Point::new(1.0, 1.0),
+        );
+        assert_near(
+            measure.at_length(100.0).unwrap().position,
+            Point::new(5.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn measure_curves_and_subpaths() {
+        let circle = Path::circle(Point::ORIGIN, 10.0);
+        let expected = 2.0 * std::f32::consts::PI * 10.0;
+
+        // Iced approximates the circle with curves, which are a little longer
+        assert!((length(&circle) - expected).abs() < expected * 0.005);
+
+        // The gap between the subpaths does not count
+        let path = Path::new(|builder| {
+            builder.move_to(Point::new(0.0, 0.0));
+            builder.line_to(Point::new(1.0, 0.0));
+            builder.move_to(Point::new(10.0, 0.0));
+            builder.line_to(Point::new(10.0, 1.0));
+        });
+
+        assert!((length(&path) - 2.0).abs() < 1e-4);
+        assert_near(
+            Measure::new(&path).at_length(1.5).unwrap().position,
+            Point::new(10.0, 0.5),
+        );
+    }
+
+    #[test]
+    fn measure_an_empty_path() {
+        let measure = Measure::new(&Path::new(|_| {}));
+
+        assert_eq!(measure.length(), 0.0);
+        assert_eq!(measure.at(0.5), None);
+        assert_eq!(measure.at_length(0.0), None);
+    }
+
+    /// Returns the signed area of the polygon with the given corners, positive
+    /// if they are counterclockwise where the Y axis points up.
+    fn signed_area(corners: &[Point]) -> f32 {
+        corners
+            .iter()
+            .zip(corners.iter().cycle().skip(1))
+            .map(|(a, b)| a.x * b.y - b.x * a.y)
+            .sum::<f32>()
+            / 2.0
+    }
+
+    #[test]
+    fn convex_hull_of_a_square_with_inner_points() {
+        let points = [
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(2.0, 0.0),
+            Point::new(0.5, 1.5),
+            // On an edge, so not a corner
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 2.0),
+            Point::new(2.0, 2.0),
+        ];
+
+        assert_eq!(
+            convex_hull(&points),
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(2.0, 0.0),
+                Point::new(2.0, 2.0),
+                Point::new(0.0, 2.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn convex_hull_is_counterclockwise_and_contains_the_points() {
+        let points: Vec<Point> = (0..50)
+            .map(|index| {
+                let t = index as f32 * 2.4;
+                Point::new(t.cos() * (index % 7) as f32, t.sin() * (index % 5) as f32)
+            })
+            .collect();
+
+        let hull = convex_hull(&points);
+        assert!(signed_area(&hull) > 0.0);
+
+        for (index, from) in hull.iter().enumerate() {
+            let to = hull[(index + 1) % hull.len()];
+
+            for point in &points {
+                let cross =
+                    (to.x - from.x) * (point.y - from.y) - (to.y - from.y) * (point.x - from.x);
+                assert!(cross >= -1e-4, "{point:?} is outside the hull");
+            }
+        }
+    }
+
+    #[test]
+    fn convex_hull_of_few_points() {
+        let point = Point::new(1.0, 2.0);
+
+        assert_eq!(convex_hull(&[]), Vec::new());
+        assert_eq!(convex_hull(&[point, point]), vec![point]);
+        assert_eq!(
+            convex_hull(&[
+                Point::new(0.0, 0.0),
+                Point::new(1.0, 1.0),
+                Point::new(2.0, 2.0)
+            ]),
+            vec![Point::new(0.0, 0.0), Point::new(2.0, 2.0)]
+        );
+    }
+
+    #[test]
+    fn min_area_rectangle_of_a_rotated_square() {
+        // A square of side 2 rotated by 45 degrees
+        let root = std::f32::consts::SQRT_2;
+        let points = [
+            Point::new(0.0, -root),
+            Point::new(root, 0.0),
+            Point::new(0.0, root),
+            Point::new(-root, 0.0),
+            Point::new(0.2, 0.1),
+        ];
+
+        let corners = min_area_rectangle(&points).unwrap();
+
+        assert!((signed_area(&corners) - 4.0).abs() < 1e-4);
+
+        for corner in corners {
+            assert!(points[..4]
+                .iter()
+                .any(|point| point.distance(corner) < 1e-4));
+        }
+
+        let point = Point::new(3.0, 4.0);
+        assert_eq!(min_area_rectangle(&[point]), Some([point; 4]));
+        assert_eq!(min_area_rectangle(&[]), None);
+    }
+
+    #[test]
+    fn bounds_of_points_paths_and_rectangles() {
+        let points = [
+            Point::new(1.0, -2.0),
+            Point::new(-3.0, 4.0),
+            Point::new(0.0, 0.0),
+        ];
+        let expected = Rectangle::new(Point::new(-3.0, -2.0), Size::new(4.0, 6.0));
+
+        assert_eq!(bounds(&points), Some(expected));
+        assert_eq!(bounds(&[]), None);
+
+        let circle = path_bounds(&Path::circle(Point::new(1.0, 1.0), 2.0)).unwrap();
+        assert_near(circle.position(), Point::new(-1.0, -1.0));
+        assert!((circle.width - 4.0).abs() < 1e-4 && (circle.height - 4.0).abs() < 1e-4);
+
+        let a = Rectangle::new(Point::new(0.0, 0.0), Size::new(1.0, 1.0));
+        let b = Rectangle::new(Point::new(2.0, 3.0), Size::new(1.0, 1.0));
+
+        assert_eq!(
+            union([a, b]),
+            Some(Rectangle::new(Point::new(0.0, 0.0), Size::new(3.0, 4.0)))
+        );
+        assert_eq!(union([]), None);
+    }
+
+    #[test]
+    fn inflate_grows_and_shrinks_around_the_center() {
+        let rectangle = Rectangle::new(Point::new(0.0, 0.0), Size::new(4.0, 2.0));
+
+        assert_eq!(
+            inflate(rectangle, Vector::new(1.0, 0.5)),
+            Rectangle::new(Point::new(-1.0, -0.5), Size::new(6.0, 3.0))
+        );
+        assert_eq!(
+            inflate(rectangle, Vector::new(-3.0, -0.5)),
+            Rectangle::new(Point::new(2.0, 0.5), Size::new(0.0, 1.0))
+        );
+    }
+}
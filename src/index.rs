@@ -0,0 +1,538 @@
+//! A spatial index of items on an [`Infinite`], for finding the items under
+//! the cursor or within a region without going through all of them.
+//!
+//! Items are kept in a [`QuadTree`] by their bounds on the canvas, which
+//! grows as far as needed in every direction.
+//!
+//! [`Infinite`]: crate::Infinite
+
+use iced::{Point, Rectangle, Size};
+
+/// The number of items a node holds before it is split into quadrants.
+const NODE_CAPACITY: usize = 8;
+
+/// The number of times a node can be split into smaller quadrants.
+const MAX_DEPTH: usize = 16;
+
+/// The size, in units, of the region the first item starts a [`QuadTree`]
+/// with, when the item is smaller.
+const MIN_ROOT_SIZE: f32 = 64.0;
+
+/// The identifier of an item in a [`QuadTree`].
+///
+/// The identifier of a removed item may be given to an item inserted later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ItemId(usize);
+
+/// A node of a [`QuadTree`], holding the items which fit in its region but
+/// not in any one of its quadrants.
+///
+/// The region is kept rather than found from that of the root, which would
+/// be rounded differently once the root has grown.
+#[derive(Debug, Clone)]
+struct Node {
+    region: Rectangle,
+    items: Vec<usize>,
+    children: Option<Box<[Node; 4]>>,
+}
+
+impl Node {
+    fn new(region: Rectangle) -> Self {
+        Self {
+            region,
+            items: Vec::new(),
+            children: None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.items.is_empty() && self.children.is_none()
+    }
+}
+
+/// A quadtree of items of type `T`, indexed by their bounds on the canvas.
+///
+/// An item is found by [`QuadTree::query`] and [`QuadTree::query_point`] if
+/// its bounds touch the region or point, so bounds should cover all of the
+/// item, including the width of its strokes.
+#[derive(Debug, Clone)]
+pub struct QuadTree<T> {
+    items: Vec<Option<(Rectangle, T)>>,
+    /// The indices of the removed items, to be reused.
+    free: Vec<usize>,
+    /// The root, or `None` before the first insert.
+    root: Option<Node>,
+    len: usize,
+}
+
+impl<T> Default for QuadTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> QuadTree<T> {
+    /// Creates an empty [`QuadTree`].
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            free: Vec::new(),
+            root: None,
+            len: 0,
+        }
+    }
+
+    /// Inserts an `item` covering the given `bounds`, returning its
+    /// [`ItemId`].
+    pub fn insert(&mut self, bounds: Rectangle, item: T) -> ItemId {
+        let bounds = normalize(bounds);
+
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.items[index] = Some((bounds, item));
+                index
+            }
+            None => {
+                self.items.push(Some((bounds, item)));
+                self.items.len() - 1
+            }
+        };
+
+        let root = grow(self.root.take(), bounds);
+        let root = self.root.insert(root);
+        insert(root, 0, index, &self.items);
+        self.len += 1;
+
+        ItemId(index)
+    }
+
+    /// Removes the item with the given [`ItemId`], returning its bounds and
+    /// the item.
+    pub fn remove(&mut self, id: ItemId) -> Option<(Rectangle, T)> {
+        let bounds = self.bounds(id)?;
+        let root = self.root.as_mut()?;
+
+        remove(root, bounds, id.0);
+
+        self.free.push(id.0);
+        self.len -= 1;
+
+        self.items[id.0].take()
+    }
+
+    /// Moves the item with the given [`ItemId`] to new `bounds`, keeping its
+    /// [`ItemId`].
+    ///
+    /// Returns `false` if there is no such item.
+    pub fn update(&mut self, id: ItemId, bounds: Rectangle) -> bool {
+        let (Some(old), Some(root)) = (self.bounds(id), self.root.as_mut()) else {
+            return false;
+        };
+
+        remove(root, old, id.0);
+
+        let bounds = normalize(bounds);
+
+        if let Some((own, _)) = &mut self.items[id.0] {
+            *own = bounds;
+        }
+
+        let root = grow(self.root.take(), bounds);
+        let root = self.root.insert(root);
+        insert(root, 0, id.0, &self.items);
+
+        true
+    }
+
+    /// Returns the item with the given [`ItemId`].
+    pub fn get(&self, id: ItemId) -> Option<&T> {
+        self.entry(id).map(|(_, item)| item)
+    }
+
+    /// Returns the item with the given [`ItemId`] mutably.
+    pub fn get_mut(&mut self, id: ItemId) -> Option<&mut T> {
+        self.items
+            .get_mut(id.0)
+            .and_then(Option::as_mut)
+            .map(|(_, item)| item)
+    }
+
+    /// Returns the bounds of the item with the given [`ItemId`].
+    pub fn bounds(&self, id: ItemId) -> Option<Rectangle> {
+        self.entry(id).map(|(bounds, _)| *bounds)
+    }
+
+    /// Returns the items whose bounds touch the `region`.
+    ///
+    /// The items are returned in no particular order.
+    pub fn query(&self, region: Rectangle) -> impl Iterator<Item = (ItemId, &T)> + '_ {
+        let region = normalize(region);
+
+        self.find(|bounds| touches(bounds, &region))
+    }
+
+    /// Returns the items whose bounds contain the `point`, as when hit
+    /// testing the cursor.
+    ///
+    /// The items are returned in no particular order.
+    pub fn query_point(&self, point: Point) -> impl Iterator<Item = (ItemId, &T)> + '_ {
+        self.find(move |bounds| contains(bounds, point))
+    }
+
+    /// Returns all items in the order of their [`ItemId`]s.
+    pub fn iter(&self) -> impl Iterator<Item = (ItemId, &T)> + '_ {
+        self.items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| entry.as_ref().map(|(_, item)| (ItemId(index), item)))
+    }
+
+    /// Returns the number of items in the [`QuadTree`].
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the [`QuadTree`] has no items.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes all items from the [`QuadTree`].
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    fn entry(&self, id: ItemId) -> Option<&(Rectangle, T)> {
+        self.items.get(id.0).and_then(Option::as_ref)
+    }
+
+    /// Returns the items whose bounds are accepted by `test`, visiting only
+    /// the nodes whose region it accepts.
+    fn find(&self, test: impl Fn(&Rectangle) -> bool) -> std::vec::IntoIter<(ItemId, &T)> {
+        let mut found = Vec::new();
+
+        if let Some(root) = &self.root {
+            // The root is always visited, as it also holds the items too large
+            // for its region
+            let mut stack = vec![root];
+
+            while let Some(node) = stack.pop() {
+                for &index in &node.items {
+                    if let Some((bounds, item)) = &self.items[index] {
+                        if test(bounds) {
+                            found.push((ItemId(index), item));
+                        }
+                    }
+                }
+
+                if let Some(children) = &node.children {
+                    stack.extend(children.iter().filter(|child| test(&loose(child.region))));
+                }
+            }
+        }
+
+        found.into_iter()
+    }
+}
+
+/// Grows the region of the `root` until it contains `bounds`, returning the
+/// new root.
+fn grow(root: Option<Node>, bounds: Rectangle) -> Node {
+    let mut root = root.unwrap_or_else(|| {
+        let side = (bounds.width.max(bounds.height).max(MIN_ROOT_SIZE) * 2.0).min(f32::MAX);
+        let center = bounds.center();
+
+        Node::new(Rectangle::new(
+            Point::new(center.x - side / 2.0, center.y - side / 2.0),
+            Size::new(side, side),
+        ))
+    });
+
+    // Bounds too large for the region to grow to are kept at the root, as a
+    // region of infinite size has no edges
+    while !within(&bounds, &root.region) && (root.region.width * 2.0).is_finite() {
+        let region = root.region;
+
+        // The old root becomes the quadrant of the new one facing away from
+        // the bounds
+        let left = bounds.x < region.x;
+        let low = bounds.y < region.y;
+
+        let x = if left {
+            region.x - region.width
+        } else {
+            region.x
+        };
+        let y = if low {
+            region.y - region.height
+        } else {
+            region.y
+        };
+        let quadrant = usize::from(left) + 2 * usize::from(low);
+
+        let region = Rectangle::new(Point::new(x, y), region.size() * 2.0);
+        let mut children = quadrants(region).map(Node::new);
+        children[quadrant] = root;
+
+        root = Node {
+            region,
+            items: Vec::new(),
+            children: Some(Box::new(children)),
+        };
+    }
+
+    root
+}
+
+/// Inserts the item at `index` into the smallest node under `node` whose
+/// region contains its bounds.
+fn insert<T>(node: &mut Node, depth: usize, index: usize, items: &[Option<(Rectangle, T)>]) {
+    let bounds = |index: usize| items[index].as_ref().map(|(bounds, _)| *bounds);
+
+    if let Some(children) = &mut node.children {
+        let target = bounds(index).and_then(|bounds| {
+            children
+                .iter_mut()
+                .find(|child| within(&bounds, &child.region))
+        });
+
+        if let Some(child) = target {
+            return insert(child, depth + 1, index, items);
+        }
+    }
+
+    node.items.push(index);
+
+    if node.children.is_none() && node.items.len() > NODE_CAPACITY && depth < MAX_DEPTH {
+        node.children = Some(Box::new(quadrants(node.region).map(Node::new)));
+
+        for index in std::mem::take(&mut node.items) {
+            insert(node, depth, index, items);
+        }
+    }
+}
+
+/// Removes the item at `index` with the given `bounds` from the node under
+/// `node` which holds it.
+fn remove(node: &mut Node, bounds: Rectangle, index: usize) -> bool {
+    if let Some(position) = node.items.iter().position(|other| *other == index) {
+        node.items.swap_remove(position);
+        return true;
+    }
+
+    let Some(children) = &mut node.children else {
+        return false;
+    };
+
+    let removed = children
+        .iter_mut()
+        .filter(|child| within(&bounds, &child.region))
+        .any(|child| remove(child, bounds, index));
+
+    // Merge the quadrants back once they are all empty leaves
+    if children.iter().all(Node::is_empty) {
+        node.children = None;
+    }
+
+    removed
+}
+
+/// Returns the four quadrants of `region`: the one at its origin, then the
+/// one along the X axis, then the one along the Y axis, then the opposite one.
+fn quadrants(region: Rectangle) -> [Rectangle; 4] {
+    let size = region.size() * 0.5;
+    let (x, y) = (region.x, region.y);
+
+    [
+        Rectangle::new(Point::new(x, y), size),
+        Rectangle::new(Point::new(x + size.width, y), size),
+        Rectangle::new(Point::new(x, y + size.height), size),
+        Rectangle::new(Point::new(x + size.width, y + size.height), size),
+    ]
+}
+
+/// Returns `region` grown by a few times the rounding error of its edges, as
+/// the region of a grown root may fall just short of the quadrant it grew
+/// from.
+fn loose(region: Rectangle) -> Rectangle {
+    let extent = region.x.abs().max(region.y.abs()) + region.width.max(region.height);
+    let margin = extent * (4.0 * f32::EPSILON);
+
+    Rectangle::new(
+        Point::new(region.x - margin, region.y - margin),
+        Size::new(region.width + 2.0 * margin, region.height + 2.0 * margin),
+    )
+}
+
+/// Returns `rectangle` with a non-negative width and height.
+fn normalize(rectangle: Rectangle) -> Rectangle {
+    let x = rectangle.x.min(rectangle.x + rectangle.width);
+    let y = rectangle.y.min(rectangle.y + rectangle.height);
+
+    Rectangle::new(
+        Point::new(x, y),
+        Size::new(rectangle.width.abs(), rectangle.height.abs()),
+    )
+}
+
+/// Returns whether `inner` lies entirely within `outer`.
+fn within(inner: &Rectangle, outer: &Rectangle) -> bool {
+    inner.x >= outer.x
+        && inner.y >= outer.y
+        && inner.x + inner.width <= outer.x + outer.width
+        && inner.y + inner.height <= outer.y + outer.height
+}
+
+/// Returns whether `a` and `b` overlap or share an edge.
+fn touches(a: &Rectangle, b: &Rectangle) -> bool {
+    a.x <= b.x + b.width && b.x <= a.x + a.width && a.y <= b.y + b.height && b.y <= a.y + a.height
+}
+
+/// Returns whether `point` lies within `rectangle` or on its edge.
+fn contains(rectangle: &Rectangle, point: Point) -> bool {
+    point.x >= rectangle.x
+        && point.x <= rectangle.x + rectangle.width
+        && point.y >= rectangle.y
+        && point.y <= rectangle.y + rectangle.height
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: f32, y: f32, size: f32) -> Rectangle {
+        Rectangle::new(Point::new(x, y), Size::new(size, size))
+    }
+
+    /// Returns the sorted items found by a query.
+    fn sorted<'a>(found: impl Iterator<Item = (ItemId, &'a usize)>) -> Vec<usize> {
+        let mut found: Vec<usize> = found.map(|(_, item)| *item).collect();
+        found.sort_unstable();
+        found
+    }
+
+    /// A tree of squares spread in every direction from the origin, enough
+    /// to split and grow it several times.
+    fn grid() -> (QuadTree<usize>, Vec<Rectangle>) {
+        let mut tree = QuadTree::new();
+        let mut squares = Vec::new();
+
+        for row in -10..10 {
+            for column in -10..10 {
+                let bounds = square(column as f32 * 50.0, row as f32 * 50.0, 20.0);
+                tree.insert(bounds, squares.len());
+                squares.push(bounds);
+            }
+        }
+
+        (tree, squares)
+    }
+
+    #[test]
+    fn query_matches_every_item() {
+        let (tree, squares) = grid();
+        let regions = [
+            square(-120.0, -80.0, 200.0),
+            square(10.0, 10.0, 5.0),
+            // Shares an edge with the square at the origin
+            square(20.0, 0.0, 10.0),
+            square(-1000.0, -1000.0, 2000.0),
+            square(5000.0, 5000.0, 10.0),
+        ];
+
+        assert_eq!(tree.len(), squares.len());
+
+        for region in regions {
+            let expected: Vec<usize> = (0..squares.len())
+                .filter(|&index| touches(&squares[index], &region))
+                .collect();
+
+            assert_eq!(sorted(tree.query(region)), expected);
+        }
+    }
+
+    #[test]
+    fn query_point_finds_items_under_it() {
+        let mut tree = QuadTree::new();
+        tree.insert(square(0.0, 0.0, 10.0), 0);
+        tree.insert(square(5.0, 5.0, 10.0), 1);
+        tree.insert(square(100.0, 100.0, 10.0), 2);
+
+        assert_eq!(sorted(tree.query_point(Point::new(7.0, 7.0))), vec![0, 1]);
+        assert_eq!(sorted(tree.query_point(Point::new(10.0, 2.0))), vec![0]);
+        assert_eq!(
+            sorted(tree.query_point(Point::new(50.0, 50.0))),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn remove_reuses_ids() {
+        let (mut tree, squares) = grid();
+        let id = tree.query_point(Point::new(5.0, 5.0)).next().unwrap().0;
+
+        assert_eq!(tree.remove(id), Some((squares[210], 210)));
+        assert_eq!(tree.remove(id), None);
+        assert_eq!(tree.len(), squares.len() - 1);
+        assert!(tree.query_point(Point::new(5.0, 5.0)).next().is_none());
+
+        let reused = tree.insert(square(0.0, 0.0, 1.0), 1000);
+        assert_eq!(reused, id);
+        assert_eq!(tree.get(reused), Some(&1000));
+    }
+
+    #[test]
+    fn update_moves_items() {
+        let (mut tree, _) = grid();
+        let id = tree.query_point(Point::new(5.0, 5.0)).next().unwrap().0;
+
+        assert!(tree.update(id, square(-9000.0, 9000.0, 10.0)));
+        assert!(tree.query_point(Point::new(5.0, 5.0)).next().is_none());
+        assert_eq!(
+            sorted(tree.query_point(Point::new(-8995.0, 9005.0))),
+            vec![210]
+        );
+        assert_eq!(tree.bounds(id), Some(square(-9000.0, 9000.0, 10.0)));
+    }
+
+    #[test]
+    fn negative_sizes_are_normalized() {
+        let mut tree = QuadTree::new();
+        let id = tree.insert(
+            Rectangle::new(Point::new(10.0, 10.0), Size::new(-10.0, -10.0)),
+            0,
+        );
+
+        assert_eq!(tree.bounds(id), Some(square(0.0, 0.0, 10.0)));
+        assert_eq!(sorted(tree.query_point(Point::new(5.0, 5.0))), vec![0]);
+    }
+
+    #[test]
+    fn far_items_keep_near_ones() {
+        let mut tree = QuadTree::new();
+        tree.insert(square(3.0, 7.0, 10.0), 0);
+        tree.insert(square(1e9, -3e9, 10.0), 1);
+
+        assert_eq!(sorted(tree.query_point(Point::new(5.0, 10.0))), vec![0]);
+        assert_eq!(sorted(tree.query_point(Point::new(1e9, -3e9))), vec![1]);
+    }
+
+    #[test]
+    fn huge_items_are_found_everywhere() {
+        let huge = square(-1e38, -1e38, 2e38);
+
+        let mut tree = QuadTree::new();
+        tree.insert(square(0.0, 0.0, 10.0), 0);
+        tree.insert(huge, 1);
+
+        assert_eq!(sorted(tree.query_point(Point::new(5.0, 5.0))), vec![0, 1]);
+        assert_eq!(sorted(tree.query_point(Point::new(1e6, -1e6))), vec![1]);
+
+        let mut tree = QuadTree::new();
+        tree.insert(huge, 1);
+        tree.insert(square(0.0, 0.0, 10.0), 0);
+
+        assert_eq!(sorted(tree.query_point(Point::new(5.0, 5.0))), vec![0, 1]);
+        assert_eq!(sorted(tree.query(square(-1e37, 0.0, 1.0))), vec![1]);
+    }
+}
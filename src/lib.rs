@@ -1,3 +1,12 @@
 pub mod canvas;
+pub mod easing;
+pub mod geometry;
+pub mod index;
+pub mod path_ops;
+pub mod route;
+pub mod text_edit;
+#[cfg(feature = "tiles")]
+pub mod tiles;
+pub mod tween;
 
 pub use canvas::*;
@@ -0,0 +1,765 @@
+//! Boolean operations on the areas filled by [`Path`]s, as when cutting
+//! shapes with an eraser, outlining several shapes as one or making the
+//! silhouette of a group of items, and the areas around their outlines, as
+//! when drawing halos or selection glows, or inflating the area an item is
+//! hit in.
+//!
+//! Paths are taken as filled with [`fill::Rule::NonZero`], with every
+//! subpath closed, and their curves are flattened into lines at most
+//! [`TOLERANCE`] units away from them. The resulting [`Path`] is made of
+//! lines only and is filled with the same rule.
+//!
+//! [`fill::Rule::NonZero`]: iced::widget::canvas::fill::Rule::NonZero
+
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::PI;
+
+use iced::widget::canvas::{
+    path::lyon_path::{
+        geom::euclid::default::{Point2D, Vector2D},
+        iterator::PathIterator,
+        math, Event as PathEvent,
+    },
+    Path,
+};
+
+use crate::geometry::TOLERANCE;
+
+/// A point in double precision, so that the points where edges cross are
+/// shared exactly by both edges.
+type Vertex = Point2D<f64>;
+
+/// The distance, relative to the extent of both paths, within which points
+/// are taken as the same.
+const SNAP: f64 = 1e-9;
+
+/// The distance, relative to the extent of both paths, from an edge to the
+/// points where the areas on either side of it are sampled.
+const SAMPLE: f64 = 1e-7;
+
+/// Returns the area filled by either `a` or `b`.
+pub fn union(a: &Path, b: &Path) -> Path {
+    combine(a, b, |a, b| a || b)
+}
+
+/// Returns the area filled by both `a` and `b`.
+pub fn intersection(a: &Path, b: &Path) -> Path {
+    combine(a, b, |a, b| a && b)
+}
+
+/// Returns the area filled by `a` but not by `b`.
+pub fn difference(a: &Path, b: &Path) -> Path {
+    combine(a, b, |a, b| a && !b)
+}
+
+/// Returns the area filled by exactly one of `a` and `b`.
+pub fn xor(a: &Path, b: &Path) -> Path {
+    combine(a, b, |a, b| a != b)
+}
+
+/// Returns the area within `distance` units of the area filled by `path`, or,
+/// for a negative `distance`, the area filled by `path` further than
+/// `-distance` units from its outline.
+///
+/// Corners are rounded outwards and kept sharp inwards.
+pub fn offset(path: &Path, distance: f32) -> Path {
+    let rings = rings(path);
+    let outlines: Vec<_> = rings.iter().map(|ring| (ring.clone(), true)).collect();
+    let strokes = strokes(&outlines, f64::from(distance.abs()));
+
+    if distance >= 0.0 {
+        resolve([rings, strokes], |a, b| a || b)
+    } else {
+        resolve([rings, strokes], |a, b| a && !b)
+    }
+}
+
+/// Returns the area covered by a stroke of `path` of the given `width`, with
+/// round joins and caps, as a [`Path`] to be filled.
+pub fn stroke(path: &Path, width: f32) -> Path {
+    let strokes = strokes(&polylines(path), f64::from(width.abs() / 2.0));
+
+    resolve([strokes, Vec::new()], |a, _| a)
+}
+
+/// An edge of the outline of one of the operands.
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    from: Vertex,
+    to: Vertex,
+    operand: usize,
+}
+
+/// Returns the outline of the area where `keep` holds for whether a point is
+/// filled by `a` and by `b`.
+fn combine(a: &Path, b: &Path, keep: impl Fn(bool, bool) -> bool) -> Path {
+    resolve([rings(a), rings(b)], keep)
+}
+
+/// Returns the outline of the area where `keep` holds for whether a point is
+/// within the `rings` of either operand, by the non-zero rule.
+///
+/// The outlines of both operands are split wherever they cross or touch, and
+/// each piece is kept if the area is kept on only one of its sides, directed
+/// so that the kept area is on its left.
+fn resolve(rings: [Vec<Vec<Vertex>>; 2], keep: impl Fn(bool, bool) -> bool) -> Path {
+    let extent = rings
+        .iter()
+        .flatten()
+        .flatten()
+        .fold(1.0_f64, |extent, point| {
+            extent.max(point.x.abs()).max(point.y.abs())
+        });
+    let snap = extent * SNAP;
+
+    let mut vertices = Vertices::new(snap);
+
+    let mut edges = Vec::new();
+
+    for (operand, rings) in rings.iter().enumerate() {
+        for ring in rings {
+            let ring: Vec<Vertex> = ring.iter().map(|point| vertices.snap(*point)).collect();
+
+            for (index, from) in ring.iter().enumerate() {
+                let to = ring[(index + 1) % ring.len()];
+
+                if *from != to {
+                    edges.push(Edge {
+                        from: *from,
+                        to,
+                        operand,
+                    });
+                }
+            }
+        }
+    }
+
+    let splits = split(&edges, &mut vertices, snap);
+    let windings = [Windings::new(&edges, 0), Windings::new(&edges, 1)];
+
+    let mut seen = HashSet::new();
+    let mut kept = Vec::new();
+
+    for (edge, splits) in edges.iter().zip(splits) {
+        let mut points = vec![edge.from];
+        points.extend(splits);
+        points.push(edge.to);
+
+        for piece in points.windows(2) {
+            let (from, to) = (piece[0], piece[1]);
+
+            if from == to || !seen.insert(undirected(from, to)) {
+                continue;
+            }
+
+            let direction = to - from;
+            let length = direction.length();
+            let normal = Vector2D::new(-direction.y, direction.x) / length;
+            let offset = (extent * SAMPLE).min(length * 1e-3);
+            let middle = from.lerp(to, 0.5);
+
+            let filled =
+                |point: Vertex| keep(windings[0].at(point) != 0, windings[1].at(point) != 0);
+
+            let left = filled(middle + normal * offset);
+            let right = filled(middle - normal * offset);
+
+            match (left, right) {
+                (true, false) => kept.push((from, to)),
+                (false, true) => kept.push((to, from)),
+                _ => {}
+            }
+        }
+    }
+
+    let rings = chain(&kept);
+
+    Path::new(|builder| {
+        for ring in rings {
+            for (index, point) in ring.into_iter().enumerate() {
+                let point = iced::Point::new(point.x as f32, point.y as f32);
+
+                if index == 0 {
+                    builder.move_to(point);
+                } else {
+                    builder.line_to(point);
+                }
+            }
+
+            builder.close();
+        }
+    })
+}
+
+/// Returns the points of each subpath of the flattened `path` which encloses
+/// an area.
+fn rings(path: &Path) -> Vec<Vec<Vertex>> {
+    polylines(path)
+        .into_iter()
+        .map(|(points, _)| points)
+        .filter(|points| points.len() > 2)
+        .collect()
+}
+
+/// Returns the points of each subpath of the flattened `path`, and whether
+/// the subpath is closed.
+fn polylines(path: &Path) -> Vec<(Vec<Vertex>, bool)> {
+    let vertex = |point: math::Point| Vertex::new(f64::from(point.x), f64::from(point.y));
+
+    let mut polylines = Vec::new();
+    let mut points = Vec::new();
+
+    for event in path.raw().iter().flattened(TOLERANCE) {
+        match event {
+            PathEvent::Begin { at } => points = vec![vertex(at)],
+            PathEvent::Line { to, .. } => points.push(vertex(to)),
+            PathEvent::End { close, .. } => polylines.push((std::mem::take(&mut points), close)),
+            _ => {}
+        }
+    }
+
+    polylines
+}
+
+/// Returns polygons covering a stroke of the `polylines` of the given
+/// `radius`, with round joins and caps, all wound counterclockwise.
+fn strokes(polylines: &[(Vec<Vertex>, bool)], radius: f64) -> Vec<Vec<Vertex>> {
+    if radius <= 0.0 {
+        return Vec::new();
+    }
+
+    // Sides of the circles at joins and caps, each at most the tolerance
+    // away from the circle
+    let sides = (PI / (1.0 - f64::from(TOLERANCE) / radius).max(-1.0).acos())
+        .ceil()
+        .clamp(8.0, 256.0) as usize;
+
+    let circle = |center: Vertex| -> Vec<Vertex> {
+        (0..sides)
+            .map(|side| {
+                let angle = 2.0 * PI * side as f64 / sides as f64;
+
+                center + Vector2D::new(angle.cos(), angle.sin()) * radius
+            })
+            .collect()
+    };
+
+    let normal = |direction: Vector2D<f64>| {
+        Vector2D::new(-direction.y, direction.x) / direction.length() * radius
+    };
+
+    let mut polygons = Vec::new();
+
+    for (points, closed) in polylines {
+        let mut points = points.clone();
+        points.dedup();
+
+        if *closed && points.len() > 1 && points.first() == points.last() {
+            let _ = points.pop();
+        }
+
+        let count = points.len();
+        let segments = if *closed && count > 1 {
+            count
+        } else {
+            count.saturating_sub(1)
+        };
+
+        for index in 0..segments {
+            let (from, to) = (points[index], points[(index + 1) % count]);
+            let normal = normal(to - from);
+
+            polygons.push(vec![from - normal, to - normal, to + normal, from + normal]);
+        }
+
+        for (index, point) in points.iter().enumerate() {
+            if count == 1 || (!*closed && (index == 0 || index == count - 1)) {
+                polygons.push(circle(*point));
+                continue;
+            }
+
+            let before = *point - points[(index + count - 1) % count];
+            let after = points[(index + 1) % count] - *point;
+            let cos = before.dot(after) / (before.length() * after.length());
+
+            // How far the arc of the join strays from the chord between the
+            // sides of both segments
+            if radius * (1.0 - ((1.0 + cos) / 2.0).max(0.0).sqrt()) > f64::from(TOLERANCE) {
+                polygons.push(circle(*point));
+                continue;
+            }
+
+            let (before, after) = (normal(before), normal(after));
+
+            for side in [1.0, -1.0] {
+                let mut triangle = vec![*point, *point + before * side, *point + after * side];
+                let area = (triangle[1] - triangle[0]).cross(triangle[2] - triangle[0]);
+
+                if area < 0.0 {
+                    triangle.reverse();
+                }
+
+                if area != 0.0 {
+                    polygons.push(triangle);
+                }
+            }
+        }
+    }
+
+    polygons
+}
+
+/// Returns, for each of the `edges`, the points along it, in order, where
+/// other edges cross or touch it.
+fn split(edges: &[Edge], vertices: &mut Vertices, snap: f64) -> Vec<Vec<Vertex>> {
+    let mut splits = vec![Vec::new(); edges.len()];
+
+    // Only edges which overlap along the X axis are compared, in order of
+    // their left ends
+    let left = |edge: &Edge| edge.from.x.min(edge.to.x);
+    let right = |edge: &Edge| edge.from.x.max(edge.to.x);
+
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_by(|i, j| left(&edges[*i]).total_cmp(&left(&edges[*j])));
+
+    for (position, &i) in order.iter().enumerate() {
+        let a = &edges[i];
+
+        for &j in &order[position + 1..] {
+            let b = &edges[j];
+
+            if left(b) > right(a) + snap {
+                break;
+            }
+
+            let apart = a.from.y.max(a.to.y) + snap < b.from.y.min(b.to.y)
+                || b.from.y.max(b.to.y) + snap < a.from.y.min(a.to.y);
+
+            if apart {
+                continue;
+            }
+
+            // Endpoints of one edge lying on the other, which also covers
+            // edges overlapping along a line
+            let mut touching = false;
+
+            for point in [b.from, b.to] {
+                if within(a, point, snap) {
+                    splits[i].push(point);
+                    touching = true;
+                }
+            }
+
+            for point in [a.from, a.to] {
+                if within(b, point, snap) {
+                    splits[j].push(point);
+                    touching = true;
+                }
+            }
+
+            if touching {
+                continue;
+            }
+
+            let (first, second) = (a.to - a.from, b.to - b.from);
+            let denominator = first.cross(second);
+
+            if denominator.abs() <= f64::EPSILON * first.length() * second.length() {
+                continue;
+            }
+
+            let offset = b.from - a.from;
+            let t = offset.cross(second) / denominator;
+            let u = offset.cross(first) / denominator;
+
+            if t <= 0.0 || t >= 1.0 || u <= 0.0 || u >= 1.0 {
+                continue;
+            }
+
+            let point = a.from + first * t;
+            let shared = [a.from, a.to, b.from, b.to]
+                .into_iter()
+                .any(|end| (end - point).length() <= snap);
+
+            if !shared {
+                let point = vertices.snap(point);
+
+                splits[i].push(point);
+                splits[j].push(point);
+            }
+        }
+    }
+
+    for (edge, splits) in edges.iter().zip(splits.iter_mut()) {
+        let direction = edge.to - edge.from;
+
+        splits.sort_by(|p, q| {
+            (*p - edge.from)
+                .dot(direction)
+                .total_cmp(&(*q - edge.from).dot(direction))
+        });
+        splits.dedup();
+    }
+
+    splits
+}
+
+/// Returns whether `point` lies on the `edge`, away from its endpoints.
+fn within(edge: &Edge, point: Vertex, snap: f64) -> bool {
+    if point == edge.from || point == edge.to {
+        return false;
+    }
+
+    let direction = edge.to - edge.from;
+    let t = (point - edge.from).dot(direction) / direction.square_length();
+
+    t > 0.0
+        && t < 1.0
+        && (edge.from + direction * t - point).length() <= snap
+        && (point - edge.from).length() > snap
+        && (point - edge.to).length() > snap
+}
+
+/// The edges of the outline of an operand, grouped into horizontal bands so
+/// that only those level with a point are counted for its winding number.
+#[derive(Debug)]
+struct Windings<'a> {
+    edges: Vec<&'a Edge>,
+    bottom: f64,
+    height: f64,
+    bands: Vec<Vec<usize>>,
+}
+
+impl<'a> Windings<'a> {
+    fn new(edges: &'a [Edge], operand: usize) -> Self {
+        let edges: Vec<&Edge> = edges
+            .iter()
+            .filter(|edge| edge.operand == operand)
+            .collect();
+
+        let bottom = edges
+            .iter()
+            .map(|edge| edge.from.y.min(edge.to.y))
+            .fold(f64::INFINITY, f64::min);
+        let top = edges
+            .iter()
+            .map(|edge| edge.from.y.max(edge.to.y))
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        let count = (edges.len() as f64).sqrt().ceil().max(1.0) as usize;
+        let height = ((top - bottom) / count as f64).max(f64::MIN_POSITIVE);
+
+        let mut bands = vec![Vec::new(); count];
+
+        for (index, edge) in edges.iter().enumerate() {
+            let band = |y: f64| (((y - bottom) / height).floor() as usize).min(count - 1);
+
+            for band in
+                &mut bands[band(edge.from.y.min(edge.to.y))..=band(edge.from.y.max(edge.to.y))]
+            {
+                band.push(index);
+            }
+        }
+
+        Self {
+            edges,
+            bottom,
+            height,
+            bands,
+        }
+    }
+
+    /// Returns the winding number of the outline around `point`.
+    fn at(&self, point: Vertex) -> i32 {
+        if self.edges.is_empty() || point.y < self.bottom {
+            return 0;
+        }
+
+        let band = ((point.y - self.bottom) / self.height).floor() as usize;
+
+        let Some(band) = self.bands.get(band) else {
+            return 0;
+        };
+
+        band.iter()
+            .map(|index| {
+                let edge = self.edges[*index];
+                let side = (edge.to - edge.from).cross(point - edge.from);
+
+                if edge.from.y <= point.y && point.y < edge.to.y && side > 0.0 {
+                    1
+                } else if edge.to.y <= point.y && point.y < edge.from.y && side < 0.0 {
+                    -1
+                } else {
+                    0
+                }
+            })
+            .sum()
+    }
+}
+
+/// Joins directed `edges` end to start into closed rings.
+fn chain(edges: &[(Vertex, Vertex)]) -> Vec<Vec<Vertex>> {
+    let mut outgoing: HashMap<_, Vec<usize>> = HashMap::new();
+
+    for (index, (from, _)) in edges.iter().enumerate() {
+        outgoing.entry(key(*from)).or_default().push(index);
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut rings = Vec::new();
+
+    for start in 0..edges.len() {
+        if used[start] {
+            continue;
+        }
+
+        let mut ring = vec![edges[start].0];
+        let mut current = start;
+
+        loop {
+            used[current] = true;
+
+            let to = edges[current].1;
+
+            if to == edges[start].0 {
+                break;
+            }
+
+            ring.push(to);
+
+            let next = outgoing
+                .get(&key(to))
+                .and_then(|candidates| candidates.iter().find(|index| !used[**index]));
+
+            match next {
+                Some(next) => current = *next,
+                None => break,
+            }
+        }
+
+        if ring.len() > 2 {
+            rings.push(ring);
+        }
+    }
+
+    rings
+}
+
+/// Returns a key of a point, the same for both zeros.
+fn key(point: Vertex) -> (u64, u64) {
+    ((point.x + 0.0).to_bits(), (point.y + 0.0).to_bits())
+}
+
+/// Returns a key of a line between two points, the same in both directions.
+fn undirected(from: Vertex, to: Vertex) -> ((u64, u64), (u64, u64)) {
+    let (from, to) = (key(from), key(to));
+
+    (from.min(to), from.max(to))
+}
+
+/// The points made so far, so that points closer than a distance are merged.
+#[derive(Debug)]
+struct Vertices {
+    size: f64,
+    cells: HashMap<(i64, i64), Vec<Vertex>>,
+}
+
+impl Vertices {
+    fn new(size: f64) -> Self {
+        Self {
+            size,
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Returns the point made so far closest to `point`, if within the
+    /// distance, or else `point`, which is kept.
+    fn snap(&mut self, point: Vertex) -> Vertex {
+        let cell = (
+            (point.x / self.size).floor() as i64,
+            (point.y / self.size).floor() as i64,
+        );
+
+        let existing = (-1..=1)
+            .flat_map(|x| (-1..=1).map(move |y| (cell.0 + x, cell.1 + y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .find(|other| (**other - point).length() <= self.size);
+
+        if let Some(existing) = existing {
+            return *existing;
+        }
+
+        self.cells.entry(cell).or_default().push(point);
+
+        point
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use iced::widget::canvas::fill;
+    use iced::{Point, Size};
+
+    use crate::geometry::fill_contains;
+
+    fn square(x: f32, y: f32, size: f32) -> Path {
+        Path::rectangle(Point::new(x, y), Size::new(size, size))
+    }
+
+    /// Returns the area filled by `path`, which winds its holes against its
+    /// outlines.
+    fn area(path: &Path) -> f64 {
+        let signed: f64 = rings(path)
+            .iter()
+            .map(|ring| {
+                ring.iter()
+                    .zip(ring.iter().cycle().skip(1))
+                    .map(|(a, b)| a.x * b.y - b.x * a.y)
+                    .sum::<f64>()
+                    / 2.0
+            })
+            .sum();
+
+        signed.abs()
+    }
+
+    fn assert_area(path: &Path, expected: f64) {
+        let area = area(path);
+
+        assert!(
+            (area - expected).abs() < 1e-3,
+            "area {area}, expected {expected}"
+        );
+    }
+
+    #[test]
+    fn overlapping_squares() {
+        let a = square(0.0, 0.0, 2.0);
+        let b = square(1.0, 1.0, 2.0);
+
+        assert_area(&union(&a, &b), 7.0);
+        assert_area(&intersection(&a, &b), 1.0);
+        assert_area(&difference(&a, &b), 3.0);
+        assert_area(&xor(&a, &b), 6.0);
+    }
+
+    #[test]
+    fn squares_sharing_an_edge() {
+        let a = square(0.0, 0.0, 1.0);
+        let b = square(1.0, 0.0, 1.0);
+
+        assert_area(&union(&a, &b), 2.0);
+        assert_area(&intersection(&a, &b), 0.0);
+        assert_area(&difference(&a, &b), 1.0);
+        assert_area(&xor(&a, &b), 2.0);
+
+        // The shared edge is dropped from the outline of the union
+        assert_eq!(rings(&union(&a, &b)).len(), 1);
+    }
+
+    #[test]
+    fn identical_squares() {
+        let a = square(0.0, 0.0, 2.0);
+
+        assert_area(&union(&a, &a), 4.0);
+        assert_area(&intersection(&a, &a), 4.0);
+        assert_area(&difference(&a, &a), 0.0);
+        assert_area(&xor(&a, &a), 0.0);
+    }
+
+    #[test]
+    fn squares_with_holes() {
+        let outer = square(0.0, 0.0, 4.0);
+        let inner = square(1.0, 1.0, 2.0);
+        let frame = difference(&outer, &inner);
+
+        assert_area(&frame, 12.0);
+        assert!(!fill_contains(
+            &frame,
+            Point::new(2.0, 2.0),
+            fill::Rule::NonZero
+        ));
+
+        assert_area(&union(&frame, &inner), 16.0);
+        assert_area(&intersection(&frame, &inner), 0.0);
+        assert_area(&xor(&frame, &outer), 4.0);
+    }
+
+    #[test]
+    fn empty_operands() {
+        let a = square(0.0, 0.0, 2.0);
+        let empty = Path::new(|_| {});
+
+        assert_area(&union(&a, &empty), 4.0);
+        assert_area(&union(&empty, &a), 4.0);
+        assert_area(&intersection(&a, &empty), 0.0);
+        assert_area(&difference(&a, &empty), 4.0);
+        assert_area(&difference(&empty, &a), 0.0);
+        assert_area(&xor(&empty, &empty), 0.0);
+    }
+
+    #[test]
+    fn offset_rounds_outer_corners() {
+        let a = square(0.0, 0.0, 2.0);
+
+        // The square, a band along each side and a quarter circle per corner
+        let expected = 4.0 + 4.0 * 2.0 + PI;
+        let grown = area(&offset(&a, 1.0));
+
+        assert!((grown - expected).abs() < 0.05, "area {grown}");
+        assert_area(&offset(&a, 0.0), 4.0);
+    }
+
+    #[test]
+    fn negative_offset_shrinks() {
+        let a = square(0.0, 0.0, 2.0);
+
+        assert_area(&offset(&a, -0.5), 1.0);
+        assert_area(&offset(&a, -1.5), 0.0);
+    }
+
+    #[test]
+    fn stroke_of_a_line() {
+        let line = Path::line(Point::new(0.0, 0.0), Point::new(4.0, 0.0));
+
+        // A band along the line and a half circle per cap
+        let expected = 4.0 * 2.0 + PI;
+        let covered = area(&stroke(&line, 2.0));
+
+        assert!((covered - expected).abs() < 0.05, "area {covered}");
+        assert!(fill_contains(
+            &stroke(&line, 2.0),
+            Point::new(2.0, 0.9),
+            fill::Rule::NonZero
+        ));
+        assert_area(&stroke(&line, 0.0), 0.0);
+    }
+
+    #[test]
+    fn stroke_of_a_closed_square() {
+        let a = square(0.0, 0.0, 2.0);
+        let outline = stroke(&a, 1.0);
+
+        // The stroke leaves the middle of the square empty
+        assert!(!fill_contains(
+            &outline,
+            Point::new(1.0, 1.0),
+            fill::Rule::NonZero
+        ));
+        assert!(fill_contains(
+            &outline,
+            Point::new(0.0, 1.0),
+            fill::Rule::NonZero
+        ));
+
+        let covered = area(&outline);
+        // The square grown by half the width, less the square shrunk by it
+        let expected = 4.0 + 4.0 * 2.0 * 0.5 + PI / 4.0 - 1.0;
+
+        assert!((covered - expected).abs() < 0.05, "area {covered}");
+    }
+}
@@ -0,0 +1,503 @@
+//! Route connectors between rectangles on an [`Infinite`] along horizontal
+//! and vertical lines, as in node editors and flowcharts.
+//!
+//! A route leaves the middle of a side of one rectangle and enters the middle
+//! of a side of the other, going around the obstacles in its way. Among the
+//! shortest routes, those with fewer bends are preferred, see
+//! [`RouteConfig::bend_cost`].
+//!
+//! [`Infinite`]: crate::Infinite
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use iced::{widget::canvas::Path, Point, Rectangle, Vector};
+
+/// The directions a route can move in, see [`turn`].
+const DIRECTIONS: [Vector; 4] = [
+    Vector::new(1.0, 0.0),
+    Vector::new(-1.0, 0.0),
+    Vector::new(0.0, 1.0),
+    Vector::new(0.0, -1.0),
+];
+
+/// Determines how connectors are routed, see [`route`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RouteConfig {
+    /// The distance, in units, a route keeps from the rectangles and
+    /// obstacles it passes.
+    pub margin: f32,
+    /// The radius, in units, of the rounded corners of a route. A radius of
+    /// 0.0 gives sharp corners.
+    pub corner_radius: f32,
+    /// The extra length, in units, a bend counts as. Higher costs give
+    /// routes with fewer bends at the expense of longer ones.
+    pub bend_cost: f32,
+}
+
+impl Default for RouteConfig {
+    fn default() -> Self {
+        Self {
+            margin: 10.0,
+            corner_radius: 5.0,
+            bend_cost: 20.0,
+        }
+    }
+}
+
+/// Returns a connector from the rectangle `from` to the rectangle `to` made
+/// of horizontal and vertical lines which go around the `obstacles`.
+///
+/// See [`waypoints`] for the corners of the connector.
+pub fn route(from: Rectangle, to: Rectangle, obstacles: &[Rectangle], config: RouteConfig) -> Path {
+    let points = waypoints(from, to, obstacles, config);
+
+    rounded(&points, config.corner_radius)
+}
+
+/// Returns the ends and corners of a connector from the rectangle `from` to
+/// the rectangle `to` made of horizontal and vertical lines which go around
+/// the `obstacles`.
+///
+/// If no such connector exists, a connector which ignores the `obstacles` is
+/// returned instead.
+pub fn waypoints(
+    from: Rectangle,
+    to: Rectangle,
+    obstacles: &[Rectangle],
+    config: RouteConfig,
+) -> Vec<Point> {
+    let points = search(from, to, obstacles, config).unwrap_or_else(|| elbow(from, to));
+
+    simplify(points)
+}
+
+/// The middle of a side of a rectangle, the point `margin` away from it and
+/// the direction from the former to the latter.
+#[derive(Debug, Clone, Copy)]
+struct Port {
+    point: Point,
+    stub: Point,
+    direction: usize,
+}
+
+fn ports(rectangle: Rectangle, margin: f32) -> [Port; 4] {
+    let center = rectangle.center();
+    let (left, right) = (rectangle.x, rectangle.x + rectangle.width);
+    let (low, high) = (rectangle.y, rectangle.y + rectangle.height);
+
+    [
+        (Point::new(right, center.y), 0),
+        (Point::new(left, center.y), 1),
+        (Point::new(center.x, high), 2),
+        (Point::new(center.x, low), 3),
+    ]
+    .map(|(point, direction)| Port {
+        point,
+        stub: point + DIRECTIONS[direction] * margin,
+        direction,
+    })
+}
+
+/// Returns the extra cost of turning from the direction `from` to `to`.
+fn turn(from: usize, to: usize, bend_cost: f32) -> f32 {
+    if from == to {
+        0.0
+    } else if from / 2 == to / 2 {
+        2.0 * bend_cost
+    } else {
+        bend_cost
+    }
+}
+
+/// Returns whether `point` lies strictly inside `rectangle`.
+fn inside(rectangle: &Rectangle, point: Point) -> bool {
+    point.x > rectangle.x
+        && point.x < rectangle.x + rectangle.width
+        && point.y > rectangle.y
+        && point.y < rectangle.y + rectangle.height
+}
+
+/// Returns whether the horizontal or vertical segment from `a` to `b` passes
+/// through the inside of `rectangle`.
+fn crosses(rectangle: &Rectangle, a: Point, b: Point) -> bool {
+    let (left, right) = (a.x.min(b.x), a.x.max(b.x));
+    let (low, high) = (a.y.min(b.y), a.y.max(b.y));
+
+    let across_x = if left == right {
+        left > rectangle.x && left < rectangle.x + rectangle.width
+    } else {
+        right > rectangle.x && left < rectangle.x + rectangle.width
+    };
+    let across_y = if low == high {
+        low > rectangle.y && low < rectangle.y + rectangle.height
+    } else {
+        high > rectangle.y && low < rectangle.y + rectangle.height
+    };
+
+    across_x && across_y
+}
+
+/// A state of the search: a point of the grid and the direction it was
+/// reached in.
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    cost: f32,
+    node: usize,
+    direction: usize,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost.total_cmp(&other.cost).is_eq()
+    }
+}
+
+impl Eq for Entry {}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed, so the cheapest entry is popped first
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Searches a grid made from the edges of the rectangles, grown by the
+/// margin, for the cheapest connector.
+fn search(
+    from: Rectangle,
+    to: Rectangle,
+    obstacles: &[Rectangle],
+    config: RouteConfig,
+) -> Option<Vec<Point>> {
+    let margin = config.margin.max(0.0);
+    let grow = |rectangle: &Rectangle| Rectangle {
+        x: rectangle.x - margin,
+        y: rectangle.y - margin,
+        width: rectangle.width + 2.0 * margin,
+        height: rectangle.height + 2.0 * margin,
+    };
+
+    let blocked: Vec<Rectangle> = obstacles.iter().chain([&from, &to]).map(grow).collect();
+
+    let starts = ports(from, margin);
+    let ends = ports(to, margin);
+
+    let mut xs = Vec::new();
+    let mut ys = Vec::new();
+
+    for rectangle in &blocked {
+        xs.extend([rectangle.x, rectangle.x + rectangle.width]);
+        ys.extend([rectangle.y, rectangle.y + rectangle.height]);
+    }
+
+    for port in starts.iter().chain(&ends) {
+        xs.push(port.stub.x);
+        ys.push(port.stub.y);
+    }
+
+    xs.sort_by(f32::total_cmp);
+    xs.dedup();
+    ys.sort_by(f32::total_cmp);
+    ys.dedup();
+
+    let point = |node: usize| Point::new(xs[node / ys.len()], ys[node % ys.len()]);
+    let node_at = |point: Point| {
+        let x = xs.binary_search_by(|x| x.total_cmp(&point.x)).ok()?;
+        let y = ys.binary_search_by(|y| y.total_cmp(&point.y)).ok()?;
+
+        Some(x * ys.len() + y)
+    };
+    let free = |point: Point| !blocked.iter().any(|rectangle| inside(rectangle, point));
+
+    let states = xs.len() * ys.len() * DIRECTIONS.len();
+    let mut costs = vec![f32::INFINITY; states];
+    let mut previous = vec![usize::MAX; states];
+    let mut heap = BinaryHeap::new();
+
+    for port in starts.iter().filter(|port| free(port.stub)) {
+        let Some(node) = node_at(port.stub) else {
+            continue;
+        };
+        let state = node * DIRECTIONS.len() + port.direction;
+
+        costs[state] = margin;
+        heap.push(Entry {
+            cost: margin,
+            node,
+            direction: port.direction,
+        });
+    }
+
+    let mut best: Option<(f32, usize, Port)> = None;
+
+    while let Some(Entry {
+        cost,
+        node,
+        direction,
+    }) = heap.pop()
+    {
+        if best.is_some_and(|(best, _, _)| cost >= best) {
+            break;
+        }
+
+        let state = node * DIRECTIONS.len() + direction;
+
+        if cost > costs[state] {
+            continue;
+        }
+
+        let here = point(node);
+
+        for port in ends.iter().filter(|port| port.stub == here) {
+            // Entering the port runs against the direction it faces
+            let inward = port.direction ^ 1;
+            let total = cost + turn(direction, inward, config.bend_cost) + margin;
+
+            if best.is_none_or(|(best, _, _)| total < best) {
+                best = Some((total, state, *port));
+            }
+        }
+
+        let (x, y) = (node / ys.len(), node % ys.len());
+        let neighbours = [
+            (x + 1 < xs.len()).then(|| node + ys.len()),
+            x.checked_sub(1).map(|_| node - ys.len()),
+            (y + 1 < ys.len()).then(|| node + 1),
+            y.checked_sub(1).map(|_| node - 1),
+        ];
+
+        for (next_direction, next) in neighbours.into_iter().enumerate() {
+            let Some(next) = next else {
+                continue;
+            };
+            let there = point(next);
+
+            if !free(there)
+                || blocked
+                    .iter()
+                    .any(|rectangle| crosses(rectangle, here, there))
+            {
+                continue;
+            }
+
+            let next_cost = cost
+                + (there - here).x.abs()
+                + (there - here).y.abs()
+                + turn(direction, next_direction, config.bend_cost);
+            let next_state = next * DIRECTIONS.len() + next_direction;
+
+            if next_cost < costs[next_state] {
+                costs[next_state] = next_cost;
+                previous[next_state] = state;
+                heap.push(Entry {
+                    cost: next_cost,
+                    node: next,
+                    direction: next_direction,
+                });
+            }
+        }
+    }
+
+    let (_, mut state, end) = best?;
+    let mut points = vec![end.point];
+
+    while state != usize::MAX {
+        points.push(point(state / DIRECTIONS.len()));
+        state = previous[state];
+    }
+
+    let start = points.last().copied()?;
+    let start = starts.iter().find(|port| port.stub == start)?;
+
+    points.push(start.point);
+    points.reverse();
+
+    Some(points)
+}
+
+/// Returns a connector between the facing sides of `from` and `to` with a
+/// single bend in each direction, ignoring any obstacles.
+fn elbow(from: Rectangle, to: Rectangle) -> Vec<Point> {
+    let (a, b) = (from.center(), to.center());
+
+    if (b.x - a.x).abs() >= (b.y - a.y).abs() {
+        let sign = (b.x - a.x).signum();
+        let start = Point::new(a.x + sign * from.width / 2.0, a.y);
+        let end = Point::new(b.x - sign * to.width / 2.0, b.y);
+        let middle = (start.x + end.x) / 2.0;
+
+        vec![
+            start,
+            Point::new(middle, start.y),
+            Point::new(middle, end.y),
+            end,
+        ]
+    } else {
+        let sign = (b.y - a.y).signum();
+        let start = Point::new(a.x, a.y + sign * from.height / 2.0);
+        let end = Point::new(b.x, b.y - sign * to.height / 2.0);
+        let middle = (start.y + end.y) / 2.0;
+
+        vec![
+            start,
+            Point::new(start.x, middle),
+            Point::new(end.x, middle),
+            end,
+        ]
+    }
+}
+
+/// Removes repeated points and points in the middle of straight lines.
+fn simplify(points: Vec<Point>) -> Vec<Point> {
+    let mut simplified: Vec<Point> = Vec::with_capacity(points.len());
+
+    for point in points {
+        if simplified.last() == Some(&point) {
+            continue;
+        }
+
+        if let [.., a, b] = simplified[..] {
+            let straight = (a.x == b.x && b.x == point.x) || (a.y == b.y && b.y == point.y);
+
+            if straight {
+                simplified.pop();
+            }
+        }
+
+        simplified.push(point);
+    }
+
+    simplified
+}
+
+/// Returns the [`Path`] through `points` with its corners rounded by `radius`.
+fn rounded(points: &[Point], radius: f32) -> Path {
+    Path::new(|builder| {
+        let Some((first, rest)) = points.split_first() else {
+            return;
+        };
+
+        builder.move_to(*first);
+
+        for window in points.windows(3) {
+            let [previous, corner, next] = [window[0], window[1], window[2]];
+            let radius = radius
+                .min(previous.distance(corner) / 2.0)
+                .min(corner.distance(next) / 2.0)
+                .max(0.0);
+
+            builder.arc_to(corner, next, radius);
+        }
+
+        if let Some(last) = rest.last() {
+            builder.line_to(*last);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use iced::Size;
+
+    fn rectangle(x: f32, y: f32, width: f32, height: f32) -> Rectangle {
+        Rectangle::new(Point::new(x, y), Size::new(width, height))
+    }
+
+    fn assert_orthogonal(points: &[Point]) {
+        for segment in points.windows(2) {
+            assert!(
+                segment[0].x == segment[1].x || segment[0].y == segment[1].y,
+                "diagonal segment in {points:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn straight_between_facing_sides() {
+        let from = rectangle(0.0, 0.0, 10.0, 10.0);
+        let to = rectangle(50.0, 0.0, 10.0, 10.0);
+
+        let points = waypoints(from, to, &[], RouteConfig::default());
+
+        assert_eq!(points, vec![Point::new(10.0, 5.0), Point::new(50.0, 5.0)]);
+    }
+
+    #[test]
+    fn goes_around_obstacles() {
+        let from = rectangle(0.0, 0.0, 10.0, 10.0);
+        let to = rectangle(100.0, 0.0, 10.0, 10.0);
+        let obstacle = rectangle(40.0, -20.0, 20.0, 50.0);
+        let config = RouteConfig::default();
+
+        let points = waypoints(from, to, &[obstacle], config);
+
+        assert_orthogonal(&points);
+        assert!(points.len() > 2);
+
+        // Each end is in the middle of a side of its rectangle
+        let middles = |rectangle| ports(rectangle, 0.0).map(|port| port.point);
+        assert!(middles(from).contains(&points[0]));
+        assert!(middles(to).contains(&points[points.len() - 1]));
+
+        let grown = rectangle(
+            obstacle.x - config.margin + 0.1,
+            obstacle.y - config.margin + 0.1,
+            obstacle.width + 2.0 * config.margin - 0.2,
+            obstacle.height + 2.0 * config.margin - 0.2,
+        );
+
+        for segment in points.windows(2) {
+            assert!(!crosses(&grown, segment[0], segment[1]));
+        }
+    }
+
+    #[test]
+    fn fewer_bends_with_a_higher_cost() {
+        let from = rectangle(0.0, 0.0, 10.0, 10.0);
+        let to = rectangle(100.0, 60.0, 10.0, 10.0);
+        let obstacle = rectangle(40.0, 20.0, 20.0, 20.0);
+
+        let bends = |bend_cost| {
+            let config = RouteConfig {
+                bend_cost,
+                ..RouteConfig::default()
+            };
+            let points = waypoints(from, to, &[obstacle], config);
+            assert_orthogonal(&points);
+
+            points.len() - 2
+        };
+
+        assert!(bends(1000.0) <= bends(0.0));
+        assert_eq!(bends(1000.0), 1);
+    }
+
+    #[test]
+    fn ignores_obstacles_without_a_route() {
+        let from = rectangle(0.0, 0.0, 10.0, 10.0);
+        let to = rectangle(100.0, 40.0, 10.0, 10.0);
+        // Covers every side of `to`
+        let obstacle = rectangle(80.0, 20.0, 50.0, 50.0);
+
+        let points = waypoints(from, to, &[obstacle], RouteConfig::default());
+
+        assert_eq!(
+            points,
+            vec![
+                Point::new(10.0, 5.0),
+                Point::new(55.0, 5.0),
+                Point::new(55.0, 45.0),
+                Point::new(100.0, 45.0),
+            ]
+        );
+    }
+}
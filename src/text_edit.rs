@@ -0,0 +1,647 @@
+//! Edit text drawn on an [`Infinite`], with a caret and a selection.
+//!
+//! A [`TextEditor`] is kept in the state of a [`Program`], which hands it
+//! the events of [`Program::update`] and draws it on a [`Buffer`], as in:
+//!
+//! ```ignore
+//! // In Program::update
+//! return (state.editor.update(&event, clipboard).status(), None);
+//!
+//! // In Program::draw
+//! state.editor.draw(&mut buffer, Text { position, ..Text::default() }, selection);
+//! ```
+//!
+//! Copying, cutting and pasting go through the clipboard handed to
+//! [`Program::update`].
+//!
+//! [`Infinite`]: crate::Infinite
+//! [`Program`]: crate::Program
+//! [`Program::update`]: crate::Program::update
+
+use std::ops::Range;
+
+use iced::{
+    advanced::{clipboard, Clipboard},
+    keyboard::{self, key::Named, Key},
+    Color, Point, Rectangle, Size, Vector,
+};
+
+use crate::canvas::{event, line_width, Buffer, Text};
+
+/// The width, in pixels, of the caret.
+const CARET_WIDTH: f32 = 1.5;
+
+/// A piece of text being edited, with a caret and a selection.
+///
+/// Positions within the text are byte indices, always on the boundary of a
+/// character.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextEditor {
+    content: String,
+    /// The position of the caret.
+    caret: usize,
+    /// The other end of the selection, if there is any.
+    anchor: Option<usize>,
+}
+
+/// The outcome of handing an event to a [`TextEditor`], see
+/// [`TextEditor::update`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Update {
+    /// The event was not meant for the editor.
+    Ignored,
+    /// The text, caret or selection changed.
+    Edited,
+    /// The selection was copied to the clipboard.
+    Copied,
+}
+
+impl Update {
+    /// Returns the status of the event which gave the [`Update`], as returned
+    /// from [`Program::update`](crate::Program::update).
+    pub fn status(&self) -> event::Status {
+        match self {
+            Self::Ignored => event::Status::Ignored,
+            Self::Edited | Self::Copied => event::Status::Captured,
+        }
+    }
+}
+
+impl TextEditor {
+    /// Creates a new [`TextEditor`] of the given `content`, with the caret at
+    /// its end.
+    pub fn new(content: impl Into<String>) -> Self {
+        let content = content.into();
+
+        Self {
+            caret: content.len(),
+            content,
+            anchor: None,
+        }
+    }
+
+    /// Returns the text of the [`TextEditor`].
+    pub fn text(&self) -> &str {
+        &self.content
+    }
+
+    /// Returns the position of the caret.
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    /// Returns the range of the selected text, if any is selected.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor.filter(|anchor| *anchor != self.caret)?;
+
+        Some(anchor.min(self.caret)..anchor.max(self.caret))
+    }
+
+    /// Returns the selected text, if any is selected.
+    pub fn selected_text(&self) -> Option<&str> {
+        self.selection().map(|range| &self.content[range])
+    }
+
+    /// Moves the caret to `index`, extending the selection to it if `select`
+    /// is true.
+    ///
+    /// The index is moved back to the nearest boundary of a character within
+    /// the text.
+    pub fn move_to(&mut self, index: usize, select: bool) {
+        let mut index = index.min(self.content.len());
+
+        while !self.content.is_char_boundary(index) {
+            index -= 1;
+        }
+
+        self.anchor = match select {
+            true => self.anchor.or(Some(self.caret)),
+            false => None,
+        };
+        self.caret = index;
+    }
+
+    /// Selects the whole text.
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.caret = self.content.len();
+    }
+
+    /// Inserts `text` at the caret, replacing the selection.
+    pub fn insert(&mut self, text: &str) {
+        let range = self.selection().unwrap_or(self.caret..self.caret);
+
+        self.content.replace_range(range.clone(), text);
+        self.caret = range.start + text.len();
+        self.anchor = None;
+    }
+
+    /// Inserts pasted `text` at the caret, replacing the selection.
+    ///
+    /// Line breaks are kept, while other control characters are left out.
+    pub fn paste(&mut self, text: &str) {
+        let text: String = text
+            .chars()
+            .filter(|c| *c == '\n' || !c.is_control())
+            .collect();
+
+        self.insert(&text);
+    }
+
+    /// Deletes the selection, or the character before the caret if nothing
+    /// is selected.
+    pub fn delete_backward(&mut self) {
+        if self.selection().is_none() {
+            self.move_to(self.previous_char(), true);
+        }
+
+        self.insert("");
+    }
+
+    /// Deletes the selection, or the character after the caret if nothing is
+    /// selected.
+    pub fn delete_forward(&mut self) {
+        if self.selection().is_none() {
+            self.move_to(self.next_char(), true);
+        }
+
+        self.insert("");
+    }
+
+    /// Edits the text with a keyboard `event`.
+    ///
+    /// Typing inserts text and Enter starts a new line. The arrow keys, Home
+    /// and End move the caret, extending the selection while Shift is held
+    /// and moving by words with the jump modifier (Alt on macOS, Ctrl
+    /// elsewhere). Cmd(Ctrl) + A selects the whole text, while Cmd(Ctrl) + C,
+    /// X and V copy, cut and paste through the `clipboard`.
+    pub fn update(&mut self, event: &event::Event, clipboard: &mut dyn Clipboard) -> Update {
+        let event::Event::Keyboard(keyboard::Event::KeyPressed {
+            key,
+            modifiers,
+            text,
+            ..
+        }) = event
+        else {
+            return Update::Ignored;
+        };
+
+        let select = modifiers.shift();
+        let jump = modifiers.jump();
+
+        match key.as_ref() {
+            Key::Character(character) if modifiers.command() => match character {
+                "a" => self.select_all(),
+                "c" | "x" => {
+                    let Some(text) = self.selected_text().map(str::to_string) else {
+                        return Update::Ignored;
+                    };
+
+                    clipboard.write(clipboard::Kind::Standard, text);
+
+                    if character == "c" {
+                        return Update::Copied;
+                    }
+
+                    self.insert("");
+                }
+                "v" => match clipboard.read(clipboard::Kind::Standard) {
+                    Some(text) => self.paste(&text),
+                    None => return Update::Ignored,
+                },
+                _ => return Update::Ignored,
+            },
+            Key::Named(Named::Backspace) => {
+                if jump && self.selection().is_none() {
+                    self.move_to(self.previous_word(), true);
+                }
+
+                self.delete_backward();
+            }
+            Key::Named(Named::Delete) => {
+                if jump && self.selection().is_none() {
+                    self.move_to(self.next_word(), true);
+                }
+
+                self.delete_forward();
+            }
+            Key::Named(Named::ArrowLeft) => match self.selection() {
+                // Collapses the selection to its start
+                Some(range) if !select => self.move_to(range.start, false),
+                _ if jump => self.move_to(self.previous_word(), select),
+                _ => self.move_to(self.previous_char(), select),
+            },
+            Key::Named(Named::ArrowRight) => match self.selection() {
+                Some(range) if !select => self.move_to(range.end, false),
+                _ if jump => self.move_to(self.next_word(), select),
+                _ => self.move_to(self.next_char(), select),
+            },
+            Key::Named(Named::ArrowUp) => self.move_to(self.line_above(), select),
+            Key::Named(Named::ArrowDown) => self.move_to(self.line_below(), select),
+            Key::Named(Named::Home) if modifiers.command() => self.move_to(0, select),
+            Key::Named(Named::End) if modifiers.command() => {
+                self.move_to(self.content.len(), select)
+            }
+            Key::Named(Named::Home) => self.move_to(self.line_start(self.caret), select),
+            Key::Named(Named::End) => self.move_to(self.line_end(self.caret), select),
+            Key::Named(Named::Enter) => self.insert("\n"),
+            _ => {
+                let text = text
+                    .as_deref()
+                    .filter(|text| !text.chars().any(char::is_control))
+                    .filter(|_| !modifiers.command());
+
+                match text {
+                    Some(text) => self.insert(text),
+                    None => return Update::Ignored,
+                }
+            }
+        }
+
+        Update::Edited
+    }
+
+    /// Returns the position of the text closest to the point `offset` pixels
+    /// from the top-left corner of the `text` the [`TextEditor`] is drawn
+    /// with, as for placing the caret where the text is clicked.
+    pub fn index_at(&self, text: &Text, offset: Vector) -> usize {
+        let height = text.line_height.to_absolute(text.size).0;
+        let line = (offset.y / height).floor().max(0.0) as usize;
+
+        let Some((start, content)) = self.lines().nth(line).or_else(|| self.lines().last()) else {
+            return 0;
+        };
+
+        let boundaries: Vec<usize> = content
+            .char_indices()
+            .map(|(index, _)| index)
+            .chain([content.len()])
+            .collect();
+
+        // The first boundary past the point, as widths grow along a line
+        let after = boundaries
+            .partition_point(|index| line_width(&content[..*index], text) < offset.x)
+            .min(boundaries.len() - 1);
+
+        let closest = match after.checked_sub(1) {
+            Some(before) => {
+                let left = line_width(&content[..boundaries[before]], text);
+                let right = line_width(&content[..boundaries[after]], text);
+
+                if offset.x - left < right - offset.x {
+                    before
+                } else {
+                    after
+                }
+            }
+            None => after,
+        };
+
+        start + boundaries[closest]
+    }
+
+    /// Draws the text of the [`TextEditor`] on the `buffer` in the style of
+    /// `text`, along with its caret and its selection, highlighted with the
+    /// `selection` color.
+    ///
+    /// The text is laid out from its top-left corner at the position of
+    /// `text`, whose content is replaced.
+    pub fn draw(&self, buffer: &mut Buffer<'_>, text: Text, selection: impl Into<Color>) {
+        let selection = selection.into();
+        let height = text.line_height.to_absolute(text.size).0;
+        let range = self.selection();
+
+        for (line, (start, content)) in self.lines().enumerate() {
+            let end = start + content.len();
+            let top = line as f32 * height;
+
+            let Some(range) = range
+                .clone()
+                .filter(|range| range.start <= end && range.end > start)
+            else {
+                continue;
+            };
+
+            let from = range.start.max(start) - start;
+            let to = range.end.min(end) - start;
+
+            let left = line_width(&content[..from], &text);
+            let mut width = line_width(&content[..to], &text) - left;
+
+            // Selected line breaks show as a sliver
+            if range.end > end {
+                width += text.size.0 / 4.0;
+            }
+
+            buffer.fill_screen_rectangle(
+                text.position,
+                Rectangle::new(Point::new(left, top), Size::new(width, height)),
+                selection,
+            );
+        }
+
+        let (line, (start, content)) = self
+            .lines()
+            .enumerate()
+            .take_while(|(_, (start, _))| *start <= self.caret)
+            .last()
+            .unwrap_or((0, (0, "")));
+
+        let caret = Rectangle::new(
+            Point::new(
+                line_width(&content[..self.caret - start], &text) - CARET_WIDTH / 2.0,
+                line as f32 * height,
+            ),
+            Size::new(CARET_WIDTH, height),
+        );
+
+        buffer.fill_screen_rectangle(text.position, caret, text.color);
+
+        buffer.draw_text(Text {
+            content: self.content.clone(),
+            ..text
+        });
+    }
+
+    /// Returns the lines of the text, along with the position each starts
+    /// at.
+    fn lines(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.content.split('\n').scan(0, |start, line| {
+            let item = (*start, line);
+            *start += line.len() + 1;
+
+            Some(item)
+        })
+    }
+
+    fn previous_char(&self) -> usize {
+        self.content[..self.caret]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_char(&self) -> usize {
+        self.content[self.caret..]
+            .chars()
+            .next()
+            .map_or(self.caret, |c| self.caret + c.len_utf8())
+    }
+
+    /// Returns the start of the word before the caret.
+    fn previous_word(&self) -> usize {
+        let before = self.content[..self.caret].trim_end();
+
+        before.rfind(char::is_whitespace).map_or(0, |index| {
+            index + before[index..].chars().next().map_or(1, char::len_utf8)
+        })
+    }
+
+    /// Returns the end of the word after the caret.
+    fn next_word(&self) -> usize {
+        let after = &self.content[self.caret..];
+        let skipped = after.len() - after.trim_start().len();
+
+        after[skipped..]
+            .find(char::is_whitespace)
+            .map_or(self.content.len(), |index| self.caret + skipped + index)
+    }
+
+    fn line_start(&self, index: usize) -> usize {
+        self.content[..index]
+            .rfind('\n')
+            .map_or(0, |index| index + 1)
+    }
+
+    fn line_end(&self, index: usize) -> usize {
+        self.content[index..]
+            .find('\n')
+            .map_or(self.content.len(), |end| index + end)
+    }
+
+    /// Returns the position in the line `start` begins at with as many
+    /// characters before it as before the caret in its line.
+    fn in_line(&self, start: usize) -> usize {
+        let column = self.content[self.line_start(self.caret)..self.caret]
+            .chars()
+            .count();
+
+        self.content[start..self.line_end(start)]
+            .char_indices()
+            .nth(column)
+            .map_or(self.line_end(start), |(index, _)| start + index)
+    }
+
+    fn line_above(&self) -> usize {
+        match self.line_start(self.caret).checked_sub(1) {
+            Some(end) => self.in_line(self.line_start(end)),
+            None => 0,
+        }
+    }
+
+    fn line_below(&self) -> usize {
+        let end = self.line_end(self.caret);
+
+        match end < self.content.len() {
+            true => self.in_line(end + 1),
+            false => self.content.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use iced::keyboard::{key, Location, Modifiers};
+
+    /// The modifier which moves the caret by words.
+    const JUMP: Modifiers = if cfg!(target_os = "macos") {
+        Modifiers::ALT
+    } else {
+        Modifiers::CTRL
+    };
+
+    /// A clipboard which keeps its contents in memory.
+    #[derive(Default)]
+    struct Memory(Option<String>);
+
+    impl Clipboard for Memory {
+        fn read(&self, _kind: clipboard::Kind) -> Option<String> {
+            self.0.clone()
+        }
+
+        fn write(&mut self, _kind: clipboard::Kind, contents: String) {
+            self.0 = Some(contents);
+        }
+    }
+
+    fn key_press(key: Key, modifiers: Modifiers, text: Option<&str>) -> event::Event {
+        event::Event::Keyboard(keyboard::Event::KeyPressed {
+            modified_key: key.clone(),
+            key,
+            physical_key: key::Physical::Unidentified(key::NativeCode::Unidentified),
+            location: Location::Standard,
+            modifiers,
+            text: text.map(Into::into),
+        })
+    }
+
+    fn press(editor: &mut TextEditor, key: Named, modifiers: Modifiers) -> Update {
+        editor.update(
+            &key_press(Key::Named(key), modifiers, None),
+            &mut Memory::default(),
+        )
+    }
+
+    fn shortcut(editor: &mut TextEditor, character: &str, clipboard: &mut Memory) -> Update {
+        let event = key_press(
+            Key::Character(character.into()),
+            Modifiers::COMMAND,
+            Some(character),
+        );
+
+        editor.update(&event, clipboard)
+    }
+
+    fn type_text(editor: &mut TextEditor, text: &str) -> Update {
+        let event = key_press(Key::Character(text.into()), Modifiers::empty(), Some(text));
+
+        editor.update(&event, &mut Memory::default())
+    }
+
+    #[test]
+    fn caret_moves_over_whole_characters() {
+        let mut editor = TextEditor::new("añ🎉");
+        assert_eq!(editor.caret(), 7);
+
+        press(&mut editor, Named::ArrowLeft, Modifiers::empty());
+        assert_eq!(editor.caret(), 3);
+
+        press(&mut editor, Named::ArrowLeft, Modifiers::empty());
+        assert_eq!(editor.caret(), 1);
+
+        press(&mut editor, Named::ArrowRight, Modifiers::empty());
+        assert_eq!(editor.caret(), 3);
+
+        // Within the emoji, so moved back to its start
+        editor.move_to(5, false);
+        assert_eq!(editor.caret(), 3);
+
+        editor.move_to(100, false);
+        assert_eq!(editor.caret(), 7);
+    }
+
+    #[test]
+    fn typing_and_deleting_keeps_characters_whole() {
+        let mut editor = TextEditor::new("añ");
+
+        assert_eq!(type_text(&mut editor, "é"), Update::Edited);
+        assert_eq!(editor.text(), "añé");
+
+        press(&mut editor, Named::Backspace, Modifiers::empty());
+        press(&mut editor, Named::Backspace, Modifiers::empty());
+        assert_eq!(editor.text(), "a");
+
+        editor.move_to(0, false);
+        press(&mut editor, Named::Delete, Modifiers::empty());
+        assert_eq!(editor.text(), "");
+        assert_eq!(editor.caret(), 0);
+    }
+
+    #[test]
+    fn shift_extends_the_selection() {
+        let mut editor = TextEditor::new("héllo wörld");
+
+        press(&mut editor, Named::ArrowLeft, Modifiers::SHIFT);
+        press(&mut editor, Named::ArrowLeft, Modifiers::SHIFT | JUMP);
+        assert_eq!(editor.selected_text(), Some("wörld"));
+
+        // Collapses to the start of the selection
+        press(&mut editor, Named::ArrowLeft, Modifiers::empty());
+        assert_eq!(editor.selection(), None);
+        assert_eq!(editor.caret(), 7);
+
+        press(&mut editor, Named::Home, Modifiers::SHIFT);
+        assert_eq!(editor.selected_text(), Some("héllo "));
+
+        type_text(&mut editor, "ß");
+        assert_eq!(editor.text(), "ßwörld");
+        assert_eq!(editor.caret(), 2);
+    }
+
+    #[test]
+    fn words_and_lines() {
+        let mut editor = TextEditor::new("ünï cödé\nab");
+
+        editor.move_to(0, false);
+        press(&mut editor, Named::ArrowRight, JUMP);
+        assert_eq!(editor.caret(), "ünï".len());
+
+        // Down keeps the column counted in characters
+        press(&mut editor, Named::ArrowDown, Modifiers::empty());
+        assert_eq!(editor.caret(), editor.text().len());
+
+        press(&mut editor, Named::ArrowUp, Modifiers::empty());
+        assert_eq!(editor.caret(), "ün".len());
+
+        press(&mut editor, Named::End, Modifiers::empty());
+        assert_eq!(editor.caret(), "ünï cödé".len());
+
+        press(&mut editor, Named::Backspace, JUMP);
+        assert_eq!(editor.text(), "ünï \nab");
+    }
+
+    #[test]
+    fn copy_cut_and_paste() {
+        let mut editor = TextEditor::new("añ🎉b");
+        let mut clipboard = Memory::default();
+
+        // Nothing to copy without a selection
+        assert_eq!(shortcut(&mut editor, "c", &mut clipboard), Update::Ignored);
+        assert_eq!(clipboard.0, None);
+
+        editor.move_to(1, false);
+        editor.move_to(7, true);
+
+        assert_eq!(shortcut(&mut editor, "c", &mut clipboard), Update::Copied);
+        assert_eq!(clipboard.0.as_deref(), Some("ñ🎉"));
+        assert_eq!(editor.text(), "añ🎉b");
+
+        assert_eq!(shortcut(&mut editor, "x", &mut clipboard), Update::Edited);
+        assert_eq!(editor.text(), "ab");
+        assert_eq!(editor.caret(), 1);
+
+        assert_eq!(shortcut(&mut editor, "v", &mut clipboard), Update::Edited);
+        assert_eq!(editor.text(), "añ🎉b");
+        assert_eq!(editor.caret(), 7);
+
+        // Control characters other than line breaks are left out
+        clipboard.0 = Some("x\ty\nz".to_string());
+        shortcut(&mut editor, "a", &mut clipboard);
+        shortcut(&mut editor, "v", &mut clipboard);
+        assert_eq!(editor.text(), "xy\nz");
+
+        let mut empty = Memory::default();
+        assert_eq!(shortcut(&mut editor, "v", &mut empty), Update::Ignored);
+    }
+
+    #[test]
+    fn other_events_are_ignored() {
+        let mut editor = TextEditor::new("text");
+        let event = event::Event::Keyboard(keyboard::Event::ModifiersChanged(Modifiers::SHIFT));
+
+        assert_eq!(
+            editor.update(&event, &mut Memory::default()),
+            Update::Ignored
+        );
+        assert_eq!(
+            editor.update(
+                &key_press(Key::Character("b".into()), Modifiers::COMMAND, Some("b")),
+                &mut Memory::default()
+            ),
+            Update::Ignored
+        );
+        assert_eq!(editor.text(), "text");
+    }
+}
@@ -0,0 +1,498 @@
+//! Raster map tiles drawn beneath the items of an [`Infinite`], turning it
+//! into a map.
+//!
+//! Tiles follow the XYZ scheme of OpenStreetMap and most other tile servers,
+//! in the Web Mercator projection. The whole map spans [`WORLD_SIZE`] units
+//! of the canvas, centered at the origin, with north towards the positive Y
+//! axis of an [`Infinite`] with [`YDirection::Up`]. Use [`project`] to place
+//! items at a longitude and latitude.
+//!
+//! The map is always drawn with north at the top. On an [`Infinite`] with
+//! [`YDirection::Down`], north is therefore towards the negative Y axis, and
+//! the Y coordinates of [`project`] and [`unproject`] are negated.
+//!
+//! A [`TileLayer`] keeps the tiles loaded so far and is drawn with
+//! [`Infinite::tiles`]. Tiles are fetched by the application, as in:
+//!
+//! ```ignore
+//! // Whenever the viewport changes
+//! let task = layer.load(viewport, |tile, url| {
+//!     Task::perform(fetch(url), move |handle| Message::TileLoaded(tile, handle))
+//! });
+//!
+//! // Once a tile is fetched
+//! layer.loaded(tile, handle);
+//! ```
+//!
+//! The `tiles` feature enables the `image` feature of iced, which the
+//! renderer needs to draw the tiles.
+//!
+//! [`Infinite`]: crate::Infinite
+//! [`Infinite::tiles`]: crate::Infinite::tiles
+//! [`YDirection::Up`]: crate::YDirection::Up
+//! [`YDirection::Down`]: crate::YDirection::Down
+
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use iced::{advanced::image, Point, Radians, Rectangle, Size, Task};
+
+use crate::canvas::{TileRenderer, Viewport, YDirection};
+
+/// The size, in units of the canvas, of the whole map.
+pub const WORLD_SIZE: f32 = 256.0;
+
+/// The most tiles drawn or requested at once.
+const MAX_TILES: usize = 1024;
+
+/// The most tiles kept loaded before those out of view are dropped.
+const MAX_CACHED_TILES: usize = 512;
+
+/// A tile of the map at a zoom level, counted from the north-west corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TileId {
+    /// The zoom level of the tile, where level 0 is a single tile.
+    pub zoom: u8,
+    /// The column of the tile, counted eastwards.
+    pub x: u32,
+    /// The row of the tile, counted southwards.
+    pub y: u32,
+}
+
+impl TileId {
+    /// Returns the region of the canvas covered by the tile, given by its
+    /// bottom-left corner.
+    pub fn bounds(self) -> Rectangle {
+        let size = tile_size(self.zoom);
+        let half = WORLD_SIZE / 2.0;
+
+        Rectangle::new(
+            Point::new(
+                -half + self.x as f32 * size,
+                half - (self.y + 1) as f32 * size,
+            ),
+            Size::new(size, size),
+        )
+    }
+
+    /// Returns the tile one zoom level lower which contains this one.
+    pub fn parent(self) -> Option<Self> {
+        Some(Self {
+            zoom: self.zoom.checked_sub(1)?,
+            x: self.x / 2,
+            y: self.y / 2,
+        })
+    }
+}
+
+/// A server of map tiles, see [`TileLayer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileSource {
+    /// The URL of a tile, where `{z}`, `{x}` and `{y}` are replaced by the
+    /// zoom level, column and row of the tile.
+    pub url: String,
+    /// The lowest zoom level of the tiles.
+    pub min_zoom: u8,
+    /// The highest zoom level of the tiles.
+    pub max_zoom: u8,
+    /// The width and height, in pixels, of the images of the tiles.
+    pub tile_size: f32,
+}
+
+impl TileSource {
+    /// Creates a new [`TileSource`] with the given `url` template, with zoom
+    /// levels from 0 to 19 and images of 256 pixels.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            min_zoom: 0,
+            max_zoom: 19,
+            tile_size: 256.0,
+        }
+    }
+
+    /// Returns the URL of the `tile`.
+    pub fn url(&self, tile: TileId) -> String {
+        self.url
+            .replace("{z}", &tile.zoom.to_string())
+            .replace("{x}", &tile.x.to_string())
+            .replace("{y}", &tile.y.to_string())
+    }
+
+    /// Returns the zoom level of the tiles best suited to the given zoom of
+    /// the canvas, where the images of the tiles are drawn closest to their
+    /// own size.
+    pub fn zoom_level(&self, zoom: f32) -> u8 {
+        let level = (zoom * WORLD_SIZE / self.tile_size).log2().round();
+        let level = level.clamp(f32::from(self.min_zoom), f32::from(self.max_zoom));
+
+        level as u8
+    }
+}
+
+/// The state of a tile of a [`TileLayer`].
+#[derive(Debug, Clone)]
+enum Tile {
+    Loading,
+    Loaded(image::Handle),
+    Failed,
+}
+
+/// The tiles of a [`TileSource`] loaded so far, drawn beneath the items of
+/// an [`Infinite`](crate::Infinite).
+///
+/// Tiles which are not loaded yet are covered by the nearest loaded tile of
+/// a lower zoom level.
+#[derive(Debug, Clone)]
+pub struct TileLayer {
+    source: TileSource,
+    tiles: HashMap<TileId, Tile>,
+    opacity: f32,
+}
+
+impl TileLayer {
+    /// Creates a new [`TileLayer`] of the tiles of the `source`.
+    pub fn new(source: TileSource) -> Self {
+        Self {
+            source,
+            tiles: HashMap::new(),
+            opacity: 1.0,
+        }
+    }
+
+    /// Sets the opacity the tiles are drawn with.
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Returns the [`TileSource`] of the [`TileLayer`].
+    pub fn source(&self) -> &TileSource {
+        &self.source
+    }
+
+    /// Returns the tiles needed to cover the `viewport`, at the zoom level
+    /// best suited to it.
+    pub fn visible_tiles(&self, viewport: &Viewport) -> Vec<TileId> {
+        let zoom = self
+            .source
+            .zoom_level(viewport.scale.x.min(viewport.scale.y));
+        let visible = upright(viewport, viewport.visible());
+
+        let size = tile_size(zoom);
+        let last = WORLD_SIZE / size - 1.0;
+        let half = WORLD_SIZE / 2.0;
+
+        let column = |x: f32| ((x + half) / size).floor().clamp(0.0, last) as u32;
+        let row = |y: f32| ((half - y) / size).floor().clamp(0.0, last) as u32;
+
+        let within = visible.x + visible.width > -half
+            && visible.x < half
+            && visible.y + visible.height > -half
+            && visible.y < half;
+
+        if !within {
+            return Vec::new();
+        }
+
+        let (left, right) = (column(visible.x), column(visible.x + visible.width));
+        let (top, bottom) = (row(visible.y + visible.height), row(visible.y));
+
+        if (right - left + 1) as usize * (bottom - top + 1) as usize > MAX_TILES {
+            return Vec::new();
+        }
+
+        (top..=bottom)
+            .flat_map(|y| (left..=right).map(move |x| TileId { zoom, x, y }))
+            .collect()
+    }
+
+    /// Requests the tiles needed to cover the `viewport` which are not loaded
+    /// yet, returning the [`Task`]s made by `fetch` from each tile and its
+    /// URL.
+    ///
+    /// Each fetched tile is to be handed back through [`TileLayer::loaded`].
+    /// Tiles already requested are not requested again.
+    pub fn load<Message: 'static>(
+        &mut self,
+        viewport: Viewport,
+        mut fetch: impl FnMut(TileId, String) -> Task<Message>,
+    ) -> Task<Message> {
+        let visible = self.visible_tiles(&viewport);
+
+        if self.tiles.len() > MAX_CACHED_TILES {
+            self.tiles.retain(|tile, state| {
+                matches!(state, Tile::Loading | Tile::Failed) || visible.contains(tile)
+            });
+        }
+
+        let needed: Vec<TileId> = visible
+            .into_iter()
+            .filter(|tile| !self.tiles.contains_key(tile))
+            .collect();
+
+        let tasks: Vec<Task<Message>> = needed
+            .into_iter()
+            .map(|tile| {
+                self.tiles.insert(tile, Tile::Loading);
+                fetch(tile, self.source.url(tile))
+            })
+            .collect();
+
+        Task::batch(tasks)
+    }
+
+    /// Stores the image of a requested `tile`, or `None` if it could not be
+    /// fetched, in which case it is not requested again until the
+    /// [`TileLayer`] is cleared.
+    pub fn loaded(&mut self, tile: TileId, handle: Option<image::Handle>) {
+        let state = match handle {
+            Some(handle) => Tile::Loaded(handle),
+            None => Tile::Failed,
+        };
+
+        let _ = self.tiles.insert(tile, state);
+    }
+
+    /// Drops all tiles loaded so far.
+    pub fn clear(&mut self) {
+        self.tiles.clear();
+    }
+
+    /// Draws the tiles covering the `viewport`, whose bounds are those of the
+    /// pane on the `renderer`.
+    pub(crate) fn draw<Renderer: TileRenderer>(
+        &self,
+        renderer: &mut Renderer,
+        viewport: &Viewport,
+    ) {
+        let loaded = |tile: &TileId| match self.tiles.get(tile) {
+            Some(Tile::Loaded(handle)) => Some(handle),
+            _ => None,
+        };
+
+        let mut shown: Vec<TileId> = Vec::new();
+
+        for tile in self.visible_tiles(viewport) {
+            let cover = std::iter::successors(Some(tile), |tile| tile.parent())
+                .find(|tile| loaded(tile).is_some());
+
+            if let Some(cover) = cover.filter(|cover| !shown.contains(cover)) {
+                shown.push(cover);
+            }
+        }
+
+        // Lower zoom levels go first, beneath the tiles they stand in for
+        shown.sort_by_key(|tile| tile.zoom);
+
+        let rotation = Radians(-viewport.rotation.0);
+
+        for tile in shown {
+            let Some(handle) = loaded(&tile) else {
+                continue;
+            };
+
+            let region = upright(viewport, tile.bounds());
+            let center = viewport.to_screen(region.center());
+            // Slightly enlarged, to hide the seams between tiles
+            let size = Size::new(
+                region.width * viewport.scale.x + 0.5,
+                region.height * viewport.scale.y + 0.5,
+            );
+            let bounds = Rectangle::new(
+                Point::new(center.x - size.width / 2.0, center.y - size.height / 2.0),
+                size,
+            );
+
+            renderer.draw_tile(handle, bounds, rotation, self.opacity);
+        }
+    }
+}
+
+/// Mirrors a `region` of the canvas across the X axis if the Y axis of the
+/// `viewport` points down, so the map is drawn with north at the top.
+fn upright(viewport: &Viewport, region: Rectangle) -> Rectangle {
+    match viewport.y_axis {
+        YDirection::Up => region,
+        YDirection::Down => Rectangle {
+            y: -(region.y + region.height),
+            ..region
+        },
+    }
+}
+
+/// Returns the point of the canvas at the given longitude and latitude, in
+/// degrees.
+pub fn project(longitude: f64, latitude: f64) -> Point {
+    let latitude = latitude.clamp(-85.051_128, 85.051_128).to_radians();
+
+    let x = (longitude + 180.0) / 360.0;
+    let y = (1.0 - (latitude.tan() + 1.0 / latitude.cos()).ln() / PI) / 2.0;
+
+    let size = f64::from(WORLD_SIZE);
+
+    Point::new(
+        (x * size - size / 2.0) as f32,
+        (size / 2.0 - y * size) as f32,
+    )
+}
+
+/// Returns the longitude and latitude, in degrees, of the given point of the
+/// canvas.
+pub fn unproject(point: Point) -> (f64, f64) {
+    let size = f64::from(WORLD_SIZE);
+
+    let x = (f64::from(point.x) + size / 2.0) / size;
+    let y = (size / 2.0 - f64::from(point.y)) / size;
+
+    let longitude = x * 360.0 - 180.0;
+    let latitude = (PI * (1.0 - 2.0 * y)).sinh().atan().to_degrees();
+
+    (longitude, latitude)
+}
+
+/// Returns the size, in units of the canvas, of the tiles at a zoom level.
+fn tile_size(zoom: u8) -> f32 {
+    WORLD_SIZE / 2f32.powi(i32::from(zoom))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use iced::Vector;
+
+    /// Returns the viewport of an unrotated [`Infinite`](crate::Infinite)
+    /// zoomed by `scale`.
+    fn viewport(scale: f32) -> Viewport {
+        Viewport {
+            offset: Vector::ZERO,
+            scale: Vector::new(scale, scale),
+            rotation: Radians(0.0),
+            y_axis: YDirection::Up,
+            bounds: Rectangle::new(Point::ORIGIN, Size::new(400.0, 300.0)),
+        }
+    }
+
+    /// Loads the tiles of the `viewport`, returning those requested.
+    fn requested(layer: &mut TileLayer, viewport: Viewport) -> Vec<TileId> {
+        let mut requested = Vec::new();
+
+        let _ = layer.load(viewport, |tile, _| {
+            requested.push(tile);
+            Task::<()>::none()
+        });
+
+        requested.sort();
+        requested
+    }
+
+    #[test]
+    fn unproject_reverses_project() {
+        for (longitude, latitude) in [(0.0, 0.0), (-0.1276, 51.5072), (151.2093, -33.8688)] {
+            let (x, y) = unproject(project(longitude, latitude));
+
+            assert!((x - longitude).abs() < 1e-3, "{x} != {longitude}");
+            assert!((y - latitude).abs() < 1e-3, "{y} != {latitude}");
+        }
+
+        assert_eq!(project(0.0, 0.0), Point::ORIGIN);
+
+        let corner = project(-180.0, 85.051_128);
+        assert!((corner.x + WORLD_SIZE / 2.0).abs() < 1e-3);
+        assert!((corner.y - WORLD_SIZE / 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn tiles_cover_their_quarter_of_the_parent() {
+        let world = TileId {
+            zoom: 0,
+            x: 0,
+            y: 0,
+        };
+        let north_east = TileId {
+            zoom: 1,
+            x: 1,
+            y: 0,
+        };
+        let south_west = TileId {
+            zoom: 1,
+            x: 0,
+            y: 1,
+        };
+
+        assert_eq!(
+            world.bounds(),
+            Rectangle::new(Point::new(-128.0, -128.0), Size::new(256.0, 256.0))
+        );
+        assert_eq!(
+            north_east.bounds(),
+            Rectangle::new(Point::new(0.0, 0.0), Size::new(128.0, 128.0))
+        );
+        assert_eq!(
+            south_west.bounds(),
+            Rectangle::new(Point::new(-128.0, -128.0), Size::new(128.0, 128.0))
+        );
+
+        assert_eq!(north_east.parent(), Some(world));
+        assert_eq!(world.parent(), None);
+    }
+
+    #[test]
+    fn zoom_level_draws_tiles_closest_to_their_size() {
+        let source = TileSource {
+            min_zoom: 2,
+            max_zoom: 10,
+            ..TileSource::new("{z}/{x}/{y}")
+        };
+
+        assert_eq!(source.zoom_level(4.0), 2);
+        assert_eq!(source.zoom_level(8.0), 3);
+        assert_eq!(source.zoom_level(11.0), 3);
+        assert_eq!(source.zoom_level(13.0), 4);
+        assert_eq!(source.zoom_level(1.0), 2);
+        assert_eq!(source.zoom_level(1e6), 10);
+
+        let layer = TileLayer::new(TileSource::new("{z}/{x}/{y}"));
+        let visible = layer.visible_tiles(&viewport(2.0));
+
+        assert!(!visible.is_empty());
+        assert!(visible.iter().all(|tile| tile.zoom == 1));
+    }
+
+    #[test]
+    fn pruned_tiles_are_fetched_again_but_failed_ones_are_not() {
+        let mut layer = TileLayer::new(TileSource::new("{z}/{x}/{y}"));
+        let world = TileId {
+            zoom: 0,
+            x: 0,
+            y: 0,
+        };
+
+        assert_eq!(requested(&mut layer, viewport(1.0)), vec![world]);
+        assert!(requested(&mut layer, viewport(1.0)).is_empty());
+
+        let handle = image::Handle::from_rgba(1, 1, vec![0; 4]);
+        layer.loaded(world, Some(handle.clone()));
+
+        for x in 0..MAX_CACHED_TILES as u32 {
+            layer.loaded(TileId { zoom: 12, x, y: 0 }, Some(handle.clone()));
+        }
+
+        let failed = TileId {
+            zoom: 12,
+            x: 0,
+            y: 1,
+        };
+        layer.loaded(failed, None);
+
+        let zoomed = requested(&mut layer, viewport(2.0));
+        assert!(!zoomed.is_empty());
+
+        assert!(!layer.tiles.contains_key(&world));
+        assert!(matches!(layer.tiles.get(&failed), Some(Tile::Failed)));
+        assert_eq!(layer.tiles.len(), zoomed.len() + 1);
+
+        assert_eq!(requested(&mut layer, viewport(1.0)), vec![world]);
+    }
+}
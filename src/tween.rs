@@ -0,0 +1,321 @@
+//! Animate the properties of items on an [`Infinite`].
+//!
+//! An [`Animated`] value is a handle for a single property of an item, like
+//! its position, opacity or stroke width. Instead of jumping to a new value,
+//! it glides towards it over time.
+//!
+//! Animations are driven by the redraws of the [`Infinite`]. A [`Program`]
+//! with running animations should report them through
+//! [`Program::is_animating`] so the [`Infinite`] keeps redrawing until they
+//! are done.
+//!
+//! [`Infinite`]: crate::Infinite
+//! [`Program`]: crate::Program
+//! [`Program::is_animating`]: crate::Program::is_animating
+
+use iced::{
+    time::{Duration, Instant},
+    Color, Point, Size, Vector,
+};
+
+use crate::easing::Easing;
+
+const DEFAULT_DURATION: Duration = Duration::from_millis(300);
+
+/// A value which can be linearly interpolated.
+pub trait Interpolate: Copy {
+    /// Returns the value at `t` between `self` and `other`.
+    ///
+    /// `t` is 0.0 at `self` and 1.0 at `other`.
+    fn interpolate(self, other: Self, t: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Interpolate for Point {
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        Point::new(
+            self.x.interpolate(other.x, t),
+            self.y.interpolate(other.y, t),
+        )
+    }
+}
+
+impl Interpolate for Vector {
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        Vector::new(
+            self.x.interpolate(other.x, t),
+            self.y.interpolate(other.y, t),
+        )
+    }
+}
+
+impl Interpolate for Size {
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        Size::new(
+            self.width.interpolate(other.width, t),
+            self.height.interpolate(other.height, t),
+        )
+    }
+}
+
+impl Interpolate for Color {
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        Color {
+            r: self.r.interpolate(other.r, t),
+            g: self.g.interpolate(other.g, t),
+            b: self.b.interpolate(other.b, t),
+            a: self.a.interpolate(other.a, t),
+        }
+    }
+}
+
+/// A sequence of values an [`Animated`] value passes through.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyframes<T> {
+    start: T,
+    frames: Vec<(Duration, T, Easing)>,
+}
+
+impl<T: Interpolate> Keyframes<T> {
+    /// Creates new [`Keyframes`] starting at `start`.
+    pub fn new(start: T) -> Self {
+        Self {
+            start,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Adds a keyframe reached linearly `duration` after the previous one.
+    pub fn then(self, value: T, duration: Duration) -> Self {
+        self.then_eased(value, duration, Easing::Linear)
+    }
+
+    /// Adds a keyframe reached `duration` after the previous one using the
+    /// given [`Easing`].
+    pub fn then_eased(mut self, value: T, duration: Duration, easing: Easing) -> Self {
+        self.frames.push((duration, value, easing));
+        self
+    }
+
+    /// Returns the total duration of the [`Keyframes`].
+    pub fn duration(&self) -> Duration {
+        self.frames.iter().map(|(duration, ..)| *duration).sum()
+    }
+
+    /// Returns the final value of the [`Keyframes`].
+    pub fn last(&self) -> T {
+        self.frames
+            .last()
+            .map(|(_, value, _)| *value)
+            .unwrap_or(self.start)
+    }
+
+    /// Returns the value of the [`Keyframes`] `elapsed` after they started.
+    pub fn sample(&self, elapsed: Duration) -> T {
+        let mut from = self.start;
+        let mut remaining = elapsed;
+
+        for (duration, to, easing) in &self.frames {
+            if remaining < *duration {
+                let t = remaining.as_secs_f32() / duration.as_secs_f32();
+                return from.interpolate(*to, easing.apply(t));
+            }
+
+            remaining -= *duration;
+            from = *to;
+        }
+
+        from
+    }
+}
+
+/// A handle to an animated property of an item.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animated<T> {
+    keyframes: Keyframes<T>,
+    started: Option<Instant>,
+    duration: Duration,
+    easing: Easing,
+}
+
+impl<T: Interpolate> Animated<T> {
+    /// Creates a new [`Animated`] value resting at `value`.
+    pub fn new(value: T) -> Self {
+        Self {
+            keyframes: Keyframes::new(value),
+            started: None,
+            duration: DEFAULT_DURATION,
+            easing: Easing::CubicOut,
+        }
+    }
+
+    /// Sets how long [`Animated::animate_to`] takes to reach its target.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Sets the [`Easing`] [`Animated::animate_to`] reaches its target with.
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Returns the value at `now`.
+    pub fn value(&self, now: Instant) -> T {
+        match self.started {
+            Some(started) => self
+                .keyframes
+                .sample(now.saturating_duration_since(started)),
+            None => self.keyframes.last(),
+        }
+    }
+
+    /// Returns the value the [`Animated`] value is heading towards.
+    pub fn target(&self) -> T {
+        self.keyframes.last()
+    }
+
+    /// Returns true if the [`Animated`] value is still changing at `now`.
+    pub fn is_animating(&self, now: Instant) -> bool {
+        match self.started {
+            Some(started) => now.saturating_duration_since(started) < self.keyframes.duration(),
+            None => false,
+        }
+    }
+
+    /// Glides from the value at `now` to `target`.
+    pub fn animate_to(&mut self, target: T, now: Instant) {
+        let keyframes =
+            Keyframes::new(self.value(now)).then_eased(target, self.duration, self.easing);
+
+        self.play(keyframes, now);
+    }
+
+    /// Plays the given [`Keyframes`] starting at `now`.
+    pub fn play(&mut self, keyframes: Keyframes<T>, now: Instant) {
+        self.keyframes = keyframes;
+        self.started = Some(now);
+    }
+
+    /// Jumps to `value` without animating.
+    pub fn set(&mut self, value: T) {
+        self.keyframes = Keyframes::new(value);
+        self.started = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    fn assert_near(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-4, "{a} is not {b}");
+    }
+
+    #[test]
+    fn interpolate_values() {
+        assert_near(2.0.interpolate(4.0, 0.25), 2.5);
+        assert_eq!(
+            Point::new(0.0, 10.0).interpolate(Point::new(10.0, 0.0), 0.5),
+            Point::new(5.0, 5.0)
+        );
+        assert_eq!(
+            Color::BLACK.interpolate(Color::WHITE, 0.5),
+            Color::from_rgba(0.5, 0.5, 0.5, 1.0)
+        );
+    }
+
+    #[test]
+    fn keyframes_sample_each_frame_in_turn() {
+        let keyframes = Keyframes::new(0.0).then(10.0, millis(100)).then_eased(
+            20.0,
+            millis(200),
+            Easing::QuadIn,
+        );
+
+        assert_eq!(keyframes.duration(), millis(300));
+        assert_eq!(keyframes.last(), 20.0);
+
+        assert_near(keyframes.sample(millis(0)), 0.0);
+        assert_near(keyframes.sample(millis(50)), 5.0);
+        assert_near(keyframes.sample(millis(100)), 10.0);
+        // Halfway through the eased frame is a quarter of the way along it
+        assert_near(keyframes.sample(millis(200)), 12.5);
+        assert_near(keyframes.sample(millis(300)), 20.0);
+        assert_near(keyframes.sample(millis(1000)), 20.0);
+    }
+
+    #[test]
+    fn keyframes_without_frames_stay_at_the_start() {
+        let keyframes = Keyframes::new(3.0);
+
+        assert_eq!(keyframes.duration(), Duration::ZERO);
+        assert_eq!(keyframes.last(), 3.0);
+        assert_eq!(keyframes.sample(millis(100)), 3.0);
+    }
+
+    #[test]
+    fn animated_glides_to_its_target() {
+        let now = Instant::now();
+        let mut opacity = Animated::new(0.0)
+            .duration(millis(100))
+            .easing(Easing::Linear);
+
+        assert!(!opacity.is_animating(now));
+        assert_eq!(opacity.value(now), 0.0);
+
+        opacity.animate_to(1.0, now);
+
+        assert_eq!(opacity.target(), 1.0);
+        assert!(opacity.is_animating(now + millis(50)));
+        assert_near(opacity.value(now + millis(50)), 0.5);
+        assert!(!opacity.is_animating(now + millis(100)));
+        assert_near(opacity.value(now + millis(150)), 1.0);
+    }
+
+    #[test]
+    fn animated_retargets_from_its_current_value() {
+        let now = Instant::now();
+        let mut width = Animated::new(0.0)
+            .duration(millis(100))
+            .easing(Easing::Linear);
+
+        width.animate_to(10.0, now);
+        width.animate_to(0.0, now + millis(50));
+
+        assert_near(width.value(now + millis(50)), 5.0);
+        assert_near(width.value(now + millis(100)), 2.5);
+        assert_near(width.value(now + millis(150)), 0.0);
+    }
+
+    #[test]
+    fn animated_plays_keyframes_and_jumps() {
+        let now = Instant::now();
+        let mut position = Animated::new(Point::ORIGIN);
+
+        position.play(
+            Keyframes::new(Point::ORIGIN)
+                .then(Point::new(10.0, 0.0), millis(100))
+                .then(Point::new(10.0, 10.0), millis(100)),
+            now,
+        );
+
+        assert_eq!(position.value(now + millis(150)), Point::new(10.0, 5.0));
+        assert!(position.is_animating(now + millis(150)));
+
+        position.set(Point::new(-1.0, -1.0));
+
+        assert!(!position.is_animating(now + millis(150)));
+        assert_eq!(position.value(now), Point::new(-1.0, -1.0));
+    }
+}